@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum DlmmCpiError {
+    #[msg("Position account does not match the PDA derived from lb_pair, base, lower_bin_id and width")]
+    InvalidPositionPda,
+
+    #[msg("Existing position bin range does not match the requested range")]
+    PositionRangeMismatch,
+
+    #[msg("Bin array account does not match the PDA derived from lb_pair and bin id")]
+    InvalidBinArrayPda,
+
+    #[msg("Swap did not consume any input token or return any output token")]
+    ZeroSwapAmount,
+
+    #[msg("Reward index is out of range")]
+    InvalidRewardIndex,
+
+    #[msg("Reward mint does not match the pool's reward info")]
+    RewardMintMismatch,
+
+    #[msg("Reward vault does not match the pool's reward info")]
+    RewardVaultMismatch,
+
+    #[msg("Basis points value must not exceed 10000")]
+    InvalidBps,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Amount received is below the minimum amount out")]
+    SlippageExceeded,
+
+    #[msg("Pool reserves did not change consistently with the swap")]
+    ReserveInvariantViolated,
+
+    #[msg("Position does not belong to the given pool")]
+    PositionLbPairMismatch,
+
+    #[msg("Position width must be between 1 and 70 bins")]
+    InvalidPositionWidth,
+
+    #[msg("Bin is outside the position's range")]
+    BinOutOfPositionRange,
+
+    #[msg("Oracle account has no valid latest observation")]
+    InvalidOracle,
+
+    #[msg("Latest oracle observation is older than the maximum staleness")]
+    OracleStale,
+
+    #[msg("Bin liquidity distribution contains the same bin more than once")]
+    DuplicateBin,
+
+    #[msg("Pool activation type is neither slot nor timestamp")]
+    InvalidActivationType,
+
+    #[msg("Reserve account does not match the pool's reserve")]
+    ReserveMismatch,
+
+    #[msg("Batch size is zero or exceeds its limit")]
+    InvalidBatchSize,
+
+    #[msg("reserve_x and reserve_y are swapped")]
+    ReservesSwapped,
+
+    #[msg("Bin array bitmap extension is missing or does not match the PDA derived from lb_pair")]
+    InvalidBitmapExtension,
+
+    #[msg("No bin arrays were passed in the remaining accounts")]
+    NoBinArrays,
+
+    #[msg("Account is not owned by the DLMM program")]
+    AccountNotOwnedByDlmm,
+
+    #[msg("Bin is on the wrong side of the active bin for the deposited token")]
+    InvalidBinForSide,
+
+    #[msg("Bin liquidity distribution is empty")]
+    EmptyDistribution,
+
+    #[msg("Token mint is neither the pool's token X nor token Y mint")]
+    MintNotInPool,
+
+    #[msg("Time since the position was last updated is shorter than the lookback window")]
+    AprWindowTooShort,
+
+    #[msg("Position holds no liquidity")]
+    ZeroPositionValue,
+
+    #[msg("Amount received by the reserve after the transfer fee is below the minimum")]
+    TransferFeeTooHigh,
+
+    #[msg("Amount deposited is below the minimum")]
+    DepositBelowMinimum,
+
+    #[msg("from_bin_id must not be greater than to_bin_id")]
+    InvalidBinRange,
+
+    #[msg("Number of bin arrays passed does not match the bin arrays the range needs")]
+    BinArrayCountMismatch,
+
+    #[msg("Strategy bin range is not within the position")]
+    InvalidStrategyRange,
+
+    #[msg("Position still has liquidity; remove it before closing")]
+    PositionNotEmpty,
+
+    #[msg("Reward accounts must be passed all together or not at all")]
+    IncompleteRewardAccounts,
+
+    #[msg("Token account mint does not match the expected token mint")]
+    UserTokenMintMismatch,
+
+    #[msg("Event authority does not match the DLMM program's event authority PDA")]
+    InvalidEventAuthority,
+
+    #[msg("Bin step and base factor are not accepted by the DLMM program")]
+    InvalidBinStep,
+
+    #[msg("min_amount_out is zero, which disables slippage protection")]
+    ZeroMinOut,
+
+    #[msg("Token program does not own the token mint")]
+    TokenProgramMismatch,
+
+    #[msg("Remaining accounts are not whole groups of the expected size")]
+    InvalidRemainingAccounts,
+
+    #[msg("Output token account does not hold the pool token opposite the input")]
+    SwapDirectionMismatch,
+
+    #[msg("Distribution shape is not uniform (0), linear (1) or concentrated (2)")]
+    InvalidDistributionShape,
+
+    #[msg("Position owner does not match the expected owner")]
+    PositionOwnerMismatch,
+
+    #[msg("Position discriminator is neither the V1 nor the V2 position layout")]
+    UnknownPositionLayout,
+
+    #[msg("Mint account does not match the pool's mint for that side")]
+    PoolMintMismatch,
+
+    #[msg("Token mint is owned by neither the Token nor the Token-2022 program")]
+    UnknownTokenProgram,
+
+    #[msg("Not enough compute units remain for the next step of the instruction")]
+    InsufficientCompute,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Bin list is empty")]
+    EmptyBinList,
+
+    #[msg("Mint has a transfer hook, whose accounts the DLMM swap cannot pass")]
+    TransferHookUnsupported,
+
+    #[msg("Not enough liquidity in the bin arrays for the swap")]
+    InsufficientLiquidity,
+
+    #[msg("Too many bins for a single instruction")]
+    TooManyBins,
+
+    #[msg("Receiver token account is not owned by the fee owner")]
+    FeeOwnerMismatch,
+
+    #[msg("Bin weight exceeds the maximum weight per bin")]
+    BinWeightTooHigh,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes_are_distinct() {
+        let errors = [
+            DlmmCpiError::InvalidPositionPda,
+            DlmmCpiError::PositionRangeMismatch,
+            DlmmCpiError::InvalidBinArrayPda,
+            DlmmCpiError::ZeroSwapAmount,
+            DlmmCpiError::InvalidRewardIndex,
+            DlmmCpiError::RewardMintMismatch,
+            DlmmCpiError::RewardVaultMismatch,
+            DlmmCpiError::InvalidBps,
+            DlmmCpiError::MathOverflow,
+            DlmmCpiError::SlippageExceeded,
+            DlmmCpiError::ReserveInvariantViolated,
+            DlmmCpiError::PositionLbPairMismatch,
+            DlmmCpiError::InvalidPositionWidth,
+            DlmmCpiError::BinOutOfPositionRange,
+            DlmmCpiError::InvalidOracle,
+            DlmmCpiError::OracleStale,
+            DlmmCpiError::DuplicateBin,
+            DlmmCpiError::InvalidActivationType,
+            DlmmCpiError::ReserveMismatch,
+            DlmmCpiError::InvalidBatchSize,
+            DlmmCpiError::ReservesSwapped,
+            DlmmCpiError::InvalidBitmapExtension,
+            DlmmCpiError::NoBinArrays,
+            DlmmCpiError::AccountNotOwnedByDlmm,
+            DlmmCpiError::InvalidBinForSide,
+            DlmmCpiError::EmptyDistribution,
+            DlmmCpiError::MintNotInPool,
+            DlmmCpiError::AprWindowTooShort,
+            DlmmCpiError::ZeroPositionValue,
+            DlmmCpiError::TransferFeeTooHigh,
+            DlmmCpiError::DepositBelowMinimum,
+            DlmmCpiError::InvalidBinRange,
+            DlmmCpiError::BinArrayCountMismatch,
+            DlmmCpiError::InvalidStrategyRange,
+            DlmmCpiError::PositionNotEmpty,
+            DlmmCpiError::IncompleteRewardAccounts,
+            DlmmCpiError::UserTokenMintMismatch,
+            DlmmCpiError::InvalidEventAuthority,
+            DlmmCpiError::InvalidBinStep,
+            DlmmCpiError::ZeroMinOut,
+            DlmmCpiError::TokenProgramMismatch,
+            DlmmCpiError::InvalidRemainingAccounts,
+            DlmmCpiError::SwapDirectionMismatch,
+            DlmmCpiError::InvalidDistributionShape,
+            DlmmCpiError::PositionOwnerMismatch,
+            DlmmCpiError::UnknownPositionLayout,
+            DlmmCpiError::PoolMintMismatch,
+            DlmmCpiError::UnknownTokenProgram,
+            DlmmCpiError::InsufficientCompute,
+            DlmmCpiError::InvalidAmount,
+            DlmmCpiError::EmptyBinList,
+            DlmmCpiError::TransferHookUnsupported,
+            DlmmCpiError::InsufficientLiquidity,
+            DlmmCpiError::TooManyBins,
+            DlmmCpiError::FeeOwnerMismatch,
+            DlmmCpiError::BinWeightTooHigh,
+        ];
+
+        let codes: HashSet<u32> = errors.into_iter().map(u32::from).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert!(codes.iter().all(|code| *code >= ERROR_CODE_OFFSET));
+    }
+}
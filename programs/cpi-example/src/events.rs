@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Emitted after liquidity is added to a DLMM position through this program.
+#[event]
+pub struct LiquidityAddedEvent {
+    pub position: Pubkey,
+    pub lb_pair: Pubkey,
+    pub sender: Pubkey,
+    /// Token X taken from the user, in base units.
+    pub amount_x: u64,
+    /// Token Y taken from the user, in base units.
+    pub amount_y: u64,
+}
+
+/// Emitted after liquidity is removed from a DLMM position through this
+/// program.
+#[event]
+pub struct LiquidityRemovedEvent {
+    pub position: Pubkey,
+    pub lb_pair: Pubkey,
+    pub sender: Pubkey,
+    /// Token X received by the user, in base units.
+    pub amount_x: u64,
+    /// Token Y received by the user, in base units.
+    pub amount_y: u64,
+    /// Number of bins liquidity was removed from.
+    pub bin_count: u32,
+}
+
+/// Emitted after a DLMM position is closed through this program.
+#[event]
+pub struct PositionClosedEvent {
+    pub position: Pubkey,
+    pub lb_pair: Pubkey,
+    pub sender: Pubkey,
+    pub rent_receiver: Pubkey,
+    /// Lamports of the closed position returned to `rent_receiver`.
+    pub rent_reclaimed: u64,
+}
@@ -0,0 +1,65 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Activation schedule of a DLMM pool, returned by `dlmm_activation_info`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActivationInfo {
+    /// Unit of `activation_point`: 0 for a slot, 1 for a unix timestamp in
+    /// seconds.
+    pub activation_type: u8,
+    /// Slot or timestamp from which the pool accepts swaps.
+    pub activation_point: u64,
+    /// Current slot or timestamp, in the same unit as `activation_point`.
+    pub current_slot_or_time: u64,
+    /// Slots or seconds left until activation. 0 once the pool is active.
+    pub remaining: u64,
+}
+
+#[derive(Accounts)]
+pub struct DlmmActivationInfo<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Reads when a Meteora DLMM pool opens for swaps.
+///
+/// `activation_type` decides how `activation_point` is compared: slot based
+/// pools (0) against `Clock::slot`, timestamp based pools (1) against
+/// `Clock::unix_timestamp`. The remaining slots or seconds are computed from
+/// the current clock and the Borsh serialized `ActivationInfo` is written
+/// with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidActivationType` if the pool has an unknown activation type.
+pub fn handle_dlmm_activation_info(ctx: Context<DlmmActivationInfo>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+    let clock = Clock::get()?;
+
+    let current_slot_or_time = match lb_pair.activation_type {
+        0 => clock.slot,
+        1 => u64::try_from(clock.unix_timestamp).map_err(|_| DlmmCpiError::MathOverflow)?,
+        _ => return err!(DlmmCpiError::InvalidActivationType),
+    };
+
+    let info = ActivationInfo {
+        activation_type: lb_pair.activation_type,
+        activation_point: lb_pair.activation_point,
+        current_slot_or_time,
+        remaining: lb_pair
+            .activation_point
+            .saturating_sub(current_slot_or_time),
+    };
+
+    set_return_data(&info.try_to_vec()?);
+
+    Ok(())
+}
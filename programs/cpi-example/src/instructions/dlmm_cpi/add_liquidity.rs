@@ -0,0 +1,214 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityAddedEvent;
+use crate::utils::{
+    has_duplicate_bins, load_dlmm_account, verify_dlmm_owned, verify_event_authority,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmAddLiquidity<'info> {
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token X from.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token Y from.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns user_token_x and user_token_y. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Adds two-sided liquidity to a Meteora DLMM position.
+///
+/// Both token X and token Y are deposited in one instruction, split across
+/// bins according to `liquidity_parameter.bin_liquidity_dist`.
+///
+/// Emits `LiquidityAddedEvent` with the amounts taken from the user once
+/// the CPI succeeds.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `liquidity_parameter` - Amounts and per-bin distribution to deposit:
+///   - `amount_x` / `amount_y`: Total amounts of token X and Y, in base units.
+///   - `bin_liquidity_dist`: For each bin, `distribution_x` and
+///     `distribution_y` are the shares of `amount_x` and `amount_y` it
+///     receives, in basis points. Across all bins, the `distribution_x`
+///     values must sum to at most 10000 and so must the `distribution_y`
+///     values, which keeps each sum within the u16 range.
+///
+///   Rules for bin_id selection:
+///   - Bins below active_id only take token Y, so their `distribution_x` must be 0
+///   - Bins above active_id only take token X, so their `distribution_y` must be 0
+///   - The active bin takes both tokens
+///   - All bin_ids must fall within [position.lower_bin_id, position.upper_bin_id]
+///   - Each bin_id may appear only once
+/// * `active_id` - The active bin ID observed off-chain prior to building
+///   the transaction.
+/// * `max_active_bin_slippage` - Maximum allowed bin ID deviation of the
+///   pool's active bin from `active_id` at execution time. A deposit
+///   straddling the active bin is split between the two tokens around it, so
+///   a moved price changes what each bin takes.
+/// * `min_deposit_x` / `min_deposit_y` - Optional minimum amounts of token X
+///   and Y that must leave the user's token accounts. If the price moved
+///   since the transaction was built, the DLMM program can take a different
+///   split of the two tokens than intended; the minimums bound that shift.
+///   Pass None to skip the check for a token.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if both amounts are 0, `DuplicateBin` if a bin_id appears more than once, `SlippageExceeded` if
+/// the active bin moved more than `max_active_bin_slippage` from
+/// `active_id`, or with `DepositBelowMinimum` if less than `min_deposit_x` of token X or
+/// `min_deposit_y` of token Y was deposited.
+pub fn handle_dlmm_add_liquidity(
+    ctx: Context<DlmmAddLiquidity>,
+    liquidity_parameter: dlmm::types::LiquidityParameter,
+    active_id: i32,
+    max_active_bin_slippage: i32,
+    min_deposit_x: Option<u64>,
+    min_deposit_y: Option<u64>,
+) -> Result<()> {
+    require!(
+        liquidity_parameter.amount_x > 0 || liquidity_parameter.amount_y > 0,
+        DlmmCpiError::InvalidAmount
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        !has_duplicate_bins(
+            liquidity_parameter
+                .bin_liquidity_dist
+                .iter()
+                .map(|dist| dist.bin_id)
+        ),
+        DlmmCpiError::DuplicateBin
+    );
+
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        require!(
+            (i64::from(lb_pair.active_id) - i64::from(active_id)).abs()
+                <= i64::from(max_active_bin_slippage),
+            DlmmCpiError::SlippageExceeded
+        );
+    }
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidity {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity(cpi_context, liquidity_parameter)?;
+
+    let amount_x = x_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_x)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = y_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_y)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    require!(
+        amount_x >= min_deposit_x.unwrap_or_default()
+            && amount_y >= min_deposit_y.unwrap_or_default(),
+        DlmmCpiError::DepositBelowMinimum
+    );
+
+    emit!(LiquidityAddedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use super::add_liquidity::DlmmAddLiquidity;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityAddedEvent;
+use crate::utils::{load_dlmm_account, verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// Adds liquidity to a Meteora DLMM position following a predefined shape.
+///
+/// Instead of per-bin weights, the deposit is described by a bin range and
+/// a strategy: Spot (uniform), Curve (concentrated around the active bin)
+/// or BidAsk (concentrated away from the active bin). Uses the same
+/// accounts as `dlmm_add_liquidity`.
+///
+/// BidAsk is the one shape checked against the position here. It puts the
+/// most liquidity in the bins furthest from the active bin, i.e. at the
+/// edges of `[min_bin_id, max_bin_id]`. If that range extends past the
+/// position, the heaviest bins are the ones that fall outside it, so the
+/// deposit ends up nothing like the intended shape. For BidAsk strategies
+/// the range must therefore lie within the position's bins.
+///
+/// Emits `LiquidityAddedEvent` with the amounts taken from the user once
+/// the CPI succeeds.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `liquidity_parameter` - Amounts, slippage and strategy of the deposit:
+///   - `amount_x` / `amount_y`: Total amounts of token X and Y, in base units.
+///   - `active_id`: The active bin ID observed off-chain prior to building
+///     the transaction.
+///   - `max_active_bin_slippage`: Maximum allowed bin ID deviation from
+///     `active_id` at execution time.
+///   - `strategy_parameters`: `min_bin_id`, `max_bin_id` and `strategy_type`.
+///     `parameteres` is reserved and should be zeroed.
+///
+///   The parameters are passed through to the DLMM program unchanged. A
+///   single bin range (`min_bin_id == max_bin_id`) and a range entirely on
+///   one side of `active_id` are both valid; in the latter case only the
+///   token for that side is deposited.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if both amounts are 0, or with `InvalidStrategyRange` if a BidAsk strategy range is not within the
+/// position's bins.
+pub fn handle_dlmm_add_liquidity_by_strategy(
+    ctx: Context<DlmmAddLiquidity>,
+    liquidity_parameter: dlmm::types::LiquidityParameterByStrategy,
+) -> Result<()> {
+    require!(
+        liquidity_parameter.amount_x > 0 || liquidity_parameter.amount_y > 0,
+        DlmmCpiError::InvalidAmount
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let strategy = &liquidity_parameter.strategy_parameters;
+    if matches!(
+        strategy.strategy_type,
+        dlmm::types::StrategyType::BidAskOneSide
+            | dlmm::types::StrategyType::BidAskBalanced
+            | dlmm::types::StrategyType::BidAskImBalanced
+    ) {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+        require!(
+            strategy.min_bin_id >= position.lower_bin_id
+                && strategy.max_bin_id <= position.upper_bin_id
+                && strategy.min_bin_id <= strategy.max_bin_id,
+            DlmmCpiError::InvalidStrategyRange
+        );
+    }
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityByStrategy {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity_by_strategy(cpi_context, liquidity_parameter)?;
+
+    let amount_x = x_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_x)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = y_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_y)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    emit!(LiquidityAddedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use super::add_liquidity::DlmmAddLiquidity;
+use super::add_liquidity_by_strategy::handle_dlmm_add_liquidity_by_strategy;
+use crate::dlmm;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+
+/// Parameters of `dlmm_add_liquidity_by_strategy_auto`: those of
+/// `dlmm_add_liquidity_by_strategy` without the observed active bin, which
+/// is read from the pool instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidityParameterByStrategyAuto {
+    /// Total amount of token X to deposit, in base units.
+    pub amount_x: u64,
+    /// Total amount of token Y to deposit, in base units.
+    pub amount_y: u64,
+    /// `min_bin_id`, `max_bin_id` and `strategy_type` of the deposit.
+    /// `parameteres` is reserved and should be zeroed.
+    pub strategy_parameters: dlmm::types::StrategyParameters,
+}
+
+impl LiquidityParameterByStrategyAuto {
+    /// Completes the parameters with the pool's `active_id`.
+    pub fn with_active_id(
+        self,
+        active_id: i32,
+        max_active_bin_slippage: i32,
+    ) -> dlmm::types::LiquidityParameterByStrategy {
+        dlmm::types::LiquidityParameterByStrategy {
+            amount_x: self.amount_x,
+            amount_y: self.amount_y,
+            active_id,
+            max_active_bin_slippage,
+            strategy_parameters: self.strategy_parameters,
+        }
+    }
+}
+
+/// Adds liquidity to a Meteora DLMM position following a predefined shape,
+/// with the active bin read on-chain instead of passed by the caller.
+///
+/// `dlmm_add_liquidity_by_strategy` takes the active bin the caller
+/// observed when building the transaction, and the DLMM program rejects
+/// the deposit if the pool's active bin has since moved more than
+/// `max_active_bin_slippage` bins. That observation goes stale while the
+/// transaction waits to land, failing deposits that would have been fine.
+/// Here the active bin is read from `lb_pair` in the same instruction, just
+/// before the CPI, so there is no window for it to go stale and the DLMM
+/// program's check passes.
+///
+/// The DLMM program always shapes the deposit around the active bin at
+/// execution, so the deposit is the same as with an up to date observation.
+/// The flip side is that a price move before the transaction lands no
+/// longer fails it: the bin range is not shifted, and the split between
+/// token X and Y follows wherever the active bin is. Use
+/// `dlmm_add_liquidity_by_strategy` with the observed bin when that matters.
+///
+/// Uses the same accounts as `dlmm_add_liquidity` and emits
+/// `LiquidityAddedEvent` the same way.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `liquidity_parameter` - Amounts and strategy of the deposit.
+/// * `max_slippage` - Maximum allowed bin ID deviation from the active bin
+///   read, passed to the DLMM program as `max_active_bin_slippage`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure, failing as
+/// `dlmm_add_liquidity_by_strategy` does.
+pub fn handle_dlmm_add_liquidity_by_strategy_auto(
+    ctx: Context<DlmmAddLiquidity>,
+    liquidity_parameter: LiquidityParameterByStrategyAuto,
+    max_slippage: i32,
+) -> Result<()> {
+    let active_id = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.active_id;
+
+    handle_dlmm_add_liquidity_by_strategy(
+        ctx,
+        liquidity_parameter.with_active_id(active_id, max_slippage),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_active_id() {
+        let liquidity_parameter = LiquidityParameterByStrategyAuto {
+            amount_x: 100,
+            amount_y: 200,
+            strategy_parameters: dlmm::types::StrategyParameters {
+                min_bin_id: -5,
+                max_bin_id: 5,
+                strategy_type: dlmm::types::StrategyType::SpotBalanced,
+                parameteres: [0; 64],
+            },
+        };
+
+        let filled = liquidity_parameter.with_active_id(-2, 3);
+
+        assert_eq!(filled.amount_x, 100);
+        assert_eq!(filled.amount_y, 200);
+        assert_eq!(filled.active_id, -2);
+        assert_eq!(filled.max_active_bin_slippage, 3);
+        assert_eq!(filled.strategy_parameters.min_bin_id, -5);
+        assert_eq!(filled.strategy_parameters.max_bin_id, 5);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::dlmm;
+use crate::utils::{pda_cpi_context, verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(position_owner_bump: u8)]
+pub struct DlmmAddLiquidityOneSideSigned<'info> {
+    #[account(mut)]
+    /// CHECK: The position account. Must be owned by position_owner.
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Token account of position_owner for the token being deposited.
+    /// Tokens are transferred FROM this account into the pool reserve.
+    pub user_token: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for the token being deposited.
+    /// Use lb_pair.reserve_x for token X deposits, lb_pair.reserve_y for token Y.
+    pub reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of the token being deposited.
+    /// Must match lb_pair.token_x_mint or lb_pair.token_y_mint.
+    pub token_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the position and of user_token. PDA of this program,
+    /// signs the CPI.
+    /// PDA: ["position_owner"], with the bump passed in instruction data.
+    #[account(
+        seeds = [b"position_owner"],
+        bump = position_owner_bump
+    )]
+    pub position_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Only admin can move liquidity of positions owned by the program.
+    #[account(
+        constraint = crate::assert_eq_admin(cpi_example_admin.key())
+    )]
+    pub cpi_example_admin: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of the mint being deposited.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Adds single-sided liquidity to a Meteora DLMM position owned by this
+/// program, from a token account of the same owner.
+///
+/// The program-signed counterpart of `dlmm_add_liquidity_one_side`:
+/// `position_owner` signs both as the position owner and as the authority
+/// of `user_token`. See `dlmm_remove_liquidity_signed` for the seed scheme
+/// and how the bump is checked.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `position_owner_bump` - Bump of the `position_owner` PDA.
+/// * `liquidity_parameter` - Amount, slippage and per-bin weights of the
+///   deposit, following the same rules as `dlmm_add_liquidity_one_side`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ConstraintSeeds` if `position_owner` is not the PDA of the bump.
+pub fn handle_dlmm_add_liquidity_one_side_signed(
+    ctx: Context<DlmmAddLiquidityOneSideSigned>,
+    position_owner_bump: u8,
+    liquidity_parameter: dlmm::types::LiquidityOneSideParameter,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityOneSide {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token: ctx.accounts.user_token.to_account_info(),
+        reserve: ctx.accounts.reserve.to_account_info(),
+        token_mint: ctx.accounts.token_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.position_owner.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let seeds = [b"position_owner".as_ref(), &[position_owner_bump]];
+
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = pda_cpi_context(
+        ctx.accounts.dlmm_program.to_account_info(),
+        accounts,
+        signer_seeds,
+    );
+
+    dlmm::cpi::add_liquidity_one_side(cpi_context, liquidity_parameter)
+}
@@ -0,0 +1,249 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityAddedEvent;
+use crate::utils::{
+    bin_array_index, derive_bin_array_bitmap_extension, is_overflow_default_bin_array_bitmap,
+    require_compute_units, verify_bin_array, verify_dlmm_owned, verify_event_authority,
+    ADD_LIQUIDITY_COMPUTE_UNITS, INITIALIZE_BIN_ARRAY_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmAddLiquiditySmart<'info> {
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool.
+    /// PDA: ["bitmap", lb_pair]
+    /// Required when the deposit range has a bin array outside the main bitmap
+    /// range (bin array index outside -512..=511), created here if missing.
+    /// Pass None otherwise.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token X from.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token Y from.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The bin array containing min_bin_id, created here if missing.
+    /// PDA: ["bin_array", lb_pair, floor(min_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The bin array containing max_bin_id, created here if missing.
+    /// PDA: ["bin_array", lb_pair, floor(max_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the range fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns user_token_x and user_token_y. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pays the rent for any bin array or bitmap extension created.
+    /// May be the same account as sender.
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Adds liquidity by strategy to a Meteora DLMM position, creating whatever
+/// the deposit range is missing first.
+///
+/// A deposit into a range nobody has used before fails in the DLMM program
+/// because its bin arrays don't exist, and for ranges far from the pool's
+/// initial price the bitmap extension may not exist either. This instruction
+/// creates them with `payer` funding the rent, then deposits, so the client
+/// doesn't have to know which accounts are missing.
+///
+/// Worst case, the bitmap extension and two bin arrays are created: three
+/// extra CPIs plus the rent for one extension and two bin arrays. The
+/// deposit alone is close to the 200k default compute unit limit for wide
+/// ranges, so request a higher limit with `ComputeBudgetInstruction` when
+/// accounts may be created. Existing accounts are skipped and cost nothing.
+///
+/// Emits `LiquidityAddedEvent` with the amounts taken from the user once
+/// the deposit succeeds.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `params` - Amounts, slippage and strategy of the deposit. Same as
+///   `dlmm_add_liquidity_by_strategy`; `strategy_parameters.min_bin_id` and
+///   `max_bin_id` determine which bin arrays are required.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if both amounts are 0, `InvalidBinArrayPda` if the bin
+/// arrays don't cover the range, or with
+/// `InvalidBitmapExtension` if the range needs the bitmap extension and it
+/// is missing or not the pool's PDA.
+pub fn handle_dlmm_add_liquidity_smart(
+    ctx: Context<DlmmAddLiquiditySmart>,
+    params: dlmm::types::LiquidityParameterByStrategy,
+) -> Result<()> {
+    require!(
+        params.amount_x > 0 || params.amount_y > 0,
+        DlmmCpiError::InvalidAmount
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+
+    let lb_pair = ctx.accounts.lb_pair.key();
+    let lower_index = bin_array_index(params.strategy_parameters.min_bin_id);
+    let upper_index = bin_array_index(params.strategy_parameters.max_bin_id);
+
+    verify_bin_array(lb_pair, ctx.accounts.bin_array_lower.key(), lower_index)?;
+    verify_bin_array(lb_pair, ctx.accounts.bin_array_upper.key(), upper_index)?;
+
+    if is_overflow_default_bin_array_bitmap(lower_index)
+        || is_overflow_default_bin_array_bitmap(upper_index)
+    {
+        let bitmap_extension = ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .ok_or(DlmmCpiError::InvalidBitmapExtension)?;
+
+        require_keys_eq!(
+            bitmap_extension.key(),
+            derive_bin_array_bitmap_extension(lb_pair).0,
+            DlmmCpiError::InvalidBitmapExtension
+        );
+
+        if bitmap_extension.data_is_empty() {
+            require_compute_units(INITIALIZE_BIN_ARRAY_COMPUTE_UNITS)?;
+
+            let accounts = dlmm::cpi::accounts::InitializeBinArrayBitmapExtension {
+                lb_pair: ctx.accounts.lb_pair.to_account_info(),
+                bin_array_bitmap_extension: bitmap_extension.to_account_info(),
+                funder: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            };
+
+            let cpi_context =
+                CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+            dlmm::cpi::initialize_bin_array_bitmap_extension(cpi_context)?;
+        }
+    }
+
+    for (bin_array, index) in [
+        (&ctx.accounts.bin_array_lower, lower_index),
+        (&ctx.accounts.bin_array_upper, upper_index),
+    ] {
+        // Covers bin_array_upper being the same account as bin_array_lower
+        if !bin_array.data_is_empty() {
+            continue;
+        }
+
+        require_compute_units(INITIALIZE_BIN_ARRAY_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::InitializeBinArray {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array: bin_array.to_account_info(),
+            funder: ctx.accounts.payer.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::initialize_bin_array(cpi_context, index)?;
+    }
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    require_compute_units(ADD_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityByStrategy {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity_by_strategy(cpi_context, params)?;
+
+    let amount_x = x_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_x)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = y_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_y)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    emit!(LiquidityAddedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair,
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+    });
+
+    Ok(())
+}
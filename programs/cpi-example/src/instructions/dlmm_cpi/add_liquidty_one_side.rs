@@ -1,5 +1,12 @@
 use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityAddedEvent;
+use crate::utils::{
+    has_duplicate_bins, load_dlmm_account, transfer_fee_for_sent_amount, verify_dlmm_owned,
+    verify_event_authority, MAX_BINS_PER_ADD,
+};
 use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
 
 #[derive(Accounts)]
 pub struct DlmmAddLiquidityOneSide<'info> {
@@ -25,7 +32,7 @@ pub struct DlmmAddLiquidityOneSide<'info> {
 
     #[account(mut)]
     /// CHECK: The pool's reserve vault for the token being deposited.
-    /// Use lb_pair.reserve_x for token X deposits, lb_pair.reserve_y for token Y.
+    /// Must be lb_pair.reserve_x for token X deposits, lb_pair.reserve_y for token Y.
     pub reserve: UncheckedAccount<'info>,
 
     /// CHECK: Mint of the token being deposited.
@@ -54,8 +61,8 @@ pub struct DlmmAddLiquidityOneSide<'info> {
     /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
     pub event_authority: UncheckedAccount<'info>,
 
-    /// CHECK: Token program of the mint being deposited.
-    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    /// CHECK: Token program of the mint being deposited. Must be the owner of
+    /// token_mint, Token (spl-token) or Token-2022.
     pub token_program: UncheckedAccount<'info>,
 }
 
@@ -65,6 +72,9 @@ pub struct DlmmAddLiquidityOneSide<'info> {
 /// liquidity exclusively to bins above the active price (for token X) or
 /// below (for token Y).
 ///
+/// Emits `LiquidityAddedEvent` with the amount taken from the user once the
+/// CPI succeeds.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all required accounts.
@@ -82,17 +92,144 @@ pub struct DlmmAddLiquidityOneSide<'info> {
 ///   - Token X deposits: all bin_ids must be strictly > active_id
 ///   - Token Y deposits: all bin_ids must be <= active_id
 ///   - All bin_ids must fall within [position.lower_bin_id, position.upper_bin_id]
+///   - Each bin_id may appear only once
+///   - At most `MAX_BINS_PER_ADD` (70) entries
+/// * `min_net_amount` - Optional minimum amount the reserve must receive.
+///   For Token-2022 mints with a transfer fee, `amount` is the gross
+///   transfer and the reserve receives `amount` minus the fee, which is
+///   what the distribution actually spreads. The expected net amount is
+///   logged before the CPI. Pass None to skip the check.
+/// * `max_weight_per_bin` - Optional cap on the weight of any single entry
+///   of `bin_liquidity_dist`. A bin receives `weight / total_weight` of the
+///   deposit, where `total_weight` is the sum of all weights, so the cap
+///   bounds each bin's share at `max_weight_per_bin / total_weight`. For
+///   example, 10 bins with a total weight of 1000 and a cap of 200 keep
+///   every bin at or below 20% of `amount`. The weights are checked as
+///   passed, since this wrapper does not rescale them. Pass None to skip
+///   the check.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating success or failure.
+/// Returns a `Result` indicating success or failure. The rules above are
+/// checked before the CPI: fails with `InvalidAmount` if `amount` is 0,
+/// `TooManyBins` if `bin_liquidity_dist` has more than `MAX_BINS_PER_ADD`
+/// entries, `EmptyDistribution` if it is empty, `DuplicateBin` if a
+/// bin_id appears more than once, `BinWeightTooHigh` if a weight exceeds
+/// `max_weight_per_bin`, `MintNotInPool` if `token_mint` is neither of the pool's
+/// mints, `ReserveMismatch` if `reserve` is not the pool's reserve of that
+/// mint, `TokenProgramMismatch` if `token_program` does not own the mint,
+/// `InvalidBinForSide` if a bin_id is on the wrong side of `active_id` for
+/// the deposited token, and `BinOutOfPositionRange` if a bin_id is outside
+/// the position. Fails with `TransferFeeTooHigh` if the
+/// net amount after the current epoch's transfer fee is below
+/// `min_net_amount`.
 pub fn handle_dlmm_add_liquidity_one_side(
     ctx: Context<DlmmAddLiquidityOneSide>,
     amount: u64,
     active_id: i32,
     max_active_bin_slippage: i32,
     bin_liquidity_dist: Vec<dlmm::types::BinLiquidityDistributionByWeight>,
+    min_net_amount: Option<u64>,
+    max_weight_per_bin: Option<u16>,
 ) -> Result<()> {
+    require!(amount > 0, DlmmCpiError::InvalidAmount);
+    require!(
+        bin_liquidity_dist.len() <= MAX_BINS_PER_ADD,
+        DlmmCpiError::TooManyBins
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        !bin_liquidity_dist.is_empty(),
+        DlmmCpiError::EmptyDistribution
+    );
+    require!(
+        !has_duplicate_bins(bin_liquidity_dist.iter().map(|dist| dist.bin_id)),
+        DlmmCpiError::DuplicateBin
+    );
+
+    if let Some(max_weight_per_bin) = max_weight_per_bin {
+        require!(
+            bin_liquidity_dist
+                .iter()
+                .all(|dist| dist.weight <= max_weight_per_bin),
+            DlmmCpiError::BinWeightTooHigh
+        );
+    }
+
+    let deposit_is_x = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        let token_mint = ctx.accounts.token_mint.key();
+        let (deposit_is_x, reserve) = if token_mint == lb_pair.token_x_mint {
+            (true, lb_pair.reserve_x)
+        } else if token_mint == lb_pair.token_y_mint {
+            (false, lb_pair.reserve_y)
+        } else {
+            return err!(DlmmCpiError::MintNotInPool);
+        };
+
+        require_keys_eq!(
+            ctx.accounts.reserve.key(),
+            reserve,
+            DlmmCpiError::ReserveMismatch
+        );
+        require_keys_eq!(
+            *ctx.accounts.token_mint.owner,
+            ctx.accounts.token_program.key(),
+            DlmmCpiError::TokenProgramMismatch
+        );
+
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+        for dist in bin_liquidity_dist.iter() {
+            let on_side = if deposit_is_x {
+                dist.bin_id > active_id
+            } else {
+                dist.bin_id <= active_id
+            };
+            require!(on_side, DlmmCpiError::InvalidBinForSide);
+            require!(
+                (position.lower_bin_id..=position.upper_bin_id).contains(&dist.bin_id),
+                DlmmCpiError::BinOutOfPositionRange
+            );
+        }
+
+        deposit_is_x
+    };
+
+    let net_amount = if ctx.accounts.token_program.key() == anchor_spl::token_2022::ID {
+        let transfer_fee = transfer_fee_for_sent_amount(
+            &ctx.accounts.token_mint.to_account_info(),
+            amount,
+            Clock::get()?.epoch,
+        )?;
+        let net_amount = amount
+            .checked_sub(transfer_fee)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        msg!(
+            "Transfer fee: {}, expected net amount in reserve: {}",
+            transfer_fee,
+            net_amount
+        );
+        net_amount
+    } else {
+        amount
+    };
+
+    if let Some(min_net_amount) = min_net_amount {
+        require!(
+            net_amount >= min_net_amount,
+            DlmmCpiError::TransferFeeTooHigh
+        );
+    }
+
+    let balance_before = accessor::amount(&ctx.accounts.user_token)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
     let accounts = dlmm::cpi::accounts::AddLiquidityOneSide {
         position: ctx.accounts.position.to_account_info(),
         lb_pair: ctx.accounts.lb_pair.to_account_info(),
@@ -122,5 +259,24 @@ pub fn handle_dlmm_add_liquidity_one_side(
     let cpi_context =
         CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
 
-    dlmm::cpi::add_liquidity_one_side(cpi_context, liquidity_parameter)
+    dlmm::cpi::add_liquidity_one_side(cpi_context, liquidity_parameter)?;
+
+    let amount = balance_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let (amount_x, amount_y) = if deposit_is_x {
+        (amount, 0)
+    } else {
+        (0, amount)
+    };
+
+    emit!(LiquidityAddedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+    });
+
+    Ok(())
 }
\ No newline at end of file
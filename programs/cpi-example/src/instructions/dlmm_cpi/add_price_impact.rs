@@ -0,0 +1,107 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, price_from_bin_id, verify_bin_array, verify_dlmm_owned,
+    x_value_share_bps,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+#[derive(Accounts)]
+pub struct DlmmAddPriceImpact<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The bin array containing the pool's active bin. Read only.
+    /// PDA: ["bin_array", lb_pair, floor(active_id / 70)]
+    pub bin_array: UncheckedAccount<'info>,
+}
+
+/// Estimates how much a prospective strategy deposit would shift the
+/// composition of a Meteora DLMM pool's active bin, without executing it.
+///
+/// Prices in DLMM are fixed per bin, so a deposit never moves the active
+/// bin's price. What it changes is the active bin's mix of token X and Y,
+/// which is what the next swaps trade against: a large one-sided deposit
+/// into the active bin leaves it mostly one token, so swaps in one direction
+/// cross into the next bin sooner. The impact is measured as the change in
+/// token X's share of the active bin's value, valued at the active bin
+/// price, in basis points.
+///
+/// The deposit is modelled like a Spot strategy: `amount_x` spread evenly
+/// over the bins from the active bin to `max_bin_id`, and `amount_y` over
+/// the bins from `min_bin_id` to the active bin. Curve strategies put more
+/// into the active bin and BidAsk less, so for those the result is only an
+/// indication. A range that doesn't include the active bin, or an empty
+/// active bin, has no composition to move and returns 0.
+///
+/// The impact is written as a little-endian `u64` with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `params` - The deposit to evaluate, as it would be passed to
+///   `dlmm_add_liquidity_by_strategy`. The pool's current active bin is
+///   used; `active_id` and `max_active_bin_slippage` are ignored.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidStrategyRange` if `min_bin_id` is above `max_bin_id`, or with
+/// `InvalidBinArrayPda` if `bin_array` doesn't contain the active bin.
+pub fn handle_dlmm_add_price_impact(
+    ctx: Context<DlmmAddPriceImpact>,
+    params: dlmm::types::LiquidityParameterByStrategy,
+) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.bin_array)?;
+
+    let strategy = &params.strategy_parameters;
+    require!(
+        strategy.min_bin_id <= strategy.max_bin_id,
+        DlmmCpiError::InvalidStrategyRange
+    );
+
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+    let active_id = lb_pair.active_id;
+
+    verify_bin_array(
+        ctx.accounts.lb_pair.key(),
+        ctx.accounts.bin_array.key(),
+        bin_array_index(active_id),
+    )?;
+
+    let bin_array = load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array)?;
+    let bin_offset = i64::from(active_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+    let bin = &bin_array.bins[bin_offset as usize];
+
+    let price = price_from_bin_id(active_id, lb_pair.bin_step).ok_or(DlmmCpiError::MathOverflow)?;
+
+    let impact_bps = if (strategy.min_bin_id..=strategy.max_bin_id).contains(&active_id) {
+        // Both counts include the active bin, so neither is zero
+        let x_bins = u64::from((strategy.max_bin_id - active_id + 1).unsigned_abs());
+        let y_bins = u64::from((active_id - strategy.min_bin_id + 1).unsigned_abs());
+
+        let amount_x = u128::from(bin.amount_x);
+        let amount_y = u128::from(bin.amount_y);
+
+        match x_value_share_bps(amount_x, amount_y, price) {
+            Some(share_before) => {
+                let share_after = x_value_share_bps(
+                    amount_x + u128::from(params.amount_x / x_bins),
+                    amount_y + u128::from(params.amount_y / y_bins),
+                    price,
+                )
+                .ok_or(DlmmCpiError::MathOverflow)?;
+
+                share_after.abs_diff(share_before)
+            }
+            None => 0,
+        }
+    } else {
+        0
+    };
+
+    set_return_data(&impact_bps.to_le_bytes());
+
+    Ok(())
+}
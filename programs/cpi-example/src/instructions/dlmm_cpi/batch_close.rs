@@ -0,0 +1,164 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::PositionClosedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, require_compute_units, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority, CLOSE_POSITION_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+
+/// Remaining accounts per position: position, bin_array_lower, bin_array_upper.
+pub const BATCH_CLOSE_GROUP_LEN: usize = 3;
+
+/// Maximum number of positions closed in one call. Closing is cheap, so the
+/// limit is the transaction size: 8 groups are 24 accounts, which with the
+/// fixed accounts still fit a legacy transaction when no bin array is
+/// shared between positions.
+pub const MAX_BATCH_CLOSE_POSITIONS: u8 = 8;
+
+#[derive(Accounts)]
+pub struct DlmmBatchClose<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside every
+    /// position and bin array of the batch.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The authority that owns every position. Must sign the transaction.
+    /// Receives the reclaimed rent when rent_receiver is None.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The account that receives the reclaimed rent of every position
+    /// closed. Must be writable. Pass None to refund sender.
+    pub rent_receiver: Option<UncheckedAccount<'info>>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+    // Positions are passed using remaining accounts, each as the writable
+    // group [position, bin_array_lower, bin_array_upper]
+}
+
+/// Closes several empty Meteora DLMM positions of the same pool in one
+/// instruction, reclaiming their rent.
+///
+/// Each position is passed in the remaining accounts as a group of three
+/// writable accounts: the position, then its lower and upper bin arrays as
+/// in `dlmm_close_position`. One `close_position` CPI is issued per group,
+/// in order, all refunding the same `rent_receiver`, or `sender` when it is
+/// `None`. Positions sharing a bin array can pass the same account in each
+/// group; a transaction lists it only once.
+///
+/// A position that still holds liquidity is skipped with a logged note
+/// instead of failing the batch, so a batch assembled from a stale view of
+/// the positions still closes the ones that are empty. Remove the
+/// liquidity first with `dlmm_batch_remove_liquidity`.
+///
+/// Emits one `PositionClosedEvent` per position closed.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `count` - Number of positions passed, from 1 to
+///   `MAX_BATCH_CLOSE_POSITIONS` (8).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBatchSize` if `count` is 0 or above `MAX_BATCH_CLOSE_POSITIONS`,
+/// `InvalidRemainingAccounts` if the remaining accounts are not `count`
+/// groups of three, `PositionLbPairMismatch` if a position belongs to
+/// another pool, and `InvalidBinArrayPda` if a group's bin arrays don't
+/// cover its position.
+pub fn handle_dlmm_batch_close<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmBatchClose<'info>>,
+    count: u8,
+) -> Result<()> {
+    require!(
+        (1..=MAX_BATCH_CLOSE_POSITIONS).contains(&count),
+        DlmmCpiError::InvalidBatchSize
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        ctx.remaining_accounts.len() == usize::from(count) * BATCH_CLOSE_GROUP_LEN,
+        DlmmCpiError::InvalidRemainingAccounts
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let rent_receiver = ctx.accounts.rent_receiver.as_ref().map_or_else(
+        || ctx.accounts.sender.to_account_info(),
+        |account| account.to_account_info(),
+    );
+
+    for group in ctx.remaining_accounts.chunks_exact(BATCH_CLOSE_GROUP_LEN) {
+        let [position, bin_array_lower, bin_array_upper] = group else {
+            unreachable!()
+        };
+
+        verify_dlmm_owned(position)?;
+        verify_dlmm_owned(bin_array_lower)?;
+        verify_dlmm_owned(bin_array_upper)?;
+
+        let is_empty = {
+            let position = load_dlmm_account::<dlmm::accounts::PositionV2>(position)?;
+            require_keys_eq!(
+                position.lb_pair,
+                lb_pair_key,
+                DlmmCpiError::PositionLbPairMismatch
+            );
+            verify_bin_array(
+                lb_pair_key,
+                bin_array_lower.key(),
+                bin_array_index(position.lower_bin_id),
+            )?;
+            verify_bin_array(
+                lb_pair_key,
+                bin_array_upper.key(),
+                bin_array_index(position.upper_bin_id),
+            )?;
+
+            position.liquidity_shares.iter().all(|share| *share == 0)
+        };
+
+        if !is_empty {
+            msg!("Skipping position {} with liquidity left", position.key());
+            continue;
+        }
+
+        let rent_reclaimed = position.lamports();
+
+        require_compute_units(CLOSE_POSITION_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::ClosePosition {
+            position: position.clone(),
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array_lower: bin_array_lower.clone(),
+            bin_array_upper: bin_array_upper.clone(),
+            sender: ctx.accounts.sender.to_account_info(),
+            rent_receiver: rent_receiver.clone(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.dlmm_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::close_position(cpi_context)?;
+
+        emit!(PositionClosedEvent {
+            position: position.key(),
+            lb_pair: lb_pair_key,
+            sender: ctx.accounts.sender.key(),
+            rent_receiver: rent_receiver.key(),
+            rent_reclaimed,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{set_return_data, MAX_RETURN_DATA};
+
+/// Summary of one position, returned by `dlmm_batch_read_positions`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PositionSummary {
+    /// Owner of the position.
+    pub owner: Pubkey,
+    /// The lowest bin ID covered by the position.
+    pub lower_bin_id: i32,
+    /// The highest bin ID covered by the position.
+    pub upper_bin_id: i32,
+    /// Sum of the position's liquidity shares over all its bins.
+    pub total_shares: u128,
+}
+
+/// Borsh size of a `PositionSummary`: owner, lower and upper bin id, shares.
+const POSITION_SUMMARY_LEN: usize = 32 + 4 + 4 + 16;
+
+/// Maximum number of positions whose summaries fit in the return data,
+/// after the 4 byte length prefix of the Borsh `Vec`.
+pub const MAX_BATCH_POSITIONS: usize = (MAX_RETURN_DATA - 4) / POSITION_SUMMARY_LEN;
+
+#[derive(Accounts)]
+pub struct DlmmBatchReadPositions {}
+
+/// Reads the summaries of several Meteora DLMM positions in one call.
+///
+/// The first `count` remaining accounts are read as positions. The result
+/// is written with `set_return_data` as a Borsh serialized
+/// `Vec<PositionSummary>`, in the order of the accounts: a little-endian
+/// `u32` length followed by 56 bytes per position (owner, `lower_bin_id`,
+/// `upper_bin_id` as little-endian `i32` and `total_shares` as
+/// little-endian `u128`).
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `count` - Number of positions to read, at most `MAX_BATCH_POSITIONS` (18).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBatchSize` if `count` exceeds `MAX_BATCH_POSITIONS`, and with
+/// `AccountNotEnoughKeys` if fewer than `count` remaining accounts are passed.
+pub fn handle_dlmm_batch_read_positions(
+    ctx: Context<DlmmBatchReadPositions>,
+    count: u8,
+) -> Result<()> {
+    let count = usize::from(count);
+    require!(count <= MAX_BATCH_POSITIONS, DlmmCpiError::InvalidBatchSize);
+    require!(
+        count <= ctx.remaining_accounts.len(),
+        ErrorCode::AccountNotEnoughKeys
+    );
+
+    let summaries = ctx.remaining_accounts[..count]
+        .iter()
+        .map(|account| {
+            let position = load_dlmm_account::<dlmm::accounts::PositionV2>(account)?;
+            Ok(PositionSummary {
+                owner: position.owner,
+                lower_bin_id: position.lower_bin_id,
+                upper_bin_id: position.upper_bin_id,
+                total_shares: position
+                    .liquidity_shares
+                    .iter()
+                    .try_fold(0u128, |total, share| total.checked_add(*share))
+                    .ok_or(DlmmCpiError::MathOverflow)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    set_return_data(&summaries.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_batch_positions_fits_return_data() {
+        let summary = PositionSummary {
+            owner: Pubkey::new_unique(),
+            lower_bin_id: -443_636,
+            upper_bin_id: -443_567,
+            total_shares: u128::MAX,
+        };
+        assert_eq!(summary.try_to_vec().unwrap().len(), POSITION_SUMMARY_LEN);
+
+        let summaries = vec![summary; MAX_BATCH_POSITIONS];
+        assert!(summaries.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+
+        let summaries = vec![summary; MAX_BATCH_POSITIONS + 1];
+        assert!(summaries.try_to_vec().unwrap().len() > MAX_RETURN_DATA);
+    }
+}
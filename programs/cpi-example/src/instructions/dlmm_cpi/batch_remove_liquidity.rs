@@ -0,0 +1,210 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityRemovedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, require_compute_units, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority, verify_reserves, REMOVE_LIQUIDITY_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// Remaining accounts per position: position, bin_array_lower, bin_array_upper.
+pub const BATCH_REMOVE_GROUP_LEN: usize = 3;
+
+/// Maximum number of positions drained in one call. Draining a full 70 bin
+/// position costs up to ~250k compute units, so 4 of them stay within the
+/// 1.4M transaction limit along with the checks around each CPI.
+pub const MAX_BATCH_REMOVE_POSITIONS: usize = 4;
+
+#[derive(Accounts)]
+pub struct DlmmBatchRemoveLiquidity<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside every
+    /// position and bin array of the batch.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive all withdrawn token X.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive all withdrawn token Y.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns every position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+    // Positions are passed using remaining accounts, each as the writable
+    // group [position, bin_array_lower, bin_array_upper]
+}
+
+/// Removes all liquidity from several Meteora DLMM positions of the same pool
+/// in one instruction.
+///
+/// Each position is passed in the remaining accounts as a group of three
+/// writable accounts: the position, then its lower and upper bin arrays as
+/// in `dlmm_remove_all_liquidity`. One `remove_all_liquidity` CPI is issued
+/// per group, in order, all paying out to `user_token_x` and `user_token_y`.
+///
+/// At most `MAX_BATCH_REMOVE_POSITIONS` (4) positions are accepted, which
+/// keeps a batch of full width positions within the compute budget. Request
+/// a 1.4M compute unit limit for a full batch.
+///
+/// Emits one `LiquidityRemovedEvent` per position with the amounts that
+/// position returned. The positions stay open; close them with
+/// `dlmm_close_position` to reclaim rent.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidRemainingAccounts` if the remaining accounts are not whole groups
+/// of three, `InvalidBatchSize` if there are no groups or more than
+/// `MAX_BATCH_REMOVE_POSITIONS`, `ReservesSwapped` or `ReserveMismatch` for
+/// the wrong reserves, `PositionLbPairMismatch` if a position belongs to
+/// another pool, and `InvalidBinArrayPda` if a group's bin arrays don't
+/// cover its position.
+pub fn handle_dlmm_batch_remove_liquidity<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmBatchRemoveLiquidity<'info>>,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let groups = ctx.remaining_accounts.chunks_exact(BATCH_REMOVE_GROUP_LEN);
+    require!(
+        groups.remainder().is_empty(),
+        DlmmCpiError::InvalidRemainingAccounts
+    );
+    require!(
+        (1..=MAX_BATCH_REMOVE_POSITIONS).contains(&groups.len()),
+        DlmmCpiError::InvalidBatchSize
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+    }
+
+    for group in groups {
+        let [position, bin_array_lower, bin_array_upper] = group else {
+            unreachable!()
+        };
+
+        verify_dlmm_owned(position)?;
+        verify_dlmm_owned(bin_array_lower)?;
+        verify_dlmm_owned(bin_array_upper)?;
+
+        let bin_count = {
+            let position = load_dlmm_account::<dlmm::accounts::PositionV2>(position)?;
+            require_keys_eq!(
+                position.lb_pair,
+                lb_pair_key,
+                DlmmCpiError::PositionLbPairMismatch
+            );
+            verify_bin_array(
+                lb_pair_key,
+                bin_array_lower.key(),
+                bin_array_index(position.lower_bin_id),
+            )?;
+            verify_bin_array(
+                lb_pair_key,
+                bin_array_upper.key(),
+                bin_array_index(position.upper_bin_id),
+            )?;
+
+            (position.upper_bin_id - position.lower_bin_id + 1) as u32
+        };
+
+        let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+        let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+        require_compute_units(REMOVE_LIQUIDITY_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::RemoveAllLiquidity {
+            position: position.clone(),
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array_bitmap_extension: ctx
+                .accounts
+                .bin_array_bitmap_extension
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            user_token_x: ctx.accounts.user_token_x.to_account_info(),
+            user_token_y: ctx.accounts.user_token_y.to_account_info(),
+            reserve_x: ctx.accounts.reserve_x.to_account_info(),
+            reserve_y: ctx.accounts.reserve_y.to_account_info(),
+            token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+            token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+            bin_array_lower: bin_array_lower.clone(),
+            bin_array_upper: bin_array_upper.clone(),
+            sender: ctx.accounts.sender.to_account_info(),
+            token_x_program: ctx.accounts.token_x_program.to_account_info(),
+            token_y_program: ctx.accounts.token_y_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.dlmm_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::remove_all_liquidity(cpi_context)?;
+
+        let amount_x = accessor::amount(&ctx.accounts.user_token_x)?
+            .checked_sub(x_before)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        let amount_y = accessor::amount(&ctx.accounts.user_token_y)?
+            .checked_sub(y_before)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+
+        emit!(LiquidityRemovedEvent {
+            position: position.key(),
+            lb_pair: lb_pair_key,
+            sender: ctx.accounts.sender.key(),
+            amount_x,
+            amount_y,
+            bin_count,
+        });
+    }
+
+    Ok(())
+}
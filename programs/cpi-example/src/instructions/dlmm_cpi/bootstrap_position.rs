@@ -0,0 +1,186 @@
+use super::initialize_position::position_upper_bin_id;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    derive_bin_array_bitmap_extension, is_overflow_default_bin_array_bitmap, load_dlmm_account,
+    require_compute_units, required_bin_array_indices, verify_bin_array, verify_event_authority,
+    INITIALIZE_BIN_ARRAY_COMPUTE_UNITS, INITIALIZE_POSITION_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmBootstrapPosition<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for every account created.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position account. A keypair that must sign the transaction,
+    /// whether or not the position already exists.
+    pub position: Signer<'info>,
+
+    /// CHECK: The pool account the position belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the range has bin arrays outside the main bitmap (index below
+    /// -512 or above 511). Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner of the position. Must sign the transaction.
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+    // Bin arrays of the position's range need to be passed using remaining
+    // accounts, writable and in ascending order
+}
+
+/// Creates everything a first deposit into a Meteora DLMM position needs,
+/// in the order the DLMM program expects: the bin array bitmap extension
+/// if the range needs it, the range's bin arrays, then the position.
+///
+/// Accounts that already exist are skipped rather than created again, so
+/// the instruction can be retried, or called for a range whose bin arrays
+/// other positions already created. An account counts as existing once it
+/// holds data. An existing position must belong to `lb_pair` and cover
+/// exactly the requested range.
+///
+/// The bin arrays are passed as writable remaining accounts, one per bin
+/// array index from `floor(lower_bin_id / 70)` to `floor(upper_bin_id / 70)`
+/// in ascending order, each being the PDA `["bin_array", lb_pair, index]`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position. May be negative.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `width` is out of range,
+/// `BinArrayCountMismatch` if the number of remaining accounts doesn't match
+/// the bin arrays of the range, `InvalidBinArrayPda` if one of them is not
+/// the expected PDA, `InvalidBitmapExtension` if the range needs the bitmap
+/// extension and it is missing or not the pool's PDA, or
+/// `PositionLbPairMismatch` or `PositionRangeMismatch` if the position
+/// already exists for another pool or range.
+pub fn handle_dlmm_bootstrap_position<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmBootstrapPosition<'info>>,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let upper_bin_id = position_upper_bin_id(lower_bin_id, width)?;
+    let indices = required_bin_array_indices(lower_bin_id, upper_bin_id);
+
+    require!(
+        ctx.remaining_accounts.len() == indices.len(),
+        DlmmCpiError::BinArrayCountMismatch
+    );
+
+    let lb_pair = ctx.accounts.lb_pair.key();
+
+    for (bin_array, index) in ctx.remaining_accounts.iter().zip(&indices) {
+        verify_bin_array(lb_pair, bin_array.key(), *index)?;
+    }
+
+    if indices
+        .iter()
+        .any(|index| is_overflow_default_bin_array_bitmap(*index))
+    {
+        let bitmap_extension = ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .ok_or(DlmmCpiError::InvalidBitmapExtension)?;
+
+        require_keys_eq!(
+            bitmap_extension.key(),
+            derive_bin_array_bitmap_extension(lb_pair).0,
+            DlmmCpiError::InvalidBitmapExtension
+        );
+
+        if bitmap_extension.data_is_empty() {
+            require_compute_units(INITIALIZE_BIN_ARRAY_COMPUTE_UNITS)?;
+
+            let accounts = dlmm::cpi::accounts::InitializeBinArrayBitmapExtension {
+                lb_pair: ctx.accounts.lb_pair.to_account_info(),
+                bin_array_bitmap_extension: bitmap_extension.to_account_info(),
+                funder: ctx.accounts.payer.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            };
+
+            let cpi_context =
+                CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+            dlmm::cpi::initialize_bin_array_bitmap_extension(cpi_context)?;
+        }
+    }
+
+    for (bin_array, index) in ctx.remaining_accounts.iter().zip(indices) {
+        if !bin_array.data_is_empty() {
+            continue;
+        }
+
+        require_compute_units(INITIALIZE_BIN_ARRAY_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::InitializeBinArray {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array: bin_array.clone(),
+            funder: ctx.accounts.payer.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::initialize_bin_array(cpi_context, index)?;
+    }
+
+    if !ctx.accounts.position.data_is_empty() {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+        require_keys_eq!(
+            position.lb_pair,
+            lb_pair,
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        require!(
+            position.lower_bin_id == lower_bin_id && position.upper_bin_id == upper_bin_id,
+            DlmmCpiError::PositionRangeMismatch
+        );
+
+        return Ok(());
+    }
+
+    require_compute_units(INITIALIZE_POSITION_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::InitializePosition {
+        payer: ctx.accounts.payer.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position(cpi_context, lower_bin_id, width)
+}
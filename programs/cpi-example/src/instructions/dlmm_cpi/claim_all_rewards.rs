@@ -0,0 +1,171 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmClaimAllRewards<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account. Its reward infos must match the reward mints
+    /// and vaults of both reward sets.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's vault for reward 0. Must match lb_pair.reward_infos[0].vault.
+    pub reward_vault_0: UncheckedAccount<'info>,
+
+    /// CHECK: The mint of reward 0. Must match lb_pair.reward_infos[0].mint.
+    pub reward_mint_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive reward 0.
+    pub user_token_account_0: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of the reward 0 mint.
+    /// Use Token (spl-token) or Token-2022 depending on the reward mint.
+    pub token_program_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's vault for reward 1. Must match lb_pair.reward_infos[1].vault.
+    /// Pass None, along with the rest of the reward 1 set, if the pool has
+    /// only one active reward.
+    pub reward_vault_1: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The mint of reward 1. Must match lb_pair.reward_infos[1].mint.
+    pub reward_mint_1: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive reward 1.
+    pub user_token_account_1: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token program of the reward 1 mint.
+    /// Use Token (spl-token) or Token-2022 depending on the reward mint.
+    pub token_program_1: Option<UncheckedAccount<'info>>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Accounts of one reward slot passed to `claim_reward`.
+struct RewardAccounts<'info> {
+    reward_vault: AccountInfo<'info>,
+    reward_mint: AccountInfo<'info>,
+    user_token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+/// Claims both farming rewards accrued by a Meteora DLMM position.
+///
+/// Pools have `NUM_REWARDS` (2) reward slots, each claimed with its own
+/// `claim_reward`. This instruction CPIs `claim_reward` for reward 0 and then
+/// reward 1, so both are claimed in a single transaction. For a pool with
+/// only one active reward, pass None for the whole reward 1 set and only
+/// reward 0 is claimed.
+///
+/// Both reward sets are checked against the pool's reward infos before
+/// either CPI, like `dlmm_claim_reward` does for a single reward.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `IncompleteRewardAccounts` if only part of the reward 1 set is passed,
+/// or with `RewardMintMismatch` or `RewardVaultMismatch` if a reward set
+/// doesn't match the pool.
+pub fn handle_dlmm_claim_all_rewards(ctx: Context<DlmmClaimAllRewards>) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let mut rewards = vec![RewardAccounts {
+        reward_vault: ctx.accounts.reward_vault_0.to_account_info(),
+        reward_mint: ctx.accounts.reward_mint_0.to_account_info(),
+        user_token_account: ctx.accounts.user_token_account_0.to_account_info(),
+        token_program: ctx.accounts.token_program_0.to_account_info(),
+    }];
+
+    match (
+        &ctx.accounts.reward_vault_1,
+        &ctx.accounts.reward_mint_1,
+        &ctx.accounts.user_token_account_1,
+        &ctx.accounts.token_program_1,
+    ) {
+        (Some(reward_vault), Some(reward_mint), Some(user_token_account), Some(token_program)) => {
+            rewards.push(RewardAccounts {
+                reward_vault: reward_vault.to_account_info(),
+                reward_mint: reward_mint.to_account_info(),
+                user_token_account: user_token_account.to_account_info(),
+                token_program: token_program.to_account_info(),
+            })
+        }
+        (None, None, None, None) => {}
+        _ => return err!(DlmmCpiError::IncompleteRewardAccounts),
+    }
+
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+        for (reward, reward_info) in rewards.iter().zip(lb_pair.reward_infos.iter()) {
+            require_keys_eq!(
+                reward.reward_mint.key(),
+                reward_info.mint,
+                DlmmCpiError::RewardMintMismatch
+            );
+            require_keys_eq!(
+                reward.reward_vault.key(),
+                reward_info.vault,
+                DlmmCpiError::RewardVaultMismatch
+            );
+        }
+    }
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    for (reward_index, reward) in (0u64..).zip(rewards) {
+        let accounts = dlmm::cpi::accounts::ClaimReward {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+            bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+            sender: ctx.accounts.sender.to_account_info(),
+            reward_vault: reward.reward_vault,
+            reward_mint: reward.reward_mint,
+            user_token_account: reward.user_token_account,
+            token_program: reward.token_program,
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.dlmm_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::claim_reward(cpi_context, reward_index)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmClaimFee<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token account to receive the token X fee. Any token account of
+    /// token_x_mint, not necessarily owned by sender.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token account to receive the token Y fee. Any token account of
+    /// token_y_mint, not necessarily owned by sender.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of both mints. `claim_fee` takes a single token
+    /// program, so token X and token Y must use the same one.
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+}
+
+/// Claims the swap fees accrued by a Meteora DLMM position.
+///
+/// Fees accrue per bin, to the bins the position holds liquidity in while
+/// swaps cross them, so only bins within the position's range contribute.
+/// Both token X and token Y fees are sent to `user_token_x` and
+/// `user_token_y`.
+///
+/// The fee accounts don't have to belong to the position owner: fees can be
+/// routed to a treasury or any other token account of the right mint, which
+/// is checked here. The DLMM program only requires the position owner (or
+/// operator) to sign, so that signature is what authorizes sending the fees
+/// elsewhere. Programs building this instruction on behalf of users must
+/// choose the destination accounts themselves rather than accept them from
+/// untrusted input.
+///
+/// Fees must be claimed before `close_position`, which fails while a
+/// position still has unclaimed fees. The full exit sequence is
+/// remove_all_liquidity → claim_fee → close_position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `UserTokenMintMismatch` if `user_token_x` or `user_token_y` is not a
+/// token account of `token_x_mint` or `token_y_mint`.
+pub fn handle_dlmm_claim_fee(ctx: Context<DlmmClaimFee>) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    require_keys_eq!(
+        accessor::mint(&ctx.accounts.user_token_x)?,
+        ctx.accounts.token_x_mint.key(),
+        DlmmCpiError::UserTokenMintMismatch
+    );
+    require_keys_eq!(
+        accessor::mint(&ctx.accounts.user_token_y)?,
+        ctx.accounts.token_y_mint.key(),
+        DlmmCpiError::UserTokenMintMismatch
+    );
+
+    let accounts = dlmm::cpi::accounts::ClaimFee {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::claim_fee(cpi_context)
+}
@@ -0,0 +1,115 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmClaimReward<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account. Its reward info at `reward_index` must match
+    /// reward_mint and reward_vault.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reward vault. Must match lb_pair.reward_infos[reward_index].vault.
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: The reward mint. Must match lb_pair.reward_infos[reward_index].mint.
+    pub reward_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive the reward.
+    pub user_token_account: UncheckedAccount<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of the reward mint.
+    /// Use Token (spl-token) or Token-2022 depending on the reward mint.
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// Claims the farming reward at `reward_index` accrued by a Meteora DLMM position.
+///
+/// The reward mint and vault are checked against the pool's reward info
+/// before the CPI so that a wrong mint can't misroute the claim.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `reward_index` - Index of the pool reward to claim. Pools have
+///   `NUM_REWARDS` (2) reward slots, so only 0 and 1 are valid.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidRewardIndex` if `reward_index` is 2 or more.
+pub fn handle_dlmm_claim_reward(ctx: Context<DlmmClaimReward>, reward_index: u64) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        reward_index < dlmm::constants::NUM_REWARDS as u64,
+        DlmmCpiError::InvalidRewardIndex
+    );
+
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        let reward_info = &lb_pair.reward_infos[reward_index as usize];
+
+        require_keys_eq!(
+            ctx.accounts.reward_mint.key(),
+            reward_info.mint,
+            DlmmCpiError::RewardMintMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.reward_vault.key(),
+            reward_info.vault,
+            DlmmCpiError::RewardVaultMismatch
+        );
+    }
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::ClaimReward {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        reward_vault: ctx.accounts.reward_vault.to_account_info(),
+        reward_mint: ctx.accounts.reward_mint.to_account_info(),
+        user_token_account: ctx.accounts.user_token_account.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::claim_reward(cpi_context, reward_index)
+}
@@ -1,4 +1,9 @@
 use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::PositionClosedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, verify_bin_array, verify_dlmm_owned, verify_event_authority,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -24,13 +29,16 @@ pub struct DlmmClosePosition<'info> {
     /// May be the same account as bin_array_lower if the position fits in one array.
     pub bin_array_upper: UncheckedAccount<'info>,
 
+    #[account(mut)]
     /// CHECK: The authority that owns the position. Must sign the transaction.
+    /// Receives the reclaimed rent when rent_receiver is None.
     pub sender: Signer<'info>,
 
     #[account(mut)]
     /// CHECK: The account that will receive the reclaimed rent lamports
-    /// from closing the position account. Typically the user's wallet.
-    pub rent_receiver: UncheckedAccount<'info>,
+    /// from closing the position account, e.g. a vault refunding another
+    /// account. Must be writable. Pass None to refund sender.
+    pub rent_receiver: Option<UncheckedAccount<'info>>,
 
     #[account(address = dlmm::ID)]
     /// CHECK: DLMM program
@@ -45,7 +53,21 @@ pub struct DlmmClosePosition<'info> {
 ///
 /// The position must have all liquidity removed (via `remove_liquidity` or
 /// `remove_all_liquidity`) and all fees claimed before this will succeed.
-/// Once closed, the rent lamports are returned to `rent_receiver`.
+/// Once closed, the rent lamports are returned to `rent_receiver`, or to
+/// `sender` when `rent_receiver` is `None`.
+///
+/// The position's liquidity shares are checked before the CPI, so closing a
+/// position that still holds liquidity fails with `PositionNotEmpty` rather
+/// than an error from inside the DLMM program. Unclaimed fees already
+/// settled into the position are only logged, as the DLMM program decides
+/// whether they block the close.
+///
+/// Only the position's rent is reclaimed. `bin_array_lower` and
+/// `bin_array_upper` stay open even if this was the last position using
+/// them: the DLMM program has no instruction to close a bin array, so their
+/// rent stays with the accounts for as long as the pool exists.
+///
+/// Emits `PositionClosedEvent` with the reclaimed rent once the CPI succeeds.
 ///
 /// # Arguments
 ///
@@ -53,15 +75,68 @@ pub struct DlmmClosePosition<'info> {
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating success or failure.
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PositionNotEmpty` if any bin of the position has liquidity left, or with
+/// `InvalidBinArrayPda` if `bin_array_lower` or `bin_array_upper` is not the
+/// bin array covering the position's lower or upper bin.
 pub fn handle_dlmm_close_position(ctx: Context<DlmmClosePosition>) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+
+        require!(
+            position.liquidity_shares.iter().all(|share| *share == 0),
+            DlmmCpiError::PositionNotEmpty
+        );
+
+        let (fee_x_pending, fee_y_pending) = position
+            .fee_infos
+            .iter()
+            .fold((0u64, 0u64), |(fee_x, fee_y), fee_info| {
+                (
+                    fee_x.saturating_add(fee_info.fee_x_pending),
+                    fee_y.saturating_add(fee_info.fee_y_pending),
+                )
+            });
+        if fee_x_pending > 0 || fee_y_pending > 0 {
+            msg!(
+                "Position has unclaimed fees: {} token X, {} token Y",
+                fee_x_pending,
+                fee_y_pending
+            );
+        }
+    }
+
+    let rent_reclaimed = ctx.accounts.position.lamports();
+
+    let rent_receiver = ctx.accounts.rent_receiver.as_ref().map_or_else(
+        || ctx.accounts.sender.to_account_info(),
+        |account| account.to_account_info(),
+    );
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
     let accounts = dlmm::cpi::accounts::ClosePosition {
         position: ctx.accounts.position.to_account_info(),
         lb_pair: ctx.accounts.lb_pair.to_account_info(),
         bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
         bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
         sender: ctx.accounts.sender.to_account_info(),
-        rent_receiver: ctx.accounts.rent_receiver.to_account_info(),
+        rent_receiver: rent_receiver.clone(),
         event_authority: ctx.accounts.event_authority.to_account_info(),
         program: ctx.accounts.dlmm_program.to_account_info(),
     };
@@ -69,5 +144,15 @@ pub fn handle_dlmm_close_position(ctx: Context<DlmmClosePosition>) -> Result<()>
     let cpi_context =
         CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
 
-    dlmm::cpi::close_position(cpi_context)
+    dlmm::cpi::close_position(cpi_context)?;
+
+    emit!(PositionClosedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        rent_receiver: rent_receiver.key(),
+        rent_reclaimed,
+    });
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, pda_cpi_context, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(position_owner_bump: u8)]
+pub struct DlmmClosePositionSigned<'info> {
+    #[account(mut)]
+    /// CHECK: The position account to close. Must be owned by position_owner
+    /// and have zero liquidity across all bins.
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside
+    /// position, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the position. PDA of this program, signs the CPI.
+    /// PDA: ["position_owner"], with the bump passed in instruction data.
+    #[account(
+        seeds = [b"position_owner"],
+        bump = position_owner_bump
+    )]
+    pub position_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Only admin can close positions owned by the program.
+    #[account(
+        constraint = crate::assert_eq_admin(cpi_example_admin.key())
+    )]
+    pub cpi_example_admin: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The account that will receive the reclaimed rent lamports
+    /// from closing the position account.
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Closes a Meteora DLMM position owned by this program and reclaims rent.
+///
+/// The program-signed counterpart of `dlmm_close_position`, typically the
+/// last step after draining the position with
+/// `dlmm_remove_liquidity_signed`, which also describes the seed scheme of
+/// `position_owner`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `position_owner_bump` - Bump of the `position_owner` PDA.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ConstraintSeeds` if `position_owner` is not the PDA of the bump,
+/// `PositionNotEmpty` if any bin of the position has liquidity left, or
+/// `InvalidBinArrayPda` if `bin_array_lower` or `bin_array_upper` is not the
+/// bin array covering the position's lower or upper bin.
+pub fn handle_dlmm_close_position_signed(
+    ctx: Context<DlmmClosePositionSigned>,
+    position_owner_bump: u8,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+
+        require!(
+            position.liquidity_shares.iter().all(|share| *share == 0),
+            DlmmCpiError::PositionNotEmpty
+        );
+    }
+
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::ClosePosition {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.position_owner.to_account_info(),
+        rent_receiver: ctx.accounts.rent_receiver.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let seeds = [b"position_owner".as_ref(), &[position_owner_bump]];
+
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = pda_cpi_context(
+        ctx.accounts.dlmm_program.to_account_info(),
+        accounts,
+        signer_seeds,
+    );
+
+    dlmm::cpi::close_position(cpi_context)
+}
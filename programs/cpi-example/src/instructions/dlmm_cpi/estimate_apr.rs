@@ -0,0 +1,156 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    amount_for_share, annualized_bps, bin_array_index, fee_since_update, load_dlmm_account,
+    mul_q64, price_from_bin_id, verify_bin_array, verify_dlmm_owned,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+#[derive(Accounts)]
+pub struct DlmmEstimateApr<'info> {
+    /// CHECK: The pool account. Must match the lb_pair stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The position account to estimate.
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+}
+
+/// Estimates the APR of a Meteora DLMM position from the fees it earned
+/// recently.
+///
+/// The window is the time since the position was last updated
+/// (`last_updated_at`, moved by every deposit, withdrawal and claim) up to
+/// the current clock. The fees earned in it are the per-bin fee growth
+/// since that update, as returned by `dlmm_read_fee_growth`; fees settled
+/// into `fee_x_pending`/`fee_y_pending` before the window are left out. Both
+/// the fees and the position's liquidity are valued in token Y at the
+/// active bin price, and the fee return is annualized without compounding.
+///
+/// The estimate assumes the window is representative: fees earned during a
+/// short or unusually busy window extrapolate poorly, and a liquidity change
+/// during the window isn't visible. `lookback_seconds` sets the shortest
+/// window accepted. Like `dlmm_position_value_in_quote`, the active bin price
+/// can be moved within a transaction, so the result must not be relied on
+/// on-chain. Rewards are not included.
+///
+/// The APR, in basis points, is written as a little-endian `u64` with
+/// `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lookback_seconds` - Minimum time since the last position update for
+///   the estimate to be returned.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `AprWindowTooShort` if the position was updated less than
+/// `lookback_seconds` ago, or with `ZeroPositionValue` if it holds no
+/// liquidity.
+pub fn handle_dlmm_estimate_apr(
+    ctx: Context<DlmmEstimateApr>,
+    lookback_seconds: u64,
+) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+    let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+    require_keys_eq!(
+        position.lb_pair,
+        lb_pair_key,
+        DlmmCpiError::PositionLbPairMismatch
+    );
+
+    let elapsed =
+        u64::try_from(Clock::get()?.unix_timestamp - position.last_updated_at).unwrap_or_default();
+    require!(
+        elapsed > 0 && elapsed >= lookback_seconds,
+        DlmmCpiError::AprWindowTooShort
+    );
+
+    let lower_index = bin_array_index(position.lower_bin_id);
+    let upper_index = bin_array_index(position.upper_bin_id);
+
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_lower.key(), lower_index)?;
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_upper.key(), upper_index)?;
+
+    let bin_array_lower =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_lower)?;
+    let bin_array_upper =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_upper)?;
+
+    let mut amount_x: u128 = 0;
+    let mut amount_y: u128 = 0;
+    let mut fee_x: u128 = 0;
+    let mut fee_y: u128 = 0;
+
+    for (offset, bin_id) in (position.lower_bin_id..=position.upper_bin_id).enumerate() {
+        let share = position.liquidity_shares[offset];
+        if share == 0 {
+            continue;
+        }
+
+        let index = bin_array_index(bin_id);
+        let bin_array = if index == lower_index {
+            &bin_array_lower
+        } else {
+            &bin_array_upper
+        };
+        let bin_offset = i64::from(bin_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+        let bin = &bin_array.bins[bin_offset as usize];
+        let fee_info = &position.fee_infos[offset];
+
+        amount_x += u128::from(amount_for_share(bin.amount_x, share, bin.liquidity_supply));
+        amount_y += u128::from(amount_for_share(bin.amount_y, share, bin.liquidity_supply));
+
+        fee_x += u128::from(
+            fee_since_update(
+                share,
+                bin.fee_amount_x_per_token_stored,
+                fee_info.fee_x_per_token_complete,
+            )
+            .ok_or(DlmmCpiError::MathOverflow)?,
+        );
+        fee_y += u128::from(
+            fee_since_update(
+                share,
+                bin.fee_amount_y_per_token_stored,
+                fee_info.fee_y_per_token_complete,
+            )
+            .ok_or(DlmmCpiError::MathOverflow)?,
+        );
+    }
+
+    let price =
+        price_from_bin_id(lb_pair.active_id, lb_pair.bin_step).ok_or(DlmmCpiError::MathOverflow)?;
+
+    let value = mul_q64(amount_x, price)
+        .and_then(|value_x| value_x.checked_add(amount_y))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let fees = mul_q64(fee_x, price)
+        .and_then(|fee_value_x| fee_value_x.checked_add(fee_y))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    require!(value > 0, DlmmCpiError::ZeroPositionValue);
+
+    let apr_bps = annualized_bps(fees, value, elapsed).ok_or(DlmmCpiError::MathOverflow)?;
+
+    set_return_data(&apr_bps.to_le_bytes());
+
+    Ok(())
+}
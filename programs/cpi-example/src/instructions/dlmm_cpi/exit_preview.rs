@@ -0,0 +1,155 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    amount_for_share, bin_array_index, fee_since_update, load_dlmm_account, verify_bin_array,
+    verify_dlmm_owned,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Tokens a full exit of a position would pay out, returned by
+/// `dlmm_exit_preview`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ExitPreview {
+    /// Token X backing the position's liquidity.
+    pub amount_x: u64,
+    /// Token Y backing the position's liquidity.
+    pub amount_y: u64,
+    /// Claimable fees in token X.
+    pub fee_x: u64,
+    /// Claimable fees in token Y.
+    pub fee_y: u64,
+}
+
+#[derive(Accounts)]
+pub struct DlmmExitPreview<'info> {
+    /// CHECK: The pool account. Must match the lb_pair stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The position account to preview the exit of.
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+}
+
+/// Previews what removing all liquidity from a Meteora DLMM position and
+/// claiming its fees would pay out, without executing either.
+///
+/// The amounts are the token X and Y backing the position's liquidity
+/// shares in each bin, as `dlmm_remove_all_liquidity` would withdraw them.
+/// The fees are those already settled into the position plus what each bin
+/// earned since, as `dlmm_claim_fee` would pay them. Rewards are not
+/// included.
+///
+/// This is an estimate at the current pool state: the bins' composition,
+/// and so the split between token X and Y, changes with every swap through
+/// them, and a Token-2022 transfer fee would be taken on the way out.
+///
+/// The result is written with `set_return_data` as a Borsh serialized
+/// `ExitPreview`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PositionLbPairMismatch` if the position belongs to another pool, or with
+/// `InvalidBinArrayPda` if the bin arrays don't cover the position.
+pub fn handle_dlmm_exit_preview(ctx: Context<DlmmExitPreview>) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+    require_keys_eq!(
+        position.lb_pair,
+        lb_pair_key,
+        DlmmCpiError::PositionLbPairMismatch
+    );
+
+    let lower_index = bin_array_index(position.lower_bin_id);
+    let upper_index = bin_array_index(position.upper_bin_id);
+
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_lower.key(), lower_index)?;
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_upper.key(), upper_index)?;
+
+    let bin_array_lower =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_lower)?;
+    let bin_array_upper =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_upper)?;
+
+    let mut preview = ExitPreview {
+        amount_x: 0,
+        amount_y: 0,
+        fee_x: 0,
+        fee_y: 0,
+    };
+
+    for (offset, bin_id) in (position.lower_bin_id..=position.upper_bin_id).enumerate() {
+        let share = position.liquidity_shares[offset];
+        let fee_info = &position.fee_infos[offset];
+
+        let mut fee_x = fee_info.fee_x_pending;
+        let mut fee_y = fee_info.fee_y_pending;
+
+        if share > 0 {
+            let index = bin_array_index(bin_id);
+            let bin_array = if index == lower_index {
+                &bin_array_lower
+            } else {
+                &bin_array_upper
+            };
+            let bin_offset =
+                i64::from(bin_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+            let bin = &bin_array.bins[bin_offset as usize];
+
+            preview.amount_x = preview
+                .amount_x
+                .checked_add(amount_for_share(bin.amount_x, share, bin.liquidity_supply))
+                .ok_or(DlmmCpiError::MathOverflow)?;
+            preview.amount_y = preview
+                .amount_y
+                .checked_add(amount_for_share(bin.amount_y, share, bin.liquidity_supply))
+                .ok_or(DlmmCpiError::MathOverflow)?;
+
+            fee_x = fee_since_update(
+                share,
+                bin.fee_amount_x_per_token_stored,
+                fee_info.fee_x_per_token_complete,
+            )
+            .and_then(|fee| fee.checked_add(fee_x))
+            .ok_or(DlmmCpiError::MathOverflow)?;
+            fee_y = fee_since_update(
+                share,
+                bin.fee_amount_y_per_token_stored,
+                fee_info.fee_y_per_token_complete,
+            )
+            .and_then(|fee| fee.checked_add(fee_y))
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        }
+
+        preview.fee_x = preview
+            .fee_x
+            .checked_add(fee_x)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        preview.fee_y = preview
+            .fee_y
+            .checked_add(fee_y)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+    }
+
+    set_return_data(&preview.try_to_vec()?);
+
+    Ok(())
+}
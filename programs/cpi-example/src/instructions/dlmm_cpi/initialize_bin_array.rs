@@ -0,0 +1,62 @@
+use crate::dlmm;
+use crate::utils::verify_bin_array;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmInitializeBinArray<'info> {
+    /// CHECK: The pool account the bin array belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The bin array account to create.
+    /// PDA: ["bin_array", lb_pair, index]
+    pub bin_array: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pays the rent for the bin array account.
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+}
+
+/// Creates a Meteora DLMM bin array.
+///
+/// Liquidity can only be deposited into bins whose bin array exists, so the
+/// bin arrays covering a new price range must be created first. Anyone can
+/// create them by paying the rent.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `index` - Index of the bin array, `floor(bin_id / 70)` for any bin it
+///   covers. Negative for bins below bin ID 0, e.g. -1 covers bins -70 to -1.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBinArrayPda` if `bin_array` is not the PDA for `index`.
+pub fn handle_dlmm_initialize_bin_array(
+    ctx: Context<DlmmInitializeBinArray>,
+    index: i64,
+) -> Result<()> {
+    verify_bin_array(
+        ctx.accounts.lb_pair.key(),
+        ctx.accounts.bin_array.key(),
+        index,
+    )?;
+
+    let accounts = dlmm::cpi::accounts::InitializeBinArray {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array: ctx.accounts.bin_array.to_account_info(),
+        funder: ctx.accounts.funder.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_bin_array(cpi_context, index)
+}
@@ -0,0 +1,69 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::derive_bin_array_bitmap_extension;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmInitializeBinArrayBitmapExtension<'info> {
+    /// CHECK: The pool account the bitmap extension belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The bitmap extension account to create.
+    /// PDA: ["bitmap", lb_pair]
+    pub bin_array_bitmap_extension: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Pays the rent for the bitmap extension account.
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+}
+
+/// Creates the bin array bitmap extension of a Meteora DLMM pool.
+///
+/// The pool tracks which bin arrays exist in a bitmap covering bin array
+/// indexes -512 to 511. Bin arrays beyond that, far from the pool's initial
+/// price, are tracked by the extension account, so it must exist before any
+/// of them can be created or used. This only needs to be called once per
+/// pool, and only for pools whose liquidity reaches that wide; most pools
+/// never need it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBitmapExtension` if `bin_array_bitmap_extension` is not the
+/// pool's PDA.
+pub fn handle_dlmm_initialize_bin_array_bitmap_extension(
+    ctx: Context<DlmmInitializeBinArrayBitmapExtension>,
+) -> Result<()> {
+    let (expected_bitmap_extension, _bump) =
+        derive_bin_array_bitmap_extension(ctx.accounts.lb_pair.key());
+    require_keys_eq!(
+        ctx.accounts.bin_array_bitmap_extension.key(),
+        expected_bitmap_extension,
+        DlmmCpiError::InvalidBitmapExtension
+    );
+
+    let accounts = dlmm::cpi::accounts::InitializeBinArrayBitmapExtension {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx.accounts.bin_array_bitmap_extension.to_account_info(),
+        funder: ctx.accounts.funder.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_bin_array_bitmap_extension(cpi_context)
+}
@@ -0,0 +1,130 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{is_valid_bin_step, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmInitializeLbPair<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account to create.
+    /// PDA: [min(token_mint_x, token_mint_y), max(token_mint_x, token_mint_y)]
+    /// under the ILM base key, see `derive_customizable_permissionless_lb_pair`.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool.
+    /// PDA: ["bitmap", lb_pair]
+    /// Only required when `active_id` is outside the main bitmap range.
+    /// Pass None otherwise.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Mint of token X of the new pool.
+    pub token_mint_x: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y of the new pool.
+    pub token_mint_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X to create.
+    /// PDA: [lb_pair, token_mint_x]
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y to create.
+    /// PDA: [lb_pair, token_mint_y]
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's oracle account to create.
+    /// PDA: ["oracle", lb_pair]
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: Funder's token X account. The DLMM program requires the pool
+    /// creator to hold token X.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Creator of the pool, paying the rent for the new accounts.
+    pub funder: Signer<'info>,
+
+    /// CHECK: Token program of both mints.
+    /// Use Token (spl-token) or Token-2022 depending on the mints.
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Creates a customizable permissionless Meteora DLMM pool.
+///
+/// Unlike pools created from a preset parameter, the creator picks the bin
+/// step and base factor, as well as the starting price (`active_id`) and
+/// when trading opens. Only one such pool can exist per token pair.
+///
+/// `bin_step` is checked before the CPI against what the DLMM program
+/// accepts: 1 to `MAX_BIN_STEP` (400) basis points, with a base fee of
+/// `base_factor * bin_step * 10` between `MIN_BASE_FEE` and `MAX_BASE_FEE`
+/// in `FEE_PRECISION` (1e9) units, i.e. 0.01% to 10%.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `params` - Parameters of the new pool:
+///   - `active_id`: The bin, and so the price, trading starts at.
+///   - `bin_step`: Price difference between two bins, in basis points.
+///   - `base_factor`: Multiplier of `bin_step` giving the base fee.
+///   - `activation_type`: 0 if `activation_point` is a slot, 1 if a timestamp.
+///   - `has_alpha_vault`: Whether an alpha vault buys before activation.
+///   - `activation_point`: When trading opens, or None to open immediately.
+///   - `padding`: Reserved, should be zeroed.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBinStep` if `bin_step` and `base_factor` are not accepted by the
+/// DLMM program.
+pub fn handle_dlmm_initialize_lb_pair(
+    ctx: Context<DlmmInitializeLbPair>,
+    params: dlmm::types::CustomizableParams,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        is_valid_bin_step(params.bin_step, params.base_factor),
+        DlmmCpiError::InvalidBinStep
+    );
+
+    let accounts = dlmm::cpi::accounts::InitializeCustomizablePermissionlessLbPair {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        token_mint_x: ctx.accounts.token_mint_x.to_account_info(),
+        token_mint_y: ctx.accounts.token_mint_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        funder: ctx.accounts.funder.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_customizable_permissionless_lb_pair(cpi_context, params)
+}
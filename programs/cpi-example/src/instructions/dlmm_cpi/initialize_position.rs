@@ -0,0 +1,139 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::verify_event_authority;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Size of the account the DLMM program creates for a position: the
+/// `PositionV2` discriminator followed by its zero-copy data, 8120 bytes.
+pub const POSITION_V2_SPACE: usize = dlmm::accounts::PositionV2::DISCRIMINATOR.len()
+    + std::mem::size_of::<dlmm::accounts::PositionV2>();
+
+/// Returns the upper bin ID of a position covering `width` bins from
+/// `lower_bin_id`, so that `upper_bin_id - lower_bin_id + 1 == width`.
+/// `width` must be from 1 to `MAX_BIN_PER_POSITION` (70), the most bins
+/// the DLMM program allows in a position.
+pub fn position_upper_bin_id(lower_bin_id: i32, width: i32) -> Result<i32> {
+    require!(
+        width > 0 && width <= dlmm::constants::MAX_BIN_PER_POSITION as i32,
+        DlmmCpiError::InvalidPositionWidth
+    );
+
+    let upper_bin_id = lower_bin_id
+        .checked_add(width - 1)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    Ok(upper_bin_id)
+}
+
+#[derive(Accounts)]
+pub struct DlmmInitializePosition<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for the position account.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position account to create. A fresh keypair that must sign
+    /// the transaction.
+    pub position: Signer<'info>,
+
+    /// CHECK: The pool account the position belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The owner of the position. Must sign the transaction.
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Initializes a Meteora DLMM position at a keypair account.
+///
+/// The position covers `[lower_bin_id, lower_bin_id + width - 1]` and is
+/// empty until liquidity is added.
+///
+/// The DLMM program creates every new position with the `PositionV2`
+/// layout, `POSITION_V2_SPACE` bytes, and the rest of this crate reads
+/// positions as `PositionV2`. The legacy `Position` (V1) layout is 7560
+/// bytes: it stores liquidity shares as `u64` instead of `u128` and has no
+/// `operator`, `fee_owner` or `lock_release_point`. V1 accounts can no
+/// longer be created, so there is no V1 initialize path here.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position. May be negative.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `width` is out of range, or `MathOverflow` if
+/// the upper bin ID does not fit in an `i32`.
+pub fn handle_dlmm_initialize_position(
+    ctx: Context<DlmmInitializePosition>,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    position_upper_bin_id(lower_bin_id, width)?;
+
+    let accounts = dlmm::cpi::accounts::InitializePosition {
+        payer: ctx.accounts.payer.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position(cpi_context, lower_bin_id, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_v2_space() {
+        assert_eq!(POSITION_V2_SPACE, 8120);
+
+        // V2 widens the 70 liquidity shares from u64 to u128
+        let v1_space = dlmm::accounts::Position::DISCRIMINATOR.len()
+            + std::mem::size_of::<dlmm::accounts::Position>();
+        assert_eq!(v1_space, 7560);
+    }
+
+    #[test]
+    fn test_position_upper_bin_id() {
+        assert_eq!(position_upper_bin_id(-35, 70).unwrap(), 34);
+        assert_eq!(position_upper_bin_id(10, 1).unwrap(), 10);
+
+        for width in [0, 71, -1] {
+            assert_eq!(
+                position_upper_bin_id(0, width).unwrap_err(),
+                DlmmCpiError::InvalidPositionWidth.into()
+            );
+        }
+
+        assert_eq!(
+            position_upper_bin_id(i32::MAX, 2).unwrap_err(),
+            DlmmCpiError::MathOverflow.into()
+        );
+    }
+}
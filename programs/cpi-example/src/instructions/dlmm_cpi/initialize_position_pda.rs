@@ -0,0 +1,122 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{derive_position_pda, pda_cpi_context, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmInitializePositionPda<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for the position account.
+    pub payer: Signer<'info>,
+
+    /// CHECK: Base key used as a seed for the position PDA. Lets the same owner
+    /// hold several positions over the same range.
+    pub base: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position PDA to create. Verified against the derivation below.
+    /// PDA: ["position", lb_pair, base, lower_bin_id, width] under the DLMM program
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The pool account the position belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the position. PDA of this program.
+    #[account(
+        seeds = [b"position_owner"],
+        bump
+    )]
+    pub position_owner: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Initializes a Meteora DLMM position owned by a PDA of this program.
+///
+/// The owner is `position_owner` (PDA: ["position_owner"] under this
+/// program), which signs the CPI, so only this program can later manage the
+/// position. This is the building block for vaults holding positions on
+/// behalf of their users.
+///
+/// The position address is a PDA of the DLMM program and is checked before
+/// the CPI. Off-chain, the caller reconstructs it with the same seeds:
+///
+/// ```ignore
+/// let (position, _bump) = Pubkey::find_program_address(
+///     &[
+///         b"position",
+///         lb_pair.as_ref(),
+///         base.as_ref(),
+///         &lower_bin_id.to_le_bytes(),
+///         &width.to_le_bytes(),
+///     ],
+///     &dlmm::ID,
+/// );
+/// ```
+///
+/// or with `utils::derive_position_pda`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position.
+/// * `width` - Number of bins covered by the position.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionPda` if `position` is not the derived PDA.
+pub fn handle_dlmm_initialize_position_pda(
+    ctx: Context<DlmmInitializePositionPda>,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let (position_key, _bump) = derive_position_pda(
+        ctx.accounts.lb_pair.key(),
+        ctx.accounts.base.key(),
+        lower_bin_id,
+        width,
+    );
+
+    require_keys_eq!(
+        ctx.accounts.position.key(),
+        position_key,
+        DlmmCpiError::InvalidPositionPda
+    );
+
+    let accounts = dlmm::cpi::accounts::InitializePositionPda {
+        payer: ctx.accounts.payer.to_account_info(),
+        base: ctx.accounts.base.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.position_owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let seeds = [b"position_owner".as_ref(), &[ctx.bumps.position_owner]];
+
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = pda_cpi_context(
+        ctx.accounts.dlmm_program.to_account_info(),
+        accounts,
+        signer_seeds,
+    );
+
+    dlmm::cpi::initialize_position_pda(cpi_context, lower_bin_id, width)
+}
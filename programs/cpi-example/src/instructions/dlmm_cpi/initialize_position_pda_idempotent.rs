@@ -0,0 +1,111 @@
+use super::initialize_position::position_upper_bin_id;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{derive_position_pda, load_dlmm_account, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmInitializePositionPdaIdempotent<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for the position account when it is created.
+    pub payer: Signer<'info>,
+
+    /// CHECK: Base key used as a seed for the position PDA. Must match the
+    /// `base` argument.
+    pub base: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position PDA. Either uninitialized, or an existing DLMM position.
+    /// PDA: ["position", lb_pair, base, lower_bin_id, width]
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The pool account the position belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The owner of the position. Must sign the transaction.
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Initializes a Meteora DLMM position at a PDA, tolerating an already
+/// initialized position.
+///
+/// If the position PDA already exists, no CPI is made and the instruction
+/// succeeds as long as the existing position covers exactly the requested
+/// range. This makes setup scripts safe to retry.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+/// * `base` - Base key used as a seed for the position PDA.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `width` is out of range, and
+/// `PositionRangeMismatch` if an existing position covers a different range.
+pub fn handle_dlmm_initialize_position_pda_idempotent(
+    ctx: Context<DlmmInitializePositionPdaIdempotent>,
+    lower_bin_id: i32,
+    width: i32,
+    base: Pubkey,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let upper_bin_id = position_upper_bin_id(lower_bin_id, width)?;
+
+    let (position_key, _bump) =
+        derive_position_pda(ctx.accounts.lb_pair.key(), base, lower_bin_id, width);
+
+    require_keys_eq!(
+        ctx.accounts.base.key(),
+        base,
+        DlmmCpiError::InvalidPositionPda
+    );
+    require_keys_eq!(
+        ctx.accounts.position.key(),
+        position_key,
+        DlmmCpiError::InvalidPositionPda
+    );
+
+    if !ctx.accounts.position.data_is_empty() {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+        require!(
+            position.lower_bin_id == lower_bin_id && position.upper_bin_id == upper_bin_id,
+            DlmmCpiError::PositionRangeMismatch
+        );
+
+        return Ok(());
+    }
+
+    let accounts = dlmm::cpi::accounts::InitializePositionPda {
+        payer: ctx.accounts.payer.to_account_info(),
+        base: ctx.accounts.base.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position_pda(cpi_context, lower_bin_id, width)
+}
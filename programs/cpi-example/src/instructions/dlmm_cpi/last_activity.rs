@@ -0,0 +1,44 @@
+use crate::dlmm;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+#[derive(Accounts)]
+pub struct DlmmLastActivity<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Reads how long ago a Meteora DLMM pool was last swapped against.
+///
+/// Uses `v_parameters.last_update_timestamp` of the pool, which the DLMM
+/// program sets to the current timestamp on every swap. It is read instead
+/// of the oracle's last update, which needs a separate account, and
+/// `last_updated_at`, which tracks fee parameter changes. Deposits and withdrawals don't move it, so a pool
+/// with liquidity activity but no trading still reads as stale.
+///
+/// The seconds elapsed since then, according to `Clock::unix_timestamp`,
+/// are written as a little-endian `u64` with `set_return_data`. A
+/// timestamp ahead of the clock reads as 0.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_last_activity(ctx: Context<DlmmLastActivity>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let elapsed = u64::try_from(
+        Clock::get()?
+            .unix_timestamp
+            .saturating_sub(lb_pair.v_parameters.last_update_timestamp),
+    )
+    .unwrap_or_default();
+
+    set_return_data(&elapsed.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use super::quote_swap::{
+    active_bin_price, drain_amount_in, total_fee_rate, update_references,
+    update_volatility_accumulator,
+};
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{bin_array_index, load_dlmm_account, verify_bin_array};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use std::ops::Deref;
+
+#[derive(Accounts)]
+pub struct DlmmMaxInputForBins<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+    // Bin arrays covering the bins walked need to be passed using remaining
+    // accounts, in any order
+}
+
+/// Returns the largest exact input swap, fee included, after which the
+/// active bin has moved at most `max_bins` bins from the pool's active bin.
+///
+/// That input drains every bin from the active bin up to, and including,
+/// the bin `max_bins` away in the swap direction. A swap of exactly that
+/// input stops in the last bin it empties, so the active bin moves no
+/// further than `max_bins`; one more unit would cross into the next bin
+/// with liquidity. Each bin is priced and charged as `quote_exact_in`
+/// would, with the variable fee growing as bins are crossed. Empty bins
+/// cost nothing but still count towards `max_bins`.
+pub fn max_input_for_bins<B: Deref<Target = dlmm::accounts::BinArray>>(
+    lb_pair: &dlmm::accounts::LbPair,
+    bin_arrays: &[B],
+    max_bins: u16,
+    swap_for_y: bool,
+    current_timestamp: i64,
+) -> Result<u64> {
+    let mut lb_pair = *lb_pair;
+    update_references(&mut lb_pair, current_timestamp);
+
+    let mut max_amount_in: u64 = 0;
+
+    for bins_moved in 0..=max_bins {
+        if bins_moved > 0 {
+            lb_pair.active_id = if swap_for_y {
+                lb_pair.active_id.checked_sub(1)
+            } else {
+                lb_pair.active_id.checked_add(1)
+            }
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        }
+
+        update_volatility_accumulator(&mut lb_pair);
+
+        let index = bin_array_index(lb_pair.active_id);
+        let bin_array = bin_arrays
+            .iter()
+            .find(|bin_array| bin_array.index == index)
+            .ok_or(DlmmCpiError::BinArrayCountMismatch)?;
+        let bin = &bin_array.bins[(i64::from(lb_pair.active_id)
+            - index * dlmm::constants::MAX_BIN_PER_ARRAY as i64)
+            as usize];
+
+        let max_amount_out = if swap_for_y {
+            bin.amount_y
+        } else {
+            bin.amount_x
+        };
+        if max_amount_out == 0 {
+            continue;
+        }
+
+        let price = active_bin_price(&lb_pair, bin)?;
+        let fee_rate = total_fee_rate(&lb_pair).ok_or(DlmmCpiError::MathOverflow)?;
+        let (amount_in, _fee) = drain_amount_in(max_amount_out, price, fee_rate, swap_for_y)?;
+
+        max_amount_in = max_amount_in
+            .checked_add(amount_in)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+    }
+
+    Ok(max_amount_in)
+}
+
+/// Sizes a Meteora DLMM swap to a bin budget: returns the largest
+/// `amount_in` that moves the pool's active bin at most `max_bins` bins,
+/// for routers limiting their price impact.
+///
+/// The pool's liquidity is walked bin by bin from the active bin in the
+/// swap direction, with the same per-bin math as `dlmm_quote_swap`. Any
+/// trade landing before the swap changes the result, so leave a margin.
+///
+/// Every bin array covering the bins walked must be passed as remaining
+/// accounts, in any order. The result is written as a little-endian `u64`
+/// with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `max_bins` - Maximum number of bins the active bin may move. 0 limits
+///   the swap to the active bin's liquidity.
+/// * `swap_for_y` - True to sell token X for token Y, false for the reverse.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `NoBinArrays` if no remaining accounts are passed, `InvalidBinArrayPda`
+/// if one is not a bin array of the pool, or `BinArrayCountMismatch` if a
+/// bin walked is not covered by the bin arrays passed.
+pub fn handle_dlmm_max_input_for_bins<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmMaxInputForBins<'info>>,
+    max_bins: u16,
+    swap_for_y: bool,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let bin_arrays = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| {
+            let bin_array = load_dlmm_account::<dlmm::accounts::BinArray>(account)?;
+            verify_bin_array(lb_pair_key, account.key(), bin_array.index)?;
+            Ok(bin_array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let max_amount_in = max_input_for_bins(
+        &lb_pair,
+        &bin_arrays,
+        max_bins,
+        swap_for_y,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    set_return_data(&max_amount_in.to_le_bytes());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::quote_swap::quote_exact_in;
+    use super::*;
+    use crate::utils::ONE_Q64;
+
+    /// 0.1% base fee, active bin 5
+    fn lb_pair() -> dlmm::accounts::LbPair {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.bin_step = 10;
+        lb_pair.parameters.base_factor = 10_000;
+        lb_pair.active_id = 5;
+        lb_pair
+    }
+
+    /// Bins 2 to 5 hold 1_000 token Y each at a price of 1, except bin 3
+    /// which is empty
+    fn bin_array() -> Box<dlmm::accounts::BinArray> {
+        let mut bin_array: Box<dlmm::accounts::BinArray> = Box::new(bytemuck::Zeroable::zeroed());
+        for bin_id in [2, 4, 5] {
+            bin_array.bins[bin_id].amount_y = 1_000;
+            bin_array.bins[bin_id].price = ONE_Q64;
+        }
+        bin_array
+    }
+
+    #[test]
+    fn test_max_input_for_bins() {
+        // Draining a bin of 1_000 takes 1_000 plus a fee of 1.001, rounded up
+        let cases = [(0, 1_002), (1, 2_004), (2, 2_004), (3, 3_006)];
+
+        for (max_bins, expected) in cases {
+            assert_eq!(
+                max_input_for_bins(&lb_pair(), &[bin_array()], max_bins, true, 0).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_input_for_bins_matches_quote() {
+        let max_amount_in = max_input_for_bins(&lb_pair(), &[bin_array()], 2, true, 0).unwrap();
+
+        let quote = quote_exact_in(&lb_pair(), &[bin_array()], max_amount_in, true, 0).unwrap();
+        assert_eq!(quote.end_active_id, 4);
+        assert_eq!(quote.amount_out, 2_000);
+
+        let quote = quote_exact_in(&lb_pair(), &[bin_array()], max_amount_in + 1, true, 0).unwrap();
+        assert_eq!(quote.end_active_id, 2);
+    }
+
+    #[test]
+    fn test_max_input_for_bins_missing_bin_array() {
+        // Bin 0 is the lowest of bin array 0, bin -1 is in bin array -1
+        assert_eq!(
+            max_input_for_bins(&lb_pair(), &[bin_array()], 6, true, 0).unwrap_err(),
+            DlmmCpiError::BinArrayCountMismatch.into()
+        );
+    }
+}
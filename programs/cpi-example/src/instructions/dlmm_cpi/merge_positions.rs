@@ -0,0 +1,379 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    amount_for_share, bin_array_index, load_dlmm_account, require_compute_units, verify_bin_array,
+    verify_dlmm_owned, verify_event_authority, ADD_LIQUIDITY_COMPUTE_UNITS,
+    CLAIM_FEE_COMPUTE_UNITS, CLOSE_POSITION_COMPUTE_UNITS, REMOVE_LIQUIDITY_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmMergePositions<'info> {
+    #[account(mut)]
+    /// CHECK: The position to merge from. Closed at the end of this instruction.
+    pub source_position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the source position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(source.lower_bin_id / 70)]
+    pub source_bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the source position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(source.upper_bin_id / 70)]
+    /// May be the same account as source_bin_array_lower if the position fits in one array.
+    pub source_bin_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position to merge into. Its range must cover the source position's bins.
+    pub target_position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the target position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(target.lower_bin_id / 70)]
+    pub target_bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the target position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(target.upper_bin_id / 70)]
+    /// May be the same account as target_bin_array_lower if the position fits in one array.
+    pub target_bin_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside both positions.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account receiving the withdrawn token X and both
+    /// positions' token X fees, and funding the re-add.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account receiving the withdrawn token Y and both
+    /// positions' token Y fees, and funding the re-add.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns both positions. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The account that will receive the reclaimed rent lamports
+    /// from closing the source position. Typically the user's wallet.
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Also passed as the single token program of `claim_fee`.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Must be the same program as token_x_program for `claim_fee` to succeed.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Merges one Meteora DLMM position into another of the same pool.
+///
+/// Runs five CPIs:
+///   1. remove_all_liquidity — drain the source position
+///   2. claim_fee            — claim the source position's fees
+///   3. close_position       — close the source position, reclaim rent SOL
+///   4. claim_fee            — claim the target position's fees
+///   5. add_liquidity        — deposit the withdrawn tokens into the target
+///
+/// The deposit keeps the source's shape: every source bin receives the
+/// share of the withdrawn tokens it held, computed from the bins before the
+/// withdrawal. Shares are rounded down to basis points, so a few base units
+/// may stay in the user's token accounts along with both positions' fees.
+///
+/// Each position must fit in its two bin arrays, which holds for any
+/// position up to the 70 bin width. The five CPIs use well over the 200k
+/// default compute unit limit; request around 400k with
+/// `ComputeBudgetInstruction`, more for wide positions.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PositionLbPairMismatch` if either position belongs to another pool,
+/// `InvalidBinArrayPda` if a bin array doesn't cover its position, or
+/// `BinOutOfPositionRange` if the target's range doesn't cover the source
+/// position's bins.
+pub fn handle_dlmm_merge_positions(ctx: Context<DlmmMergePositions>) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    verify_dlmm_owned(&ctx.accounts.source_position)?;
+    verify_dlmm_owned(&ctx.accounts.source_bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.source_bin_array_upper)?;
+    verify_dlmm_owned(&ctx.accounts.target_position)?;
+    verify_dlmm_owned(&ctx.accounts.target_bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.target_bin_array_upper)?;
+
+    let lb_pair = ctx.accounts.lb_pair.key();
+
+    // Token X and Y held by each source bin, before anything moves
+    let source_bins = {
+        let source =
+            load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.source_position)?;
+        let target =
+            load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.target_position)?;
+
+        require_keys_eq!(
+            source.lb_pair,
+            lb_pair,
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        require_keys_eq!(
+            target.lb_pair,
+            lb_pair,
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        require!(
+            target.lower_bin_id <= source.lower_bin_id
+                && source.upper_bin_id <= target.upper_bin_id,
+            DlmmCpiError::BinOutOfPositionRange
+        );
+
+        let lower_index = bin_array_index(source.lower_bin_id);
+        let upper_index = bin_array_index(source.upper_bin_id);
+
+        verify_bin_array(
+            lb_pair,
+            ctx.accounts.source_bin_array_lower.key(),
+            lower_index,
+        )?;
+        verify_bin_array(
+            lb_pair,
+            ctx.accounts.source_bin_array_upper.key(),
+            upper_index,
+        )?;
+        verify_bin_array(
+            lb_pair,
+            ctx.accounts.target_bin_array_lower.key(),
+            bin_array_index(target.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair,
+            ctx.accounts.target_bin_array_upper.key(),
+            bin_array_index(target.upper_bin_id),
+        )?;
+
+        let bin_array_lower =
+            load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.source_bin_array_lower)?;
+        let bin_array_upper =
+            load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.source_bin_array_upper)?;
+
+        let mut source_bins: Vec<(i32, u64, u64)> = Vec::new();
+
+        for (offset, bin_id) in (source.lower_bin_id..=source.upper_bin_id).enumerate() {
+            let share = source.liquidity_shares[offset];
+            if share == 0 {
+                continue;
+            }
+
+            let bin_array = if bin_array_index(bin_id) == lower_index {
+                &bin_array_lower
+            } else {
+                &bin_array_upper
+            };
+            let bin_offset =
+                i64::from(bin_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+            let bin = &bin_array.bins[bin_offset as usize];
+
+            source_bins.push((
+                bin_id,
+                amount_for_share(bin.amount_x, share, bin.liquidity_supply),
+                amount_for_share(bin.amount_y, share, bin.liquidity_supply),
+            ));
+        }
+
+        source_bins
+    };
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    // 1. Drain the source position
+    require_compute_units(REMOVE_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::RemoveAllLiquidity {
+        position: ctx.accounts.source_position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.source_bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.source_bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::remove_all_liquidity(cpi_context)?;
+
+    // Measured before the fees are claimed, so only the liquidity is moved
+    let amount_x = accessor::amount(&ctx.accounts.user_token_x)?.saturating_sub(x_before);
+    let amount_y = accessor::amount(&ctx.accounts.user_token_y)?.saturating_sub(y_before);
+
+    // 2. Claim the source position's fees
+    claim_fee(
+        ctx.accounts,
+        &ctx.accounts.source_position,
+        &ctx.accounts.source_bin_array_lower,
+        &ctx.accounts.source_bin_array_upper,
+    )?;
+
+    // 3. Close the source position
+    require_compute_units(CLOSE_POSITION_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::ClosePosition {
+        position: ctx.accounts.source_position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_lower: ctx.accounts.source_bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.source_bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        rent_receiver: ctx.accounts.rent_receiver.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::close_position(cpi_context)?;
+
+    // 4. Claim the target position's fees
+    claim_fee(
+        ctx.accounts,
+        &ctx.accounts.target_position,
+        &ctx.accounts.target_bin_array_lower,
+        &ctx.accounts.target_bin_array_upper,
+    )?;
+
+    // 5. Redeposit into the target with the source's distribution
+    let total_x: u128 = source_bins.iter().map(|(_, x, _)| u128::from(*x)).sum();
+    let total_y: u128 = source_bins.iter().map(|(_, _, y)| u128::from(*y)).sum();
+    // At most BASIS_POINT_MAX since every bin holds at most the total
+    let bps = |amount: u64, total: u128| {
+        (u128::from(amount) * dlmm::constants::BASIS_POINT_MAX as u128)
+            .checked_div(total)
+            .unwrap_or_default() as u16
+    };
+
+    let bin_liquidity_dist = source_bins
+        .iter()
+        .map(|(bin_id, x, y)| dlmm::types::BinLiquidityDistribution {
+            bin_id: *bin_id,
+            distribution_x: bps(*x, total_x),
+            distribution_y: bps(*y, total_y),
+        })
+        .collect();
+
+    require_compute_units(ADD_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidity {
+        position: ctx.accounts.target_position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.target_bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.target_bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity(
+        cpi_context,
+        dlmm::types::LiquidityParameter {
+            amount_x,
+            amount_y,
+            bin_liquidity_dist,
+        },
+    )
+}
+
+/// Claims the fees of one of the two positions into the user's token accounts.
+fn claim_fee<'info>(
+    accounts: &DlmmMergePositions<'info>,
+    position: &UncheckedAccount<'info>,
+    bin_array_lower: &UncheckedAccount<'info>,
+    bin_array_upper: &UncheckedAccount<'info>,
+) -> Result<()> {
+    require_compute_units(CLAIM_FEE_COMPUTE_UNITS)?;
+
+    let cpi_accounts = dlmm::cpi::accounts::ClaimFee {
+        lb_pair: accounts.lb_pair.to_account_info(),
+        position: position.to_account_info(),
+        bin_array_lower: bin_array_lower.to_account_info(),
+        bin_array_upper: bin_array_upper.to_account_info(),
+        sender: accounts.sender.to_account_info(),
+        reserve_x: accounts.reserve_x.to_account_info(),
+        reserve_y: accounts.reserve_y.to_account_info(),
+        user_token_x: accounts.user_token_x.to_account_info(),
+        user_token_y: accounts.user_token_y.to_account_info(),
+        token_x_mint: accounts.token_x_mint.to_account_info(),
+        token_y_mint: accounts.token_y_mint.to_account_info(),
+        token_program: accounts.token_x_program.to_account_info(),
+        event_authority: accounts.event_authority.to_account_info(),
+        program: accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(accounts.dlmm_program.to_account_info(), cpi_accounts);
+
+    dlmm::cpi::claim_fee(cpi_context)
+}
@@ -3,6 +3,67 @@ mod add_liquidty_one_side;
 mod close_position;
 mod remove_liquidity;
 mod remove_all_liquidity;
+mod initialize_position_pda_idempotent;
+mod open_cost;
+mod swap_feed_oracle;
+mod claim_reward;
+mod remove_and_balance;
+mod swap2;
+mod read_authorities;
+mod swap_checked;
+mod add_liquidity;
+mod add_liquidity_by_strategy;
+mod position_value_in_quote;
+mod initialize_position;
+mod initialize_position_pda;
+mod claim_fee;
+mod validate_distribution;
+mod shift_liquidity;
+mod swap_exact_out;
+mod read_oracle;
+mod swap_with_price_impact;
+mod open_centered;
+mod exit_position;
+mod activation_info;
+mod batch_read_positions;
+mod initialize_bin_array;
+mod add_liquidity_smart;
+mod initialize_bin_array_bitmap_extension;
+mod read_fee_growth;
+mod swap_flexible;
+mod estimate_apr;
+mod merge_positions;
+mod update_position_operator;
+mod read_lb_pair;
+mod pool_has_mint;
+mod remove_liquidity_by_range;
+mod swap_track_volume;
+mod open_wide;
+mod open_and_add;
+mod last_activity;
+mod claim_all_rewards;
+mod add_price_impact;
+mod initialize_lb_pair;
+mod remove_liquidity_signed;
+mod add_liquidity_one_side_signed;
+mod close_position_signed;
+mod exit_preview;
+mod swap_split_output;
+mod batch_remove_liquidity;
+mod suggest_one_side_dist;
+mod remove_liquidity_guarded;
+mod read_position_fees;
+mod token_programs;
+mod position_price_ladder;
+mod quote_swap;
+mod zap_in_balanced;
+mod max_input_for_bins;
+mod pool_depth;
+mod withdraw_protocol_fee;
+mod bootstrap_position;
+mod read_protocol_fees;
+mod add_liquidity_by_strategy_auto;
+mod batch_close;
 
 pub mod dlmm_swap {
     pub use super::swap::*;
@@ -23,3 +84,247 @@ pub mod dlmm_remove_liquidity {
 pub mod dlmm_remove_all_liquidity {
     pub use super::remove_all_liquidity::*;
 }
+
+pub mod dlmm_initialize_position_pda_idempotent {
+    pub use super::initialize_position_pda_idempotent::*;
+}
+
+pub mod dlmm_open_cost {
+    pub use super::open_cost::*;
+}
+
+pub mod dlmm_swap_feed_oracle {
+    pub use super::swap_feed_oracle::*;
+}
+
+pub mod dlmm_claim_reward {
+    pub use super::claim_reward::*;
+}
+
+pub mod dlmm_remove_and_balance {
+    pub use super::remove_and_balance::*;
+}
+
+pub mod dlmm_swap2 {
+    pub use super::swap2::*;
+}
+
+pub mod dlmm_read_authorities {
+    pub use super::read_authorities::*;
+}
+
+pub mod dlmm_swap_checked {
+    pub use super::swap_checked::*;
+}
+
+pub mod dlmm_add_liquidity {
+    pub use super::add_liquidity::*;
+}
+
+pub mod dlmm_add_liquidity_by_strategy {
+    pub use super::add_liquidity_by_strategy::*;
+}
+
+pub mod dlmm_position_value_in_quote {
+    pub use super::position_value_in_quote::*;
+}
+
+pub mod dlmm_initialize_position {
+    pub use super::initialize_position::*;
+}
+
+pub mod dlmm_initialize_position_pda {
+    pub use super::initialize_position_pda::*;
+}
+
+pub mod dlmm_claim_fee {
+    pub use super::claim_fee::*;
+}
+
+pub mod dlmm_validate_distribution {
+    pub use super::validate_distribution::*;
+}
+
+pub mod dlmm_shift_liquidity {
+    pub use super::shift_liquidity::*;
+}
+
+pub mod dlmm_swap_exact_out {
+    pub use super::swap_exact_out::*;
+}
+
+pub mod dlmm_read_oracle {
+    pub use super::read_oracle::*;
+}
+
+pub mod dlmm_swap_with_price_impact {
+    pub use super::swap_with_price_impact::*;
+}
+
+pub mod dlmm_open_centered {
+    pub use super::open_centered::*;
+}
+
+pub mod dlmm_exit_position {
+    pub use super::exit_position::*;
+}
+
+pub mod dlmm_activation_info {
+    pub use super::activation_info::*;
+}
+
+pub mod dlmm_batch_read_positions {
+    pub use super::batch_read_positions::*;
+}
+
+pub mod dlmm_initialize_bin_array {
+    pub use super::initialize_bin_array::*;
+}
+
+pub mod dlmm_add_liquidity_smart {
+    pub use super::add_liquidity_smart::*;
+}
+
+pub mod dlmm_initialize_bin_array_bitmap_extension {
+    pub use super::initialize_bin_array_bitmap_extension::*;
+}
+
+pub mod dlmm_read_fee_growth {
+    pub use super::read_fee_growth::*;
+}
+
+pub mod dlmm_swap_flexible {
+    pub use super::swap_flexible::*;
+}
+
+pub mod dlmm_estimate_apr {
+    pub use super::estimate_apr::*;
+}
+
+pub mod dlmm_merge_positions {
+    pub use super::merge_positions::*;
+}
+
+pub mod dlmm_update_position_operator {
+    pub use super::update_position_operator::*;
+}
+
+pub mod dlmm_read_lb_pair {
+    pub use super::read_lb_pair::*;
+}
+
+pub mod dlmm_pool_has_mint {
+    pub use super::pool_has_mint::*;
+}
+
+pub mod dlmm_remove_liquidity_by_range {
+    pub use super::remove_liquidity_by_range::*;
+}
+
+pub mod dlmm_swap_track_volume {
+    pub use super::swap_track_volume::*;
+}
+
+pub mod dlmm_open_wide {
+    pub use super::open_wide::*;
+}
+
+pub mod dlmm_open_and_add {
+    pub use super::open_and_add::*;
+}
+
+pub mod dlmm_last_activity {
+    pub use super::last_activity::*;
+}
+
+pub mod dlmm_claim_all_rewards {
+    pub use super::claim_all_rewards::*;
+}
+
+pub mod dlmm_add_price_impact {
+    pub use super::add_price_impact::*;
+}
+
+pub mod dlmm_initialize_lb_pair {
+    pub use super::initialize_lb_pair::*;
+}
+
+pub mod dlmm_remove_liquidity_signed {
+    pub use super::remove_liquidity_signed::*;
+}
+
+pub mod dlmm_add_liquidity_one_side_signed {
+    pub use super::add_liquidity_one_side_signed::*;
+}
+
+pub mod dlmm_close_position_signed {
+    pub use super::close_position_signed::*;
+}
+
+pub mod dlmm_exit_preview {
+    pub use super::exit_preview::*;
+}
+
+pub mod dlmm_swap_split_output {
+    pub use super::swap_split_output::*;
+}
+
+pub mod dlmm_batch_remove_liquidity {
+    pub use super::batch_remove_liquidity::*;
+}
+
+pub mod dlmm_suggest_one_side_dist {
+    pub use super::suggest_one_side_dist::*;
+}
+
+pub mod dlmm_remove_liquidity_guarded {
+    pub use super::remove_liquidity_guarded::*;
+}
+
+pub mod dlmm_read_position_fees {
+    pub use super::read_position_fees::*;
+}
+
+pub mod dlmm_token_programs {
+    pub use super::token_programs::*;
+}
+
+pub mod dlmm_position_price_ladder {
+    pub use super::position_price_ladder::*;
+}
+
+pub mod dlmm_quote_swap {
+    pub use super::quote_swap::*;
+}
+
+pub mod dlmm_zap_in_balanced {
+    pub use super::zap_in_balanced::*;
+}
+
+pub mod dlmm_max_input_for_bins {
+    pub use super::max_input_for_bins::*;
+}
+
+pub mod dlmm_pool_depth {
+    pub use super::pool_depth::*;
+}
+
+pub mod dlmm_withdraw_protocol_fee {
+    pub use super::withdraw_protocol_fee::*;
+}
+
+pub mod dlmm_bootstrap_position {
+    pub use super::bootstrap_position::*;
+}
+
+pub mod dlmm_read_protocol_fees {
+    pub use super::read_protocol_fees::*;
+}
+
+pub mod dlmm_add_liquidity_by_strategy_auto {
+    pub use super::add_liquidity_by_strategy_auto::*;
+}
+
+pub mod dlmm_batch_close {
+    pub use super::batch_close::*;
+}
@@ -0,0 +1,218 @@
+use super::initialize_position::position_upper_bin_id;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityAddedEvent;
+use crate::utils::{
+    bin_array_index, require_compute_units, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority, ADD_LIQUIDITY_COMPUTE_UNITS, INITIALIZE_POSITION_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmOpenAndAdd<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for the position account.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position account to create. A fresh keypair that must sign
+    /// the transaction.
+    pub position: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account the position belongs to. Must match
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token X from.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to deposit token Y from.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The owner of the position, also the sender of the deposit. Owns
+    /// user_token_x and user_token_y and must sign the transaction.
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Initializes a Meteora DLMM position and adds liquidity by strategy to it
+/// in one instruction.
+///
+/// Opening and funding a position in separate transactions leaves an empty
+/// position behind if the second one never lands, and costs an extra round
+/// trip. Here the `initialize_position` and `add_liquidity_by_strategy` CPIs
+/// run in the same instruction, so a failed deposit reverts the position
+/// creation as well.
+///
+/// The position has no operator yet, so `owner` is both the position owner
+/// and the sender of the deposit.
+///
+/// Emits `LiquidityAddedEvent` with the amounts taken from the user once
+/// the deposit succeeds.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position. May be negative.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+/// * `liquidity_parameter` - Amounts, slippage and strategy of the deposit.
+///   Same as `dlmm_add_liquidity_by_strategy`; the strategy range must lie
+///   within the position.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if both amounts are 0, `InvalidPositionWidth` if `width`
+/// is out of range, or with
+/// `InvalidBinArrayPda` if the bin arrays don't cover the position.
+pub fn handle_dlmm_open_and_add(
+    ctx: Context<DlmmOpenAndAdd>,
+    lower_bin_id: i32,
+    width: i32,
+    liquidity_parameter: dlmm::types::LiquidityParameterByStrategy,
+) -> Result<()> {
+    require!(
+        liquidity_parameter.amount_x > 0 || liquidity_parameter.amount_y > 0,
+        DlmmCpiError::InvalidAmount
+    );
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let upper_bin_id = position_upper_bin_id(lower_bin_id, width)?;
+
+    let lb_pair = ctx.accounts.lb_pair.key();
+
+    verify_bin_array(
+        lb_pair,
+        ctx.accounts.bin_array_lower.key(),
+        bin_array_index(lower_bin_id),
+    )?;
+    verify_bin_array(
+        lb_pair,
+        ctx.accounts.bin_array_upper.key(),
+        bin_array_index(upper_bin_id),
+    )?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    require_compute_units(INITIALIZE_POSITION_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::InitializePosition {
+        payer: ctx.accounts.payer.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position(cpi_context, lower_bin_id, width)?;
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    require_compute_units(ADD_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityByStrategy {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.owner.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity_by_strategy(cpi_context, liquidity_parameter)?;
+
+    let amount_x = x_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_x)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = y_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_y)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    emit!(LiquidityAddedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair,
+        sender: ctx.accounts.owner.key(),
+        amount_x,
+        amount_y,
+    });
+
+    Ok(())
+}
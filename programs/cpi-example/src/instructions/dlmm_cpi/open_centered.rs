@@ -0,0 +1,80 @@
+use super::initialize_position::DlmmInitializePosition;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Bin range chosen by `dlmm_open_centered`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CenteredRange {
+    /// The lowest bin ID covered by the position.
+    pub lower_bin_id: i32,
+    /// Number of bins covered by the position.
+    pub width: i32,
+}
+
+/// Initializes a Meteora DLMM position centered on the pool's active bin.
+///
+/// The position covers `[active_id - half_width, active_id + half_width]`,
+/// using the active bin read from `lb_pair` at execution time, so clients
+/// don't need to compute the range themselves. The chosen range is written
+/// as a Borsh serialized `CenteredRange` with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `half_width` - Number of bins on each side of the active bin. The
+///   total width `2 * half_width + 1` must not exceed
+///   `MAX_BIN_PER_POSITION` (70).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `half_width` is negative or too large.
+pub fn handle_dlmm_open_centered(
+    ctx: Context<DlmmInitializePosition>,
+    half_width: i32,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let width = half_width
+        .checked_mul(2)
+        .and_then(|width| width.checked_add(1))
+        .ok_or(DlmmCpiError::InvalidPositionWidth)?;
+
+    require!(
+        half_width >= 0 && width <= dlmm::constants::MAX_BIN_PER_POSITION as i32,
+        DlmmCpiError::InvalidPositionWidth
+    );
+
+    let active_id = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.active_id;
+    let lower_bin_id = active_id
+        .checked_sub(half_width)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    let accounts = dlmm::cpi::accounts::InitializePosition {
+        payer: ctx.accounts.payer.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position(cpi_context, lower_bin_id, width)?;
+
+    set_return_data(
+        &CenteredRange {
+            lower_bin_id,
+            width,
+        }
+        .try_to_vec()?,
+    );
+
+    Ok(())
+}
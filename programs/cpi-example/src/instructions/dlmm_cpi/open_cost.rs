@@ -0,0 +1,83 @@
+use super::initialize_position::{position_upper_bin_id, POSITION_V2_SPACE};
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{bin_array_index, derive_bin_array_pda};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct DlmmOpenCost<'info> {
+    /// CHECK: The pool account the position will be opened in.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The lower bin array account covering the requested range. May not exist yet.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: The upper bin array account covering the requested range. May not exist yet.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the range fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+}
+
+/// Computes the lamports needed to open a Meteora DLMM position over
+/// `[lower_bin_id, lower_bin_id + width - 1]`.
+///
+/// The total is the position account rent plus the rent of every bin array
+/// covering the range that does not exist yet. The result is written as a
+/// little-endian `u64` with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `width` is out of range.
+pub fn handle_dlmm_open_cost(
+    ctx: Context<DlmmOpenCost>,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<()> {
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let upper_bin_id = position_upper_bin_id(lower_bin_id, width)?;
+
+    let (bin_array_lower_key, _bump) =
+        derive_bin_array_pda(lb_pair_key, bin_array_index(lower_bin_id));
+    let (bin_array_upper_key, _bump) =
+        derive_bin_array_pda(lb_pair_key, bin_array_index(upper_bin_id));
+
+    require_keys_eq!(
+        ctx.accounts.bin_array_lower.key(),
+        bin_array_lower_key,
+        DlmmCpiError::InvalidBinArrayPda
+    );
+    require_keys_eq!(
+        ctx.accounts.bin_array_upper.key(),
+        bin_array_upper_key,
+        DlmmCpiError::InvalidBinArrayPda
+    );
+
+    let rent = Rent::get()?;
+    let bin_array_space = dlmm::accounts::BinArray::DISCRIMINATOR.len()
+        + std::mem::size_of::<dlmm::accounts::BinArray>();
+
+    let mut lamports = rent.minimum_balance(POSITION_V2_SPACE);
+
+    if ctx.accounts.bin_array_lower.data_is_empty() {
+        lamports += rent.minimum_balance(bin_array_space);
+    }
+
+    if bin_array_upper_key != bin_array_lower_key && ctx.accounts.bin_array_upper.data_is_empty() {
+        lamports += rent.minimum_balance(bin_array_space);
+    }
+
+    set_return_data(&lamports.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use super::initialize_position::position_upper_bin_id;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    require_compute_units, required_bin_array_indices, verify_bin_array, verify_event_authority,
+    INITIALIZE_BIN_ARRAY_COMPUTE_UNITS, INITIALIZE_POSITION_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmOpenWide<'info> {
+    #[account(mut)]
+    /// CHECK: Pays the rent for the position and any bin array created.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: The position account to create. A fresh keypair that must sign
+    /// the transaction.
+    pub position: Signer<'info>,
+
+    /// CHECK: The pool account the position belongs to.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The owner of the position. Must sign the transaction.
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Initializes a Meteora DLMM position and creates every bin array its range
+/// needs.
+///
+/// A position can be opened over bins whose bin arrays don't exist yet, but
+/// nothing can be deposited into it until they do. This instruction creates
+/// the position, then the missing bin arrays with `payer` funding the rent,
+/// so the position is ready for liquidity. Bin arrays that already exist are
+/// skipped.
+///
+/// The bin arrays are passed as writable remaining accounts, one per bin
+/// array index from `floor(lower_bin_id / 70)` to `floor(upper_bin_id / 70)`
+/// in ascending order, each being the PDA
+/// `["bin_array", lb_pair, index]`. A position covers at most
+/// `MAX_BIN_PER_POSITION` (70) bins, so one or two accounts are expected.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `lower_bin_id` - The lowest bin ID covered by the position. May be negative.
+/// * `width` - Number of bins covered by the position, from 1 to
+///   `MAX_BIN_PER_POSITION` (70).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidPositionWidth` if `width` is out of range,
+/// `BinArrayCountMismatch` if the number of remaining accounts doesn't match
+/// the bin arrays of the range, or `InvalidBinArrayPda` if one of them is
+/// not the expected PDA.
+pub fn handle_dlmm_open_wide<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmOpenWide<'info>>,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let upper_bin_id = position_upper_bin_id(lower_bin_id, width)?;
+    let indices = required_bin_array_indices(lower_bin_id, upper_bin_id);
+
+    require!(
+        ctx.remaining_accounts.len() == indices.len(),
+        DlmmCpiError::BinArrayCountMismatch
+    );
+
+    require_compute_units(INITIALIZE_POSITION_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::InitializePosition {
+        payer: ctx.accounts.payer.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::initialize_position(cpi_context, lower_bin_id, width)?;
+
+    let lb_pair = ctx.accounts.lb_pair.key();
+
+    for (bin_array, index) in ctx.remaining_accounts.iter().zip(indices) {
+        verify_bin_array(lb_pair, bin_array.key(), index)?;
+
+        if !bin_array.data_is_empty() {
+            continue;
+        }
+
+        require_compute_units(INITIALIZE_BIN_ARRAY_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::InitializeBinArray {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array: bin_array.clone(),
+            funder: ctx.accounts.payer.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+        dlmm::cpi::initialize_bin_array(cpi_context, index)?;
+    }
+
+    Ok(())
+}
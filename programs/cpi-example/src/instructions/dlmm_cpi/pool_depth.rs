@@ -0,0 +1,137 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, required_bin_array_indices, verify_bin_array,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use std::ops::Deref;
+
+/// Largest `radius` accepted by `dlmm_pool_depth`. Each entry takes 36 bytes,
+/// so the 27 bins of radius 13 and the vector length fit in the 1024 byte
+/// return data limit.
+pub const MAX_POOL_DEPTH_RADIUS: u16 = 13;
+
+#[derive(Accounts)]
+pub struct DlmmPoolDepth<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+    // Bin arrays covering the bins within radius of the active bin need to be
+    // passed using remaining accounts, in ascending order
+}
+
+/// Returns `(bin_id, amount_x, amount_y)` for every bin from `lower_bin_id`
+/// to `upper_bin_id`, read from `bin_arrays`, which must be the bin arrays
+/// covering that range in ascending order.
+pub fn bin_reserves<B: Deref<Target = dlmm::accounts::BinArray>>(
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    bin_arrays: &[B],
+) -> Vec<(i32, u128, u128)> {
+    let first_index = bin_array_index(lower_bin_id);
+
+    (lower_bin_id..=upper_bin_id)
+        .map(|bin_id| {
+            let index = bin_array_index(bin_id);
+            let bin_array = &bin_arrays[(index - first_index) as usize];
+            let bin = &bin_array.bins
+                [(i64::from(bin_id) - index * dlmm::constants::MAX_BIN_PER_ARRAY as i64) as usize];
+            (bin_id, u128::from(bin.amount_x), u128::from(bin.amount_y))
+        })
+        .collect()
+}
+
+/// Reads the liquidity of a Meteora DLMM pool around its active bin, for
+/// rendering a depth chart.
+///
+/// Returns the token X and Y reserves of each bin within `radius` of the
+/// active bin, across all positions, in base units. Bins below the active
+/// bin only hold token Y and bins above it only token X.
+///
+/// The bin arrays covering those bins must be passed as remaining
+/// accounts, in ascending order. The result is written with
+/// `set_return_data` as a Borsh serialized `Vec<(i32, u128, u128)>` of
+/// `(bin_id, reserve_x, reserve_y)`, from the lowest bin up. Return data is
+/// limited to 1024 bytes, so `radius` may be at most
+/// `MAX_POOL_DEPTH_RADIUS` (13).
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `radius` - Number of bins read on each side of the active bin.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `TooManyBins` if `radius` is above `MAX_POOL_DEPTH_RADIUS`,
+/// `BinArrayCountMismatch` if the number of remaining accounts is not the
+/// number of bin arrays the range spans, or `InvalidBinArrayPda` if one of
+/// them is not the expected bin array.
+pub fn handle_dlmm_pool_depth<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmPoolDepth<'info>>,
+    radius: u16,
+) -> Result<()> {
+    require!(radius <= MAX_POOL_DEPTH_RADIUS, DlmmCpiError::TooManyBins);
+
+    let active_id = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.active_id;
+    let lower_bin_id = active_id
+        .checked_sub(i32::from(radius))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let upper_bin_id = active_id
+        .checked_add(i32::from(radius))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    let indices = required_bin_array_indices(lower_bin_id, upper_bin_id);
+    require!(
+        ctx.remaining_accounts.len() == indices.len(),
+        DlmmCpiError::BinArrayCountMismatch
+    );
+
+    let bin_arrays = ctx
+        .remaining_accounts
+        .iter()
+        .zip(indices)
+        .map(|(account, index)| {
+            verify_bin_array(ctx.accounts.lb_pair.key(), account.key(), index)?;
+            load_dlmm_account::<dlmm::accounts::BinArray>(account)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let depth = bin_reserves(lower_bin_id, upper_bin_id, &bin_arrays);
+
+    set_return_data(&depth.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_reserves_across_bin_arrays() {
+        let mut lower: Box<dlmm::accounts::BinArray> = Box::new(bytemuck::Zeroable::zeroed());
+        let mut upper: Box<dlmm::accounts::BinArray> = Box::new(bytemuck::Zeroable::zeroed());
+        lower.index = -1;
+        lower.bins[69].amount_y = 500;
+        upper.bins[0].amount_x = 100;
+        upper.bins[0].amount_y = 200;
+        upper.bins[1].amount_x = 300;
+
+        // Bin -1 is the last of bin array -1, bin 0 the first of bin array 0
+        assert_eq!(
+            bin_reserves(-2, 1, &[lower, upper]),
+            vec![(-2, 0, 0), (-1, 0, 500), (0, 100, 200), (1, 300, 0)]
+        );
+    }
+
+    #[test]
+    fn test_max_pool_depth_fits_return_data() {
+        let depth = vec![(0i32, u128::MAX, u128::MAX); 2 * MAX_POOL_DEPTH_RADIUS as usize + 1];
+
+        assert!(
+            depth.try_to_vec().unwrap().len()
+                <= anchor_lang::solana_program::program::MAX_RETURN_DATA
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use crate::dlmm;
+use crate::utils::{load_dlmm_account, pool_contains_mint};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+#[derive(Accounts)]
+pub struct DlmmPoolHasMint<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Checks whether a Meteora DLMM pool trades a given mint.
+///
+/// Pools can't be enumerated on-chain, so routers that found a pool
+/// off-chain can use this to confirm it trades `mint` as either token X or
+/// token Y before building a swap. The result is written as a Borsh
+/// serialized `bool` with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `mint` - The mint to look for.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_pool_has_mint(ctx: Context<DlmmPoolHasMint>, mint: Pubkey) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    set_return_data(&pool_contains_mint(&lb_pair, &mint).try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, price_from_bin_id};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Most bins returned by one `dlmm_position_price_ladder` call. Each entry
+/// takes 20 bytes, so 51 of them and the vector length fit in the 1024 byte
+/// return data limit, while a full 70 bin position would not.
+pub const MAX_PRICE_LADDER_BINS: i32 = 51;
+
+#[derive(Accounts)]
+pub struct DlmmPositionPriceLadder<'info> {
+    /// CHECK: The pool account, read for its bin step. Must match the lb_pair
+    /// stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The position account whose bin range is priced.
+    pub position: UncheckedAccount<'info>,
+}
+
+/// Returns `(bin_id, price)` for every bin from `lower_bin_id` to
+/// `upper_bin_id`, with the price as computed by `price_from_bin_id`.
+/// The range may span at most `MAX_BIN_PER_POSITION` (70) bins.
+pub fn price_ladder(
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    bin_step: u16,
+) -> Result<Vec<(i32, u128)>> {
+    let width = i64::from(upper_bin_id) - i64::from(lower_bin_id) + 1;
+    require!(
+        (1..=dlmm::constants::MAX_BIN_PER_POSITION as i64).contains(&width),
+        DlmmCpiError::InvalidPositionWidth
+    );
+
+    (lower_bin_id..=upper_bin_id)
+        .map(|bin_id| {
+            let price = price_from_bin_id(bin_id, bin_step).ok_or(DlmmCpiError::MathOverflow)?;
+            Ok((bin_id, price))
+        })
+        .collect()
+}
+
+/// Reads the price ladder of a Meteora DLMM position: the price of each bin
+/// in its range, for rendering the range in a UI.
+///
+/// Prices are Q64.64 numbers of token Y base units per token X base unit,
+/// derived from the pool's bin step. Adjust by the mints' decimals for a
+/// human readable price.
+///
+/// The result is written with `set_return_data` as a Borsh serialized
+/// `Vec<(i32, u128)>` of `(bin_id, price)`, from the lowest bin up. Return
+/// data is limited to 1024 bytes, so at most `MAX_PRICE_LADDER_BINS` (51)
+/// bins are returned per call, starting `start_offset` bins above the
+/// position's lower bin. Positions of up to 51 bins come back whole with
+/// `start_offset` 0; for wider ones, call again with `start_offset` 51.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `start_offset` - Offset of the first bin returned from the position's
+///   lower bin.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PositionLbPairMismatch` if the position belongs to another pool, or
+/// `BinOutOfPositionRange` if `start_offset` is past the position's upper
+/// bin.
+pub fn handle_dlmm_position_price_ladder(
+    ctx: Context<DlmmPositionPriceLadder>,
+    start_offset: u8,
+) -> Result<()> {
+    let bin_step = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.bin_step;
+
+    let (lower_bin_id, upper_bin_id) = {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        require_keys_eq!(
+            position.lb_pair,
+            ctx.accounts.lb_pair.key(),
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        (position.lower_bin_id, position.upper_bin_id)
+    };
+
+    let from_bin_id = lower_bin_id
+        .checked_add(i32::from(start_offset))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    require!(
+        from_bin_id <= upper_bin_id,
+        DlmmCpiError::BinOutOfPositionRange
+    );
+    let to_bin_id = upper_bin_id.min(from_bin_id + MAX_PRICE_LADDER_BINS - 1);
+
+    let ladder = price_ladder(from_bin_id, to_bin_id, bin_step)?;
+
+    set_return_data(&ladder.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_ladder_increases() {
+        // Crossing bin 0, where the price is exactly 1
+        let ladder = price_ladder(-35, 34, 25).unwrap();
+
+        assert_eq!(ladder.len(), 70);
+        assert_eq!(ladder[35], (0, crate::utils::ONE_Q64));
+        assert!(ladder
+            .windows(2)
+            .all(|pair| pair[1].0 == pair[0].0 + 1 && pair[1].1 > pair[0].1));
+    }
+
+    #[test]
+    fn test_price_ladder_bounded_to_position_width() {
+        assert!(price_ladder(0, 69, 1).is_ok());
+        assert_eq!(
+            price_ladder(0, 70, 1).unwrap_err(),
+            DlmmCpiError::InvalidPositionWidth.into()
+        );
+        assert_eq!(
+            price_ladder(1, 0, 1).unwrap_err(),
+            DlmmCpiError::InvalidPositionWidth.into()
+        );
+    }
+}
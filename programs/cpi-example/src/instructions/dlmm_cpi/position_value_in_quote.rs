@@ -0,0 +1,115 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    amount_for_share, bin_array_index, div_by_price, load_dlmm_account, mul_q64, price_from_bin_id,
+    verify_bin_array, verify_dlmm_owned,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+#[derive(Accounts)]
+pub struct DlmmPositionValueInQuote<'info> {
+    /// CHECK: The pool account. Must match the lb_pair stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The position account to value.
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+}
+
+/// Computes the value of a Meteora DLMM position in a single quote token.
+///
+/// The token X and Y amounts backing the position's liquidity shares are
+/// summed over its bins, then the non-quote amount is converted at the
+/// current active bin price. This is the spot price, not a TWAP, so it can
+/// be moved within a transaction and must not be used as a manipulation
+/// resistant valuation. Unclaimed fees and rewards are not included.
+///
+/// The total, in quote token base units, is written as a little-endian `u64`
+/// with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `quote_is_y` - Value the position in token Y if true, token X otherwise.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_position_value_in_quote(
+    ctx: Context<DlmmPositionValueInQuote>,
+    quote_is_y: bool,
+) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+    let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+    require_keys_eq!(
+        position.lb_pair,
+        lb_pair_key,
+        DlmmCpiError::PositionLbPairMismatch
+    );
+
+    let lower_index = bin_array_index(position.lower_bin_id);
+    let upper_index = bin_array_index(position.upper_bin_id);
+
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_lower.key(), lower_index)?;
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_upper.key(), upper_index)?;
+
+    let bin_array_lower =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_lower)?;
+    let bin_array_upper =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_upper)?;
+
+    let mut amount_x: u128 = 0;
+    let mut amount_y: u128 = 0;
+
+    for (offset, bin_id) in (position.lower_bin_id..=position.upper_bin_id).enumerate() {
+        let share = position.liquidity_shares[offset];
+        if share == 0 {
+            continue;
+        }
+
+        let index = bin_array_index(bin_id);
+        let bin_array = if index == lower_index {
+            &bin_array_lower
+        } else {
+            &bin_array_upper
+        };
+        let bin_offset = i64::from(bin_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+        let bin = &bin_array.bins[bin_offset as usize];
+
+        amount_x += u128::from(amount_for_share(bin.amount_x, share, bin.liquidity_supply));
+        amount_y += u128::from(amount_for_share(bin.amount_y, share, bin.liquidity_supply));
+    }
+
+    let price =
+        price_from_bin_id(lb_pair.active_id, lb_pair.bin_step).ok_or(DlmmCpiError::MathOverflow)?;
+
+    let value = if quote_is_y {
+        mul_q64(amount_x, price)
+            .and_then(|value_x| value_x.checked_add(amount_y))
+            .ok_or(DlmmCpiError::MathOverflow)?
+    } else {
+        div_by_price(amount_y, price)
+            .and_then(|value_y| value_y.checked_add(amount_x))
+            .ok_or(DlmmCpiError::MathOverflow)?
+    };
+    let value = u64::try_from(value).map_err(|_| DlmmCpiError::MathOverflow)?;
+
+    set_return_data(&value.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,421 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, price_from_bin_id, verify_bin_array, SCALE_OFFSET};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use std::ops::Deref;
+
+/// Expected outcome of an exact input swap, returned by `dlmm_quote_swap`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Output token received.
+    pub amount_out: u64,
+    /// Total swap fee, in the input token. Included in `amount_in`.
+    pub fee: u64,
+    /// Share of `fee` that goes to the protocol.
+    pub protocol_fee: u64,
+    /// Active bin of the pool once the swap is done.
+    pub end_active_id: i32,
+}
+
+#[derive(Accounts)]
+pub struct DlmmQuoteSwap<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts, in swap order
+}
+
+/// Total fee rate in `FEE_PRECISION` units: base fee plus variable fee,
+/// capped at `MAX_FEE_RATE`.
+pub(crate) fn total_fee_rate(lb_pair: &dlmm::accounts::LbPair) -> Option<u128> {
+    let bin_step = u128::from(lb_pair.bin_step);
+    let base_fee = u128::from(lb_pair.parameters.base_factor) * bin_step * 10;
+
+    let variable_fee = if lb_pair.parameters.variable_fee_control > 0 {
+        let square_vfa_bin =
+            (u128::from(lb_pair.v_parameters.volatility_accumulator) * bin_step).checked_pow(2)?;
+        let v_fee =
+            u128::from(lb_pair.parameters.variable_fee_control).checked_mul(square_vfa_bin)?;
+        v_fee.checked_add(99_999_999_999)? / 100_000_000_000
+    } else {
+        0
+    };
+
+    Some(
+        base_fee
+            .checked_add(variable_fee)?
+            .min(u128::from(dlmm::constants::MAX_FEE_RATE)),
+    )
+}
+
+/// Fee to add on top of `amount` so that `amount` remains once it is taken.
+fn compute_fee(amount: u64, fee_rate: u128) -> Option<u64> {
+    let denominator = u128::from(dlmm::constants::FEE_PRECISION).checked_sub(fee_rate)?;
+    u64::try_from(
+        u128::from(amount)
+            .checked_mul(fee_rate)?
+            .div_ceil(denominator),
+    )
+    .ok()
+}
+
+/// Fee taken out of `amount`, which includes it.
+fn compute_fee_from_amount(amount: u64, fee_rate: u128) -> Option<u64> {
+    u64::try_from(
+        u128::from(amount)
+            .checked_mul(fee_rate)?
+            .div_ceil(u128::from(dlmm::constants::FEE_PRECISION)),
+    )
+    .ok()
+}
+
+/// `amount * price >> 64`, rounded down or up.
+fn mul_shr(amount: u64, price: u128, round_up: bool) -> Option<u64> {
+    let (price_hi, price_lo) = (price >> SCALE_OFFSET, price & u128::from(u64::MAX));
+    let low = u128::from(amount) * price_lo;
+    let mut result = u128::from(amount).checked_mul(price_hi)? + (low >> SCALE_OFFSET);
+    if round_up && low & u128::from(u64::MAX) != 0 {
+        result += 1;
+    }
+    u64::try_from(result).ok()
+}
+
+/// `(amount << 64) / price`, rounded down or up.
+fn shl_div(amount: u64, price: u128, round_up: bool) -> Option<u64> {
+    let shifted = u128::from(amount) << SCALE_OFFSET;
+    let result = if round_up {
+        shifted.div_ceil(price)
+    } else {
+        shifted / price
+    };
+    u64::try_from(result).ok()
+}
+
+/// Price of `bin`, the pool's active bin. Bins that were never swapped
+/// against store no price, so it is derived from the bin id.
+pub(crate) fn active_bin_price(
+    lb_pair: &dlmm::accounts::LbPair,
+    bin: &dlmm::types::Bin,
+) -> Result<u128> {
+    if bin.price != 0 {
+        return Ok(bin.price);
+    }
+    let price =
+        price_from_bin_id(lb_pair.active_id, lb_pair.bin_step).ok_or(DlmmCpiError::MathOverflow)?;
+    Ok(price)
+}
+
+/// Input that drains `max_amount_out` from a bin at `price` and `fee_rate`,
+/// fee included, and that fee.
+pub(crate) fn drain_amount_in(
+    max_amount_out: u64,
+    price: u128,
+    fee_rate: u128,
+    swap_for_y: bool,
+) -> Result<(u64, u64)> {
+    let amount_in = if swap_for_y {
+        shl_div(max_amount_out, price, true)
+    } else {
+        mul_shr(max_amount_out, price, true)
+    }
+    .ok_or(DlmmCpiError::MathOverflow)?;
+    let fee = compute_fee(amount_in, fee_rate).ok_or(DlmmCpiError::MathOverflow)?;
+
+    Ok((
+        amount_in
+            .checked_add(fee)
+            .ok_or(DlmmCpiError::MathOverflow)?,
+        fee,
+    ))
+}
+
+/// Refreshes the volatility reference at the start of a swap, as the DLMM
+/// program does with the time elapsed since the pool's last update.
+pub(crate) fn update_references(lb_pair: &mut dlmm::accounts::LbPair, current_timestamp: i64) {
+    let elapsed = current_timestamp.saturating_sub(lb_pair.v_parameters.last_update_timestamp);
+
+    if elapsed >= i64::from(lb_pair.parameters.filter_period) {
+        lb_pair.v_parameters.index_reference = lb_pair.active_id;
+        lb_pair.v_parameters.volatility_reference =
+            if elapsed < i64::from(lb_pair.parameters.decay_period) {
+                (u64::from(lb_pair.v_parameters.volatility_accumulator)
+                    * u64::from(lb_pair.parameters.reduction_factor)
+                    / dlmm::constants::BASIS_POINT_MAX as u64) as u32
+            } else {
+                0
+            };
+    }
+}
+
+/// Raises the volatility accumulator by the distance of the active bin from
+/// the reference bin, as the DLMM program does on every bin it crosses.
+pub(crate) fn update_volatility_accumulator(lb_pair: &mut dlmm::accounts::LbPair) {
+    let delta_id = (i64::from(lb_pair.v_parameters.index_reference) - i64::from(lb_pair.active_id))
+        .unsigned_abs();
+    let volatility_accumulator = u64::from(lb_pair.v_parameters.volatility_reference)
+        .saturating_add(delta_id.saturating_mul(dlmm::constants::BASIS_POINT_MAX as u64));
+
+    lb_pair.v_parameters.volatility_accumulator =
+        volatility_accumulator.min(u64::from(lb_pair.parameters.max_volatility_accumulator)) as u32;
+}
+
+/// Quotes an exact input swap of `amount_in` against a copy of the pool,
+/// following the DLMM swap: the volatility reference is refreshed for
+/// `current_timestamp`, then bins are consumed from the active bin in the
+/// swap direction, each at its own price and fee rate.
+///
+/// `bin_arrays` must be in swap order. When the next one is further along
+/// than the active bin, the active bin jumps to its nearest edge, like the
+/// DLMM program skipping bin arrays without liquidity.
+pub fn quote_exact_in<B: Deref<Target = dlmm::accounts::BinArray>>(
+    lb_pair: &dlmm::accounts::LbPair,
+    bin_arrays: &[B],
+    amount_in: u64,
+    swap_for_y: bool,
+    current_timestamp: i64,
+) -> Result<SwapQuote> {
+    let mut lb_pair = *lb_pair;
+    update_references(&mut lb_pair, current_timestamp);
+
+    let mut quote = SwapQuote {
+        amount_out: 0,
+        fee: 0,
+        protocol_fee: 0,
+        end_active_id: lb_pair.active_id,
+    };
+    let mut amount_left = amount_in;
+
+    for bin_array in bin_arrays {
+        if amount_left == 0 {
+            break;
+        }
+
+        let lower_bin_id = bin_array.index * dlmm::constants::MAX_BIN_PER_ARRAY as i64;
+        let upper_bin_id = lower_bin_id + dlmm::constants::MAX_BIN_PER_ARRAY as i64 - 1;
+
+        let active_id = i64::from(lb_pair.active_id);
+        if swap_for_y && active_id > upper_bin_id {
+            lb_pair.active_id = upper_bin_id as i32;
+        } else if !swap_for_y && active_id < lower_bin_id {
+            lb_pair.active_id = lower_bin_id as i32;
+        }
+
+        while amount_left > 0 && (lower_bin_id..=upper_bin_id).contains(&lb_pair.active_id.into()) {
+            update_volatility_accumulator(&mut lb_pair);
+
+            let bin = &bin_array.bins[(i64::from(lb_pair.active_id) - lower_bin_id) as usize];
+            let max_amount_out = if swap_for_y {
+                bin.amount_y
+            } else {
+                bin.amount_x
+            };
+
+            if max_amount_out > 0 {
+                let price = active_bin_price(&lb_pair, bin)?;
+                let fee_rate = total_fee_rate(&lb_pair).ok_or(DlmmCpiError::MathOverflow)?;
+                let (max_amount_in, max_fee) =
+                    drain_amount_in(max_amount_out, price, fee_rate, swap_for_y)?;
+
+                let (amount_in_with_fee, amount_out, fee) = if amount_left > max_amount_in {
+                    (max_amount_in, max_amount_out, max_fee)
+                } else {
+                    let fee = compute_fee_from_amount(amount_left, fee_rate)
+                        .ok_or(DlmmCpiError::MathOverflow)?;
+                    let amount_in_after_fee = amount_left - fee;
+                    let amount_out = if swap_for_y {
+                        mul_shr(amount_in_after_fee, price, false)
+                    } else {
+                        shl_div(amount_in_after_fee, price, false)
+                    }
+                    .ok_or(DlmmCpiError::MathOverflow)?;
+                    (amount_left, amount_out.min(max_amount_out), fee)
+                };
+
+                let protocol_fee = u128::from(fee) * u128::from(lb_pair.parameters.protocol_share)
+                    / dlmm::constants::BASIS_POINT_MAX as u128;
+
+                amount_left -= amount_in_with_fee;
+                quote.amount_out = quote
+                    .amount_out
+                    .checked_add(amount_out)
+                    .ok_or(DlmmCpiError::MathOverflow)?;
+                quote.fee = quote
+                    .fee
+                    .checked_add(fee)
+                    .ok_or(DlmmCpiError::MathOverflow)?;
+                quote.protocol_fee = quote
+                    .protocol_fee
+                    .checked_add(protocol_fee as u64)
+                    .ok_or(DlmmCpiError::MathOverflow)?;
+            }
+
+            if amount_left > 0 {
+                lb_pair.active_id = if swap_for_y {
+                    lb_pair.active_id - 1
+                } else {
+                    lb_pair.active_id + 1
+                };
+            }
+        }
+    }
+
+    require!(amount_left == 0, DlmmCpiError::InsufficientLiquidity);
+
+    quote.end_active_id = lb_pair.active_id;
+
+    Ok(quote)
+}
+
+/// Quotes a Meteora DLMM swap without executing it.
+///
+/// Replays the DLMM exact input swap on the current state of the pool and
+/// of the bin arrays passed: the same per-bin pricing, base and variable
+/// fees, protocol share and rounding. Nothing is written, so this can run
+/// in a simulated transaction or be called before `dlmm_swap` to set
+/// `min_amount_out`. The quote matches the swap when both see the same pool
+/// state and clock; any trade landing in between moves it, which is what
+/// the slippage allowance is for. Host fees are not split out of
+/// `protocol_fee`, and pool activation and status are not checked.
+///
+/// The bin arrays are passed as remaining accounts, in the order the swap
+/// traverses them, as for `dlmm_swap`. The Borsh serialized `SwapQuote` is
+/// written with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `amount_in` - The amount of input tokens, fee included.
+/// * `swap_for_y` - True to sell token X for token Y, false for the reverse.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if `amount_in` is 0, `NoBinArrays` if no remaining
+/// accounts are passed, `InvalidBinArrayPda` if one is not a bin array of
+/// the pool, or `InsufficientLiquidity` if the bin arrays run out of
+/// liquidity before `amount_in` is consumed.
+pub fn handle_dlmm_quote_swap<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmQuoteSwap<'info>>,
+    amount_in: u64,
+    swap_for_y: bool,
+) -> Result<()> {
+    require!(amount_in > 0, DlmmCpiError::InvalidAmount);
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let bin_arrays = ctx
+        .remaining_accounts
+        .iter()
+        .map(|account| {
+            let bin_array = load_dlmm_account::<dlmm::accounts::BinArray>(account)?;
+            verify_bin_array(lb_pair_key, account.key(), bin_array.index)?;
+            Ok(bin_array)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let quote = quote_exact_in(
+        &lb_pair,
+        &bin_arrays,
+        amount_in,
+        swap_for_y,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ONE_Q64;
+
+    /// 0.1% base fee, half of it to the protocol, active bin 5
+    fn lb_pair() -> dlmm::accounts::LbPair {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.bin_step = 10;
+        lb_pair.parameters.base_factor = 10_000;
+        lb_pair.parameters.protocol_share = 5_000;
+        lb_pair.active_id = 5;
+        lb_pair
+    }
+
+    /// Bins 4 and 5 of bin array 0 hold 1_000 token Y each, at a price of 1
+    fn bin_array() -> Box<dlmm::accounts::BinArray> {
+        let mut bin_array: Box<dlmm::accounts::BinArray> = Box::new(bytemuck::Zeroable::zeroed());
+        for bin in &mut bin_array.bins[4..=5] {
+            bin.amount_y = 1_000;
+            bin.price = ONE_Q64;
+        }
+        bin_array
+    }
+
+    #[test]
+    fn test_quote_exact_in_across_bins() {
+        let quote = quote_exact_in(&lb_pair(), &[bin_array()], 1_500, true, 0).unwrap();
+
+        // Bin 5 is drained by 1_000 + 2 fee, the 498 left buys 497 in bin 4
+        assert_eq!(
+            quote,
+            SwapQuote {
+                amount_out: 1_497,
+                fee: 3,
+                protocol_fee: 1,
+                end_active_id: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_quote_exact_in_within_active_bin() {
+        let quote = quote_exact_in(&lb_pair(), &[bin_array()], 100, true, 0).unwrap();
+
+        assert_eq!(
+            quote,
+            SwapQuote {
+                amount_out: 99,
+                fee: 1,
+                protocol_fee: 0,
+                end_active_id: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_quote_exact_in_insufficient_liquidity() {
+        // 2_000 token Y in total, and no token X to buy in the other direction
+        for (amount_in, swap_for_y) in [(2_100, true), (1, false)] {
+            assert_eq!(
+                quote_exact_in(&lb_pair(), &[bin_array()], amount_in, swap_for_y, 0).unwrap_err(),
+                DlmmCpiError::InsufficientLiquidity.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote_exact_in_variable_fee() {
+        let mut lb_pair = lb_pair();
+        lb_pair.parameters.variable_fee_control = 100_000;
+        lb_pair.parameters.max_volatility_accumulator = 350_000;
+        lb_pair.parameters.filter_period = 30;
+        lb_pair.parameters.decay_period = 600;
+        lb_pair.parameters.reduction_factor = 5_000;
+        lb_pair.v_parameters.volatility_accumulator = 200_000;
+
+        // Recent activity keeps half of the volatility: (100_000 * 10)^2 *
+        // 100_000 / 1e11 = 1e6 variable fee on top of the 1e6 base fee
+        let quote = quote_exact_in(&lb_pair, &[bin_array()], 1_000, true, 60).unwrap();
+        assert_eq!(quote.fee, 2);
+        assert_eq!(quote.amount_out, 998);
+
+        // Long after the last swap the volatility has decayed away
+        let quote = quote_exact_in(&lb_pair, &[bin_array()], 1_000, true, 1_000).unwrap();
+        assert_eq!(quote.fee, 1);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::dlmm;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Keys controlling a DLMM pool, returned by `dlmm_read_authorities`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolAuthorities {
+    /// Pool creator. Can set the pre-activation duration and swap address.
+    pub creator: Pubkey,
+    /// Legacy fee owner slot of the pool. Newer pools leave it zeroed as
+    /// protocol fees are withdrawn by the DLMM admin.
+    pub fee_owner: Pubkey,
+    /// Funder of each of the two reward slots. Default pubkey when the slot
+    /// is not initialized.
+    pub reward_funders: [Pubkey; 2],
+}
+
+#[derive(Accounts)]
+pub struct DlmmReadAuthorities<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Reads the keys controlling a Meteora DLMM pool.
+///
+/// The Borsh serialized `PoolAuthorities` is written with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_read_authorities(ctx: Context<DlmmReadAuthorities>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let authorities = PoolAuthorities {
+        creator: lb_pair.creator,
+        fee_owner: Pubkey::new_from_array(lb_pair.padding1),
+        reward_funders: [
+            lb_pair.reward_infos[0].funder,
+            lb_pair.reward_infos[1].funder,
+        ],
+    };
+
+    set_return_data(&authorities.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    bin_array_index, fee_since_update, load_dlmm_account, verify_bin_array, verify_dlmm_owned,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{set_return_data, MAX_RETURN_DATA};
+
+/// Borsh size of one `(bin_id, fee_x, fee_y)` entry.
+const FEE_GROWTH_ENTRY_LEN: usize = 4 + 8 + 8;
+
+/// Maximum number of bins whose fee growth fits in the return data, after
+/// the 4 byte length prefix of the Borsh `Vec`.
+pub const MAX_FEE_GROWTH_BINS: usize = (MAX_RETURN_DATA - 4) / FEE_GROWTH_ENTRY_LEN;
+
+#[derive(Accounts)]
+pub struct DlmmReadFeeGrowth<'info> {
+    /// CHECK: The pool account. Must match the lb_pair stored inside position.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: The position account to read.
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+}
+
+/// Reads the fees a Meteora DLMM position earned in each of its bins since
+/// it was last updated.
+///
+/// Every bin keeps a running fee per liquidity share for each token, and the
+/// position remembers the value it last settled at. For each bin in the
+/// position's range the fee is the growth between the two multiplied by the
+/// position's share, which is what the DLMM program adds to the position the
+/// next time it is touched.
+///
+/// This is not the position's pending fee. `fee_x_pending` and
+/// `fee_y_pending` hold fees already settled into the position by an earlier
+/// deposit, withdrawal or claim and are not included here; the claimable fee
+/// of a bin is its pending fee plus the amount returned here.
+///
+/// The result is written with `set_return_data` as a Borsh serialized
+/// `Vec<(i32, u64, u64)>` of `(bin_id, fee_x, fee_y)`, ordered by bin ID.
+/// Only bins where either fee is non-zero are included, so the entries
+/// never exceed the position width.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBatchSize` if more than `MAX_FEE_GROWTH_BINS` (51) bins earned
+/// fees, since the result would not fit in the return data.
+pub fn handle_dlmm_read_fee_growth(ctx: Context<DlmmReadFeeGrowth>) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+    require_keys_eq!(
+        position.lb_pair,
+        lb_pair_key,
+        DlmmCpiError::PositionLbPairMismatch
+    );
+
+    let lower_index = bin_array_index(position.lower_bin_id);
+    let upper_index = bin_array_index(position.upper_bin_id);
+
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_lower.key(), lower_index)?;
+    verify_bin_array(lb_pair_key, ctx.accounts.bin_array_upper.key(), upper_index)?;
+
+    let bin_array_lower =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_lower)?;
+    let bin_array_upper =
+        load_dlmm_account::<dlmm::accounts::BinArray>(&ctx.accounts.bin_array_upper)?;
+
+    let mut fee_growth: Vec<(i32, u64, u64)> = Vec::new();
+
+    for (offset, bin_id) in (position.lower_bin_id..=position.upper_bin_id).enumerate() {
+        let share = position.liquidity_shares[offset];
+        if share == 0 {
+            continue;
+        }
+
+        let index = bin_array_index(bin_id);
+        let bin_array = if index == lower_index {
+            &bin_array_lower
+        } else {
+            &bin_array_upper
+        };
+        let bin_offset = i64::from(bin_id).rem_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64);
+        let bin = &bin_array.bins[bin_offset as usize];
+        let fee_info = &position.fee_infos[offset];
+
+        let fee_x = fee_since_update(
+            share,
+            bin.fee_amount_x_per_token_stored,
+            fee_info.fee_x_per_token_complete,
+        )
+        .ok_or(DlmmCpiError::MathOverflow)?;
+        let fee_y = fee_since_update(
+            share,
+            bin.fee_amount_y_per_token_stored,
+            fee_info.fee_y_per_token_complete,
+        )
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+        if fee_x > 0 || fee_y > 0 {
+            fee_growth.push((bin_id, fee_x, fee_y));
+        }
+    }
+
+    require!(
+        fee_growth.len() <= MAX_FEE_GROWTH_BINS,
+        DlmmCpiError::InvalidBatchSize
+    );
+
+    set_return_data(&fee_growth.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::dlmm;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// State of a DLMM pool needed to build a swap, returned by
+/// `dlmm_read_lb_pair`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LbPairInfo {
+    /// Bin holding the current price.
+    pub active_id: i32,
+    /// Price step between two consecutive bins, in basis points.
+    pub bin_step: u16,
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    /// The pool's reserve vault for token X.
+    pub reserve_x: Pubkey,
+    /// The pool's reserve vault for token Y.
+    pub reserve_y: Pubkey,
+    /// Pool status. 0 when enabled, 1 when disabled by the DLMM admin.
+    pub status: u8,
+}
+
+#[derive(Accounts)]
+pub struct DlmmReadLbPair<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Reads the state of a Meteora DLMM pool needed to build a swap.
+///
+/// Lets a client get the active bin, bin step, mints and reserves of a pool
+/// with a single simulated transaction instead of fetching and decoding the
+/// `lb_pair` account itself. The Borsh serialized `LbPairInfo` is written
+/// with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `AccountOwnedByWrongProgram` if `lb_pair` is not owned by the DLMM
+/// program, `AccountDidNotDeserialize` if it is too short to hold a pool,
+/// or `AccountDiscriminatorMismatch` if it is another DLMM account.
+pub fn handle_dlmm_read_lb_pair(ctx: Context<DlmmReadLbPair>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let info = LbPairInfo {
+        active_id: lb_pair.active_id,
+        bin_step: lb_pair.bin_step,
+        token_x_mint: lb_pair.token_x_mint,
+        token_y_mint: lb_pair.token_y_mint,
+        reserve_x: lb_pair.reserve_x,
+        reserve_y: lb_pair.reserve_y,
+        status: lb_pair.status,
+    };
+
+    set_return_data(&info.try_to_vec()?);
+
+    Ok(())
+}
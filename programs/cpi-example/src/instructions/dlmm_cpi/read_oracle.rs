@@ -0,0 +1,83 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::Discriminator;
+
+/// Serialized size of a DLMM oracle observation: i128 + i64 + i64.
+const OBSERVATION_SIZE: usize = 32;
+
+/// Latest observation of a DLMM oracle, returned by `dlmm_read_oracle`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OracleObservation {
+    /// Active bin ID accumulated over time. The TWAP bin ID between two
+    /// observations is the difference of this value divided by the elapsed
+    /// seconds.
+    pub cumulative_active_bin_id: i128,
+    /// Timestamp the observation sample was created.
+    pub created_at: i64,
+    /// Timestamp the observation sample was last updated.
+    pub last_updated_at: i64,
+}
+
+#[derive(Accounts)]
+pub struct DlmmReadOracle<'info> {
+    /// CHECK: Oracle account of the pool. PDA: ["oracle", lb_pair]
+    pub oracle: UncheckedAccount<'info>,
+}
+
+/// Reads the latest observation of a Meteora DLMM pool oracle.
+///
+/// The oracle is only updated by swaps, so on an idle pool the latest
+/// observation can be arbitrarily old. The instruction fails if it was last
+/// updated more than `max_staleness_seconds` ago, so callers never act on a
+/// stale TWAP. The Borsh serialized `OracleObservation` is written with
+/// `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `max_staleness_seconds` - Maximum accepted age of the latest observation.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `OracleStale` if the latest observation is too old.
+pub fn handle_dlmm_read_oracle(
+    ctx: Context<DlmmReadOracle>,
+    max_staleness_seconds: u64,
+) -> Result<()> {
+    let idx = {
+        let oracle = load_dlmm_account::<dlmm::accounts::Oracle>(&ctx.accounts.oracle)?;
+        require!(oracle.idx < oracle.length, DlmmCpiError::InvalidOracle);
+        oracle.idx as usize
+    };
+
+    // Observations are stored right after the oracle header
+    let data = ctx.accounts.oracle.try_borrow_data()?;
+    let offset = dlmm::accounts::Oracle::DISCRIMINATOR.len()
+        + std::mem::size_of::<dlmm::accounts::Oracle>()
+        + idx * OBSERVATION_SIZE;
+    let bytes = data
+        .get(offset..offset + OBSERVATION_SIZE)
+        .ok_or(DlmmCpiError::InvalidOracle)?;
+
+    let observation = OracleObservation {
+        cumulative_active_bin_id: i128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        created_at: i64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+        last_updated_at: i64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+    };
+
+    let age = Clock::get()?
+        .unix_timestamp
+        .saturating_sub(observation.last_updated_at);
+    require!(
+        u64::try_from(age).unwrap_or(0) <= max_staleness_seconds,
+        DlmmCpiError::OracleStale
+    );
+
+    set_return_data(&observation.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, verify_dlmm_owned};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::Discriminator;
+
+/// Fees and rewards settled into a position and not yet claimed, returned by
+/// `dlmm_read_position_fees`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PositionFees {
+    /// Claimable fees in token X.
+    pub fee_x_owed: u64,
+    /// Claimable fees in token Y.
+    pub fee_y_owed: u64,
+    /// Claimable rewards, by reward index of the pool.
+    pub reward_owed: [u64; 2],
+}
+
+#[derive(Accounts)]
+pub struct DlmmReadPositionFees<'info> {
+    /// CHECK: The position account to read, in the V1 or V2 layout.
+    pub position: UncheckedAccount<'info>,
+}
+
+/// Sums the pending fees and rewards over the bins of a position.
+pub fn owed_fees(
+    fee_infos: &[dlmm::types::FeeInfo],
+    reward_infos: &[dlmm::types::UserRewardInfo],
+) -> Result<PositionFees> {
+    let mut fees = PositionFees {
+        fee_x_owed: 0,
+        fee_y_owed: 0,
+        reward_owed: [0; 2],
+    };
+
+    for fee_info in fee_infos {
+        fees.fee_x_owed = fees
+            .fee_x_owed
+            .checked_add(fee_info.fee_x_pending)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+        fees.fee_y_owed = fees
+            .fee_y_owed
+            .checked_add(fee_info.fee_y_pending)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+    }
+
+    for reward_info in reward_infos {
+        for (owed, pending) in fees.reward_owed.iter_mut().zip(reward_info.reward_pendings) {
+            *owed = owed
+                .checked_add(pending)
+                .ok_or(DlmmCpiError::MathOverflow)?;
+        }
+    }
+
+    Ok(fees)
+}
+
+/// Reads the fees and rewards a Meteora DLMM position can claim.
+///
+/// Only the amounts already settled into the position are counted: the
+/// pending fee and reward of each bin, which the DLMM program updates
+/// whenever the position is touched. Fees the bins earned since then are
+/// not, as they need the bin arrays; `dlmm_exit_preview` includes them.
+///
+/// Both the legacy `Position` (V1) and the `PositionV2` layout are read,
+/// told apart by their discriminator. The result is written with
+/// `set_return_data` as a Borsh serialized `PositionFees`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `AccountNotOwnedByDlmm` if the position is not a DLMM account, or with
+/// `UnknownPositionLayout` if its discriminator is neither V1 nor V2.
+pub fn handle_dlmm_read_position_fees(ctx: Context<DlmmReadPositionFees>) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+
+    let is_v2 = {
+        let data = ctx.accounts.position.try_borrow_data()?;
+        if data.starts_with(dlmm::accounts::PositionV2::DISCRIMINATOR) {
+            true
+        } else if data.starts_with(dlmm::accounts::Position::DISCRIMINATOR) {
+            false
+        } else {
+            return err!(DlmmCpiError::UnknownPositionLayout);
+        }
+    };
+
+    let fees = if is_v2 {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        owed_fees(&position.fee_infos, &position.reward_infos)?
+    } else {
+        let position = load_dlmm_account::<dlmm::accounts::Position>(&ctx.accounts.position)?;
+        owed_fees(&position.fee_infos, &position.reward_infos)?
+    };
+
+    set_return_data(&fees.try_to_vec()?);
+
+    Ok(())
+}
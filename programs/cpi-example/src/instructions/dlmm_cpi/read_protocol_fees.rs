@@ -0,0 +1,44 @@
+use crate::dlmm;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Protocol fees held by a DLMM pool, returned by `dlmm_read_protocol_fees`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolFees {
+    /// Protocol fee accrued in token X, in base units.
+    pub protocol_fee_x: u64,
+    /// Protocol fee accrued in token Y, in base units.
+    pub protocol_fee_y: u64,
+}
+
+#[derive(Accounts)]
+pub struct DlmmReadProtocolFees<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Reads the protocol fees a Meteora DLMM pool has accrued and not yet
+/// withdrawn, i.e. the most `dlmm_withdraw_protocol_fee` can sweep.
+///
+/// The Borsh serialized `ProtocolFees` is written with `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_read_protocol_fees(ctx: Context<DlmmReadProtocolFees>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let protocol_fees = ProtocolFees {
+        protocol_fee_x: lb_pair.protocol_fee.amount_x,
+        protocol_fee_y: lb_pair.protocol_fee.amount_y,
+    };
+
+    set_return_data(&protocol_fees.try_to_vec()?);
+
+    Ok(())
+}
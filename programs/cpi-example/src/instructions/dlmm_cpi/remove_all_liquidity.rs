@@ -1,5 +1,12 @@
 use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityRemovedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority, verify_reserves,
+};
 use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
 
 #[derive(Accounts)]
 pub struct DlmmRemoveAllLiquidity<'info> {
@@ -85,14 +92,56 @@ pub struct DlmmRemoveAllLiquidity<'info> {
 ///   2. claim_fee             — claim any accumulated swap fees
 ///   3. close_position        — close the position account, reclaim rent SOL
 ///
+/// `dlmm_exit_position` runs all three steps in a single instruction.
+///
+/// Emits `LiquidityRemovedEvent` with the amounts received and the width of
+/// the position once the CPI succeeds.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all required accounts.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating success or failure.
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ReservesSwapped` if `reserve_x` and `reserve_y` are passed the wrong way
+/// round, with `ReserveMismatch` if either is not the pool's reserve,
+/// and with `InvalidBinArrayPda` if `bin_array_lower` or `bin_array_upper` is
+/// not the bin array covering the position's lower or upper bin.
 pub fn handle_dlmm_remove_all_liquidity(ctx: Context<DlmmRemoveAllLiquidity>) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    let bin_count = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+
+        (position.upper_bin_id - position.lower_bin_id + 1) as u32
+    };
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
     let accounts = dlmm::cpi::accounts::RemoveAllLiquidity {
         position: ctx.accounts.position.to_account_info(),
         lb_pair: ctx.accounts.lb_pair.to_account_info(),
@@ -119,5 +168,23 @@ pub fn handle_dlmm_remove_all_liquidity(ctx: Context<DlmmRemoveAllLiquidity>) ->
     let cpi_context =
         CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
 
-    dlmm::cpi::remove_all_liquidity(cpi_context)
+    dlmm::cpi::remove_all_liquidity(cpi_context)?;
+
+    let amount_x = accessor::amount(&ctx.accounts.user_token_x)?
+        .checked_sub(x_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = accessor::amount(&ctx.accounts.user_token_y)?
+        .checked_sub(y_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    emit!(LiquidityRemovedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+        bin_count,
+    });
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,256 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    div_by_price, load_dlmm_account, mul_q64, price_from_bin_id, require_compute_units,
+    verify_bin_liquidity_removal, verify_dlmm_owned, verify_event_authority,
+    REMOVE_LIQUIDITY_COMPUTE_UNITS, SWAP_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmRemoveAndBalance<'info> {
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive withdrawn token X. Also the swap
+    /// source or destination for token X.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to receive withdrawn token Y. Also the swap
+    /// source or destination for token Y.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool. Used by the rebalancing swap.
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays for the rebalancing swap need to be passed using remaining accounts
+}
+
+/// Removes liquidity from specific bins of a Meteora DLMM position, then
+/// swaps the withdrawn token in excess so that the withdrawn amounts reach
+/// `target_ratio_bps` of token X value.
+///
+/// Values are measured at the pool's active bin price after the removal.
+/// Only the amounts withdrawn by this instruction are rebalanced, the rest
+/// of the user's token balances are left untouched.
+///
+/// The swap's `min_amount_out` is derived from that same active bin price,
+/// so on its own it would follow a price moved earlier in the slot, e.g. by
+/// a sandwich. The active bin is therefore first checked against the
+/// `expected_active_id` the caller observed off-chain, as in `dlmm_swap`.
+///
+/// Accounts are the `remove_liquidity` accounts plus the pool `oracle`. The
+/// bin arrays the swap traverses, starting from the active bin array, must
+/// be passed as remaining accounts, exactly like `dlmm_swap`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `bin_liquidity_removal` - A list of per-bin removal instructions, see
+///   `dlmm_remove_liquidity`.
+/// * `target_ratio_bps` - Target share of token X in the withdrawn value,
+///   out of 10000. 5000 = equal value of X and Y.
+/// * `max_swap_slippage_bps` - Maximum shortfall of the rebalancing swap
+///   output versus the active bin price, out of 10000. Used to derive the
+///   swap's `min_amount_out`.
+/// * `expected_active_id` - The active bin ID observed off-chain.
+/// * `max_active_bin_slippage` - Maximum number of bins the active bin may
+///   have moved from `expected_active_id`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `EmptyBinList` if `bin_liquidity_removal` is empty, `InvalidAmount` if
+/// an entry removes 0 bps, or `SlippageExceeded` if the active bin moved
+/// more than `max_active_bin_slippage` from `expected_active_id`.
+pub fn handle_dlmm_remove_and_balance<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmRemoveAndBalance<'info>>,
+    bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+    target_ratio_bps: u16,
+    max_swap_slippage_bps: u16,
+    expected_active_id: i32,
+    max_active_bin_slippage: i32,
+) -> Result<()> {
+    verify_bin_liquidity_removal(&bin_liquidity_removal)?;
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(target_ratio_bps <= 10_000, DlmmCpiError::InvalidBps);
+    require!(max_swap_slippage_bps <= 10_000, DlmmCpiError::InvalidBps);
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    // 1. Remove liquidity
+    require_compute_units(REMOVE_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::RemoveLiquidity {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::remove_liquidity(cpi_context, bin_liquidity_removal)?;
+
+    let withdrawn_x = accessor::amount(&ctx.accounts.user_token_x)?.saturating_sub(x_before);
+    let withdrawn_y = accessor::amount(&ctx.accounts.user_token_y)?.saturating_sub(y_before);
+
+    // 2. Work out which side is in excess, valued in token Y at the active price
+    let price = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+        let deviation = (i64::from(lb_pair.active_id) - i64::from(expected_active_id)).abs();
+        require!(
+            deviation <= i64::from(max_active_bin_slippage),
+            DlmmCpiError::SlippageExceeded
+        );
+
+        price_from_bin_id(lb_pair.active_id, lb_pair.bin_step).ok_or(DlmmCpiError::MathOverflow)?
+    };
+
+    let value_x = mul_q64(u128::from(withdrawn_x), price).ok_or(DlmmCpiError::MathOverflow)?;
+    let value_y = u128::from(withdrawn_y);
+    let target_value_x = value_x
+        .checked_add(value_y)
+        .and_then(|value| value.checked_mul(u128::from(target_ratio_bps)))
+        .ok_or(DlmmCpiError::MathOverflow)?
+        / 10_000;
+
+    let (swap_for_y, amount_in, expected_out) = if value_x > target_value_x {
+        let excess_value = value_x - target_value_x;
+        let amount_in = div_by_price(excess_value, price).ok_or(DlmmCpiError::MathOverflow)?;
+        (true, amount_in, excess_value)
+    } else {
+        let excess_value = target_value_x - value_x;
+        let expected_out = div_by_price(excess_value, price).ok_or(DlmmCpiError::MathOverflow)?;
+        (false, excess_value, expected_out)
+    };
+
+    let amount_in = u64::try_from(amount_in).map_err(|_| DlmmCpiError::MathOverflow)?;
+    if amount_in == 0 {
+        return Ok(());
+    }
+
+    let min_amount_out = expected_out
+        .checked_mul(u128::from(10_000 - max_swap_slippage_bps))
+        .ok_or(DlmmCpiError::MathOverflow)?
+        / 10_000;
+    let min_amount_out = u64::try_from(min_amount_out).map_err(|_| DlmmCpiError::MathOverflow)?;
+
+    // 3. Swap the excess
+    let (user_token_in, user_token_out) = if swap_for_y {
+        (&ctx.accounts.user_token_x, &ctx.accounts.user_token_y)
+    } else {
+        (&ctx.accounts.user_token_y, &ctx.accounts.user_token_x)
+    };
+
+    require_compute_units(SWAP_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: user_token_in.to_account_info(),
+        user_token_out: user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: None,
+        user: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)
+}
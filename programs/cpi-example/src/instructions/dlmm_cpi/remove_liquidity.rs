@@ -1,5 +1,12 @@
 use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityRemovedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, verify_bin_array, verify_bin_liquidity_removal,
+    verify_dlmm_owned, verify_event_authority, verify_reserves, MAX_BINS_PER_REMOVE,
+};
 use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
 
 #[derive(Accounts)]
 pub struct DlmmRemoveLiquidity<'info> {
@@ -78,22 +85,79 @@ pub struct DlmmRemoveLiquidity<'info> {
 /// The position account remains open after this call; use `close_position`
 /// only after all bins have been fully drained.
 ///
+/// Emits `LiquidityRemovedEvent` with the amounts received and the number
+/// of bins in `bin_liquidity_removal` once the CPI succeeds.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing all required accounts.
-/// * `bin_liquidity_removal` - A list of per-bin removal instructions. Each entry
-///   specifies a `bin_id` and `bps_to_remove` (basis points out of 10000):
+/// * `bin_liquidity_removal` - A list of at most `MAX_BINS_PER_REMOVE` (70)
+///   per-bin removal instructions. Each entry specifies a `bin_id` and
+///   `bps_to_remove` (basis points out of 10000):
 ///   - 10000 bps = 100% (full removal from that bin)
 ///   -  5000 bps =  50% (partial removal from that bin)
 ///   Only bins listed here are affected; unlisted bins are untouched.
+/// * `min_amount_x` / `min_amount_y` - Minimum amounts of token X and Y the
+///   user token accounts must receive. A swap landing just before the
+///   withdrawal can shift the bins' composition towards the other token;
+///   the minimums bound how much. Pass 0 to accept any amount.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating success or failure.
+/// Returns a `Result` indicating success or failure. Fails with
+/// `TooManyBins` if `bin_liquidity_removal` has more than
+/// `MAX_BINS_PER_REMOVE` entries, `EmptyBinList` if it is empty or
+/// `InvalidAmount` if an entry removes 0 bps, with `ReservesSwapped` if `reserve_x` and
+/// `reserve_y` are passed the wrong way round, with `ReserveMismatch` if either is not the pool's reserve,
+/// with `InvalidBinArrayPda` if `bin_array_lower` or `bin_array_upper` is
+/// not the bin array covering the position's lower or upper bin, and with
+/// `SlippageExceeded` if less than `min_amount_x` or `min_amount_y` was
+/// received.
 pub fn handle_dlmm_remove_liquidity(
     ctx: Context<DlmmRemoveLiquidity>,
     bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+    min_amount_x: u64,
+    min_amount_y: u64,
 ) -> Result<()> {
+    require!(
+        bin_liquidity_removal.len() <= MAX_BINS_PER_REMOVE,
+        DlmmCpiError::TooManyBins
+    );
+    verify_bin_liquidity_removal(&bin_liquidity_removal)?;
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+    }
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    let bin_count = bin_liquidity_removal.len() as u32;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
     let accounts = dlmm::cpi::accounts::RemoveLiquidity {
         position: ctx.accounts.position.to_account_info(),
         lb_pair: ctx.accounts.lb_pair.to_account_info(),
@@ -120,5 +184,28 @@ pub fn handle_dlmm_remove_liquidity(
     let cpi_context =
         CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
 
-    dlmm::cpi::remove_liquidity(cpi_context, bin_liquidity_removal)
+    dlmm::cpi::remove_liquidity(cpi_context, bin_liquidity_removal)?;
+
+    let amount_x = accessor::amount(&ctx.accounts.user_token_x)?
+        .checked_sub(x_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = accessor::amount(&ctx.accounts.user_token_y)?
+        .checked_sub(y_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    require!(
+        amount_x >= min_amount_x && amount_y >= min_amount_y,
+        DlmmCpiError::SlippageExceeded
+    );
+
+    emit!(LiquidityRemovedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: ctx.accounts.lb_pair.key(),
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+        bin_count,
+    });
+
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+use super::remove_liquidity::DlmmRemoveLiquidity;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::events::LiquidityRemovedEvent;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, verify_bin_array, verify_dlmm_owned,
+    verify_event_authority, verify_reserves,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// Removes liquidity from a contiguous range of bins in a Meteora DLMM
+/// position.
+///
+/// Withdraws the same percentage, `bps_to_remove`, from every bin in the
+/// inclusive range `from_bin_id..=to_bin_id`, instead of listing each bin
+/// in a `bin_liquidity_removal` like `dlmm_remove_liquidity` does. Bins of
+/// the range without liquidity are skipped by the DLMM program.
+///
+/// Accounts are the same as `dlmm_remove_liquidity`.
+///
+/// Emits `LiquidityRemovedEvent` with the amounts received and the number
+/// of bins in the range once the CPI succeeds.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `from_bin_id` - First bin of the range.
+/// * `to_bin_id` - Last bin of the range, included.
+/// * `bps_to_remove` - Share of each bin's liquidity to withdraw, in basis
+///   points out of 10000.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBinRange` if `from_bin_id` is greater than `to_bin_id`, with
+/// `InvalidAmount` if `bps_to_remove` is 0, with `InvalidBps` if it exceeds
+/// 10000, and with the same
+/// account errors as `dlmm_remove_liquidity`.
+pub fn handle_dlmm_remove_liquidity_by_range(
+    ctx: Context<DlmmRemoveLiquidity>,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Result<()> {
+    require!(bps_to_remove > 0, DlmmCpiError::InvalidAmount);
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(from_bin_id <= to_bin_id, DlmmCpiError::InvalidBinRange);
+    require!(
+        bps_to_remove <= dlmm::constants::BASIS_POINT_MAX as u16,
+        DlmmCpiError::InvalidBps
+    );
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+    }
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::RemoveLiquidityByRange {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::remove_liquidity_by_range(cpi_context, from_bin_id, to_bin_id, bps_to_remove)?;
+
+    let amount_x = accessor::amount(&ctx.accounts.user_token_x)?
+        .checked_sub(x_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_y = accessor::amount(&ctx.accounts.user_token_y)?
+        .checked_sub(y_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    emit!(LiquidityRemovedEvent {
+        position: ctx.accounts.position.key(),
+        lb_pair: lb_pair_key,
+        sender: ctx.accounts.sender.key(),
+        amount_x,
+        amount_y,
+        bin_count: to_bin_id.abs_diff(from_bin_id) + 1,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use super::remove_liquidity::{handle_dlmm_remove_liquidity, DlmmRemoveLiquidity};
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+
+/// Removes liquidity from a Meteora DLMM position after checking it is the
+/// position the client meant to modify.
+///
+/// Uses the same accounts as `dlmm_remove_liquidity`. Before anything else,
+/// the position's stored `lb_pair` and `owner` are compared with the values
+/// the client pinned when building the transaction, and the `lb_pair`
+/// account passed must be that pool. A stale or mixed-up position key then
+/// fails up front instead of withdrawing from the wrong position.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `expected_lb_pair` - The pool the position must belong to.
+/// * `expected_owner` - The owner the position must have.
+/// * `bin_liquidity_removal` / `min_amount_x` / `min_amount_y` - As in
+///   `dlmm_remove_liquidity`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PositionLbPairMismatch` if the position or the `lb_pair` account is not
+/// `expected_lb_pair`, `PositionOwnerMismatch` if the position's owner is
+/// not `expected_owner`, and otherwise like `dlmm_remove_liquidity`.
+pub fn handle_dlmm_remove_liquidity_guarded(
+    ctx: Context<DlmmRemoveLiquidity>,
+    expected_lb_pair: Pubkey,
+    expected_owner: Pubkey,
+    bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+    min_amount_x: u64,
+    min_amount_y: u64,
+) -> Result<()> {
+    {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+        require_keys_eq!(
+            position.lb_pair,
+            expected_lb_pair,
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.lb_pair.key(),
+            expected_lb_pair,
+            DlmmCpiError::PositionLbPairMismatch
+        );
+        require_keys_eq!(
+            position.owner,
+            expected_owner,
+            DlmmCpiError::PositionOwnerMismatch
+        );
+    }
+
+    handle_dlmm_remove_liquidity(ctx, bin_liquidity_removal, min_amount_x, min_amount_y)
+}
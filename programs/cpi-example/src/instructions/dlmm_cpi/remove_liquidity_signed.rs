@@ -0,0 +1,193 @@
+use crate::dlmm;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, pda_cpi_context, verify_bin_array,
+    verify_bin_liquidity_removal, verify_dlmm_owned, verify_event_authority, verify_reserves,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(position_owner_bump: u8)]
+pub struct DlmmRemoveLiquiditySigned<'info> {
+    #[account(mut)]
+    /// CHECK: The position account. Must be owned by position_owner.
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Token account to receive withdrawn token X.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token account to receive withdrawn token Y.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the position. PDA of this program, signs the CPI.
+    /// PDA: ["position_owner"], with the bump passed in instruction data.
+    #[account(
+        seeds = [b"position_owner"],
+        bump = position_owner_bump
+    )]
+    pub position_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Only admin can move liquidity of positions owned by the program.
+    #[account(
+        constraint = crate::assert_eq_admin(cpi_example_admin.key())
+    )]
+    pub cpi_example_admin: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Removes liquidity from specific bins of a Meteora DLMM position owned by
+/// this program.
+///
+/// `dlmm_remove_liquidity` requires the position owner to sign the
+/// transaction, which a program can't do. Positions created with
+/// `dlmm_initialize_position_pda` are owned by `position_owner`, a PDA of
+/// this program, so here the program signs the CPI as the owner instead.
+///
+/// Seed scheme: `position_owner` is
+/// `find_program_address(&[b"position_owner"], &cpi_example::ID)`, a single
+/// owner for every position of the program. The caller passes the bump
+/// returned by `find_program_address` in instruction data, and the account
+/// is checked against
+/// `create_program_address(&[b"position_owner", &[bump]], &cpi_example::ID)`
+/// instead of searching for the bump on-chain. The same seeds, with that
+/// bump, sign the CPI.
+///
+/// Since the program, not the caller, authorizes the withdrawal,
+/// `cpi_example_admin` must sign, and the tokens go to any `user_token_x`
+/// and `user_token_y` the admin chooses.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `position_owner_bump` - Bump of the `position_owner` PDA.
+/// * `bin_liquidity_removal` - Bins to withdraw from and the share of each
+///   to remove in basis points, as for `dlmm_remove_liquidity`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ConstraintSeeds` if `position_owner` is not the PDA of the bump, or with
+/// `EmptyBinList`, `InvalidAmount`, `ReservesSwapped`, `ReserveMismatch` or
+/// `InvalidBinArrayPda` like
+/// `dlmm_remove_liquidity`.
+pub fn handle_dlmm_remove_liquidity_signed(
+    ctx: Context<DlmmRemoveLiquiditySigned>,
+    position_owner_bump: u8,
+    bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+) -> Result<()> {
+    verify_bin_liquidity_removal(&bin_liquidity_removal)?;
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let lb_pair_key = ctx.accounts.lb_pair.key();
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_lower.key(),
+            bin_array_index(position.lower_bin_id),
+        )?;
+        verify_bin_array(
+            lb_pair_key,
+            ctx.accounts.bin_array_upper.key(),
+            bin_array_index(position.upper_bin_id),
+        )?;
+    }
+
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    let accounts = dlmm::cpi::accounts::RemoveLiquidity {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.position_owner.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let seeds = [b"position_owner".as_ref(), &[position_owner_bump]];
+
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_context = pda_cpi_context(
+        ctx.accounts.dlmm_program.to_account_info(),
+        accounts,
+        signer_seeds,
+    );
+
+    dlmm::cpi::remove_liquidity(cpi_context, bin_liquidity_removal)
+}
@@ -0,0 +1,200 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    load_dlmm_account, require_compute_units, verify_bin_liquidity_removal, verify_dlmm_owned,
+    verify_event_authority, ADD_LIQUIDITY_COMPUTE_UNITS, REMOVE_LIQUIDITY_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmShiftLiquidity<'info> {
+    #[account(mut)]
+    /// CHECK: The user's position account
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account receiving withdrawn token X and funding the re-add.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account receiving withdrawn token Y and funding the re-add.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+}
+
+/// Moves liquidity between bins of the same Meteora DLMM position.
+///
+/// Removes liquidity from the bins listed in `from_removals`, then deposits
+/// exactly the withdrawn token X and Y amounts back into the position with
+/// `add_liquidity_by_strategy` over the range of `to_add`. The `amount_x` and
+/// `amount_y` of `to_add` are ignored and replaced by the withdrawn amounts.
+///
+/// Accounts are the same as `dlmm_remove_liquidity`: the position, the pool,
+/// its reserves and mints, the user token accounts that receive the
+/// withdrawn tokens and fund the re-add, and the bin arrays covering the
+/// position. Both sets of bins must be within the position's range, so the
+/// same two bin arrays serve both CPIs.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `from_removals` - Bins to withdraw from, see `dlmm_remove_liquidity`.
+/// * `to_add` - Strategy to redeposit the withdrawn tokens with, see
+///   `dlmm_add_liquidity_by_strategy`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `EmptyBinList` if `from_removals` is empty, `InvalidAmount` if a removal
+/// is 0 bps, or with `BinOutOfPositionRange` if a removal bin or the
+/// strategy range is outside the position.
+pub fn handle_dlmm_shift_liquidity(
+    ctx: Context<DlmmShiftLiquidity>,
+    from_removals: Vec<dlmm::types::BinLiquidityReduction>,
+    mut to_add: dlmm::types::LiquidityParameterByStrategy,
+) -> Result<()> {
+    verify_bin_liquidity_removal(&from_removals)?;
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    {
+        let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+        let in_range =
+            |bin_id: i32| bin_id >= position.lower_bin_id && bin_id <= position.upper_bin_id;
+
+        require!(
+            from_removals.iter().all(|removal| in_range(removal.bin_id)),
+            DlmmCpiError::BinOutOfPositionRange
+        );
+        require!(
+            in_range(to_add.strategy_parameters.min_bin_id)
+                && in_range(to_add.strategy_parameters.max_bin_id),
+            DlmmCpiError::BinOutOfPositionRange
+        );
+    }
+
+    let x_before = accessor::amount(&ctx.accounts.user_token_x)?;
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    // 1. Remove liquidity from the source bins
+    require_compute_units(REMOVE_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::RemoveLiquidity {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::remove_liquidity(cpi_context, from_removals)?;
+
+    to_add.amount_x = accessor::amount(&ctx.accounts.user_token_x)?.saturating_sub(x_before);
+    to_add.amount_y = accessor::amount(&ctx.accounts.user_token_y)?.saturating_sub(y_before);
+
+    // 2. Redeposit the withdrawn tokens into the target bins
+    require_compute_units(ADD_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityByStrategy {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity_by_strategy(cpi_context, to_add)
+}
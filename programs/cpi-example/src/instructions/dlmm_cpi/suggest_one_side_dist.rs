@@ -0,0 +1,189 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Every bin gets the same weight.
+pub const SHAPE_UNIFORM: u8 = 0;
+/// Weights fall off linearly away from the active bin.
+pub const SHAPE_LINEAR: u8 = 1;
+/// Weights fall off quadratically away from the active bin.
+pub const SHAPE_CONCENTRATED: u8 = 2;
+
+#[derive(Accounts)]
+pub struct DlmmSuggestOneSideDist<'info> {
+    /// CHECK: The pool account, read for its active bin.
+    pub lb_pair: UncheckedAccount<'info>,
+}
+
+/// Builds a single-sided distribution over `[from_bin_id, to_bin_id]` for
+/// the given shape.
+///
+/// A range strictly above `active_id` takes token X and a range at or below
+/// it takes token Y. Bins are ranked by their distance from the active bin,
+/// the nearest first; with `n` bins, the bin of rank `k` gets weight 1
+/// (uniform), `n - k` (linear) or `(n - k)^2` (concentrated).
+pub fn suggest_one_side_dist(
+    from_bin_id: i32,
+    to_bin_id: i32,
+    active_id: i32,
+    shape: u8,
+) -> Result<Vec<dlmm::types::BinLiquidityDistributionByWeight>> {
+    require!(from_bin_id <= to_bin_id, DlmmCpiError::InvalidBinRange);
+
+    let width = i64::from(to_bin_id) - i64::from(from_bin_id) + 1;
+    require!(
+        width <= dlmm::constants::MAX_BIN_PER_POSITION as i64,
+        DlmmCpiError::InvalidPositionWidth
+    );
+
+    let deposit_is_x = if from_bin_id > active_id {
+        true
+    } else if to_bin_id <= active_id {
+        false
+    } else {
+        return err!(DlmmCpiError::InvalidBinForSide);
+    };
+
+    (from_bin_id..=to_bin_id)
+        .map(|bin_id| {
+            let rank = if deposit_is_x {
+                bin_id - from_bin_id
+            } else {
+                to_bin_id - bin_id
+            };
+            // At most 70, so the square fits in a u16
+            let step = (width - i64::from(rank)) as u16;
+
+            let weight = match shape {
+                SHAPE_UNIFORM => 1,
+                SHAPE_LINEAR => step,
+                SHAPE_CONCENTRATED => step * step,
+                _ => return err!(DlmmCpiError::InvalidDistributionShape),
+            };
+
+            Ok(dlmm::types::BinLiquidityDistributionByWeight { bin_id, weight })
+        })
+        .collect()
+}
+
+/// Suggests a weight distribution for a single-sided deposit into a Meteora
+/// DLMM pool, to pass to `dlmm_add_liquidity_one_side`.
+///
+/// The side follows from the range and the pool's current active bin: a
+/// range strictly above it is a token X deposit, a range at or below it a
+/// token Y deposit. The linear and concentrated shapes put the most weight
+/// next to the active bin, where the liquidity is traded first.
+///
+/// The result is written with `set_return_data` as a Borsh serialized
+/// `Vec<BinLiquidityDistributionByWeight>`, one entry per bin from
+/// `from_bin_id` to `to_bin_id`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `from_bin_id` - The lowest bin of the range.
+/// * `to_bin_id` - The highest bin of the range, at most
+///   `MAX_BIN_PER_POSITION` (70) bins above `from_bin_id` inclusive.
+/// * `shape` - `SHAPE_UNIFORM` (0), `SHAPE_LINEAR` (1) or
+///   `SHAPE_CONCENTRATED` (2).
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidBinRange` if `from_bin_id` is greater than `to_bin_id`,
+/// `InvalidPositionWidth` if the range is wider than a position,
+/// `InvalidBinForSide` if the range contains both the active bin and the
+/// bin above it, or `InvalidDistributionShape` for an unknown shape.
+pub fn handle_dlmm_suggest_one_side_dist(
+    ctx: Context<DlmmSuggestOneSideDist>,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    shape: u8,
+) -> Result<()> {
+    let active_id = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.active_id;
+
+    let dist = suggest_one_side_dist(from_bin_id, to_bin_id, active_id, shape)?;
+
+    set_return_data(&dist.try_to_vec()?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Active bin is 100
+    fn weights(from_bin_id: i32, to_bin_id: i32, shape: u8) -> Vec<(i32, u16)> {
+        suggest_one_side_dist(from_bin_id, to_bin_id, 100, shape)
+            .unwrap()
+            .iter()
+            .map(|dist| (dist.bin_id, dist.weight))
+            .collect()
+    }
+
+    #[test]
+    fn test_suggest_one_side_dist_uniform() {
+        assert_eq!(
+            weights(101, 104, SHAPE_UNIFORM),
+            [(101, 1), (102, 1), (103, 1), (104, 1)]
+        );
+    }
+
+    #[test]
+    fn test_suggest_one_side_dist_linear() {
+        // Token X, heaviest just above the active bin
+        assert_eq!(
+            weights(101, 104, SHAPE_LINEAR),
+            [(101, 4), (102, 3), (103, 2), (104, 1)]
+        );
+        // Token Y, heaviest at the active bin
+        assert_eq!(
+            weights(97, 100, SHAPE_LINEAR),
+            [(97, 1), (98, 2), (99, 3), (100, 4)]
+        );
+    }
+
+    #[test]
+    fn test_suggest_one_side_dist_concentrated() {
+        assert_eq!(
+            weights(101, 104, SHAPE_CONCENTRATED),
+            [(101, 16), (102, 9), (103, 4), (104, 1)]
+        );
+        assert_eq!(
+            weights(97, 100, SHAPE_CONCENTRATED),
+            [(97, 1), (98, 4), (99, 9), (100, 16)]
+        );
+
+        // The widest range still fits the weights in a u16
+        let dist = weights(101, 170, SHAPE_CONCENTRATED);
+        assert_eq!(dist.len(), 70);
+        assert_eq!(dist[0], (101, 4900));
+    }
+
+    #[test]
+    fn test_suggest_one_side_dist_invalid() {
+        let error = |from_bin_id, to_bin_id, shape| {
+            suggest_one_side_dist(from_bin_id, to_bin_id, 100, shape).unwrap_err()
+        };
+
+        assert_eq!(
+            error(100, 101, SHAPE_UNIFORM),
+            DlmmCpiError::InvalidBinForSide.into()
+        );
+        assert_eq!(
+            error(104, 101, SHAPE_UNIFORM),
+            DlmmCpiError::InvalidBinRange.into()
+        );
+        assert_eq!(
+            error(101, 171, SHAPE_UNIFORM),
+            DlmmCpiError::InvalidPositionWidth.into()
+        );
+        assert_eq!(
+            error(101, 104, 3),
+            DlmmCpiError::InvalidDistributionShape.into()
+        );
+    }
+}
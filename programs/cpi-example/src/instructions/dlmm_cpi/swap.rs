@@ -1,5 +1,9 @@
 use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, swap_for_y, transfer_hook_program, verify_event_authority};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::accessor;
 
 #[derive(Accounts)]
 pub struct DlmmSwap<'info> {
@@ -34,7 +38,9 @@ pub struct DlmmSwap<'info> {
     pub oracle: UncheckedAccount<'info>,
 
     #[account(mut)]
-    /// CHECK: Referral fee account
+    /// CHECK: Referral fee account of the host (e.g. a front-end), receiving
+    /// part of the swap fee. When passed, it must be an initialized token
+    /// account of the input token owned by the host. Pass None for no host fee.
     pub host_fee_in: Option<UncheckedAccount<'info>>,
 
     /// CHECK: User who's executing the swap
@@ -51,25 +57,119 @@ pub struct DlmmSwap<'info> {
     pub token_x_program: UncheckedAccount<'info>,
     /// CHECK: Token program of mint Y
     pub token_y_program: UncheckedAccount<'info>,
-    // Bin arrays need to be passed using remaining accounts
+    // Bin arrays need to be passed using remaining accounts, in swap order
 }
 
 /// Executes a DLMM swap
 ///
+/// The direction follows the mint of `user_token_in`: token X in swaps for
+/// token Y and the other way round. `user_token_out` must hold the opposite
+/// token of the pool, which is checked before the CPI.
+///
+/// The amount received by `user_token_out`, measured from its balance before
+/// and after the CPI, is written as a little-endian `u64` with
+/// `set_return_data`. Clients can read it from the transaction's return data,
+/// or simulate the transaction to quote the output without executing it.
+///
+/// With `preserve_inner_return_data` set, no return data is written and the
+/// transaction's return data is left as the DLMM CPI ended it, tagged with
+/// the program that set it. This suits a caller that expects the DLMM
+/// program's own output, but gives up the measured `amount_out`, and there
+/// may be nothing to read: the DLMM swap sets no return data of its own and
+/// the runtime clears it before each CPI the swap makes.
+///
+/// The bin arrays are passed as remaining accounts, writable, in the order
+/// the swap traverses them: the active bin array first, then the next ones
+/// in the swap direction (lower indexes when selling token X, higher when
+/// selling token Y). There is no fixed limit, so a swap crossing many bins
+/// can pass as many bin arrays as it needs. The DLMM program fails the swap
+/// if it runs out of bin arrays before the input is consumed.
+///
+/// Because every remaining account is taken as a bin array, there is no room
+/// for the extra accounts of a Token-2022 transfer hook. Pools with a hook
+/// mint are rejected before the CPI rather than failing inside the transfer.
+///
+/// When `host_fee_in` is passed, the DLMM program sends it a share of the
+/// swap fee (`HOST_FEE_BPS`, 20%, of the protocol fee). The user pays the
+/// same fee either way, so front-ends can collect a referral fee without
+/// worsening the swap.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context containing accounts and programs.
 /// * `amount_in` - The amount of input tokens to be swapped.
 /// * `min_amount_out` - The minimum amount of output tokens expected a.k.a slippage
+/// * `reject_zero_min_out` - If true, a `min_amount_out` of 0 is rejected
+///   instead of executing the swap without slippage protection. Pass false
+///   to allow it.
+/// * `preserve_inner_return_data` - If true, skip writing `amount_out` as
+///   return data so the DLMM CPI's return data reaches the outer caller.
+/// * `expected_active_id` - The active bin ID observed off-chain prior to
+///   building the transaction.
+/// * `max_active_bin_slippage` - Maximum allowed bin ID deviation from
+///   `expected_active_id` at execution time. When both are set, the swap
+///   fails before the CPI if the pool's active bin has moved further,
+///   without waiting for `min_amount_out` to catch the worse price. Pass
+///   None for either to skip the check.
 ///
 /// # Returns
 ///
-/// Returns a `Result` indicating success or failure.
+/// Returns a `Result` indicating success or failure. Fails with
+/// `NoBinArrays` if no remaining accounts are passed, `ZeroMinOut` if
+/// `reject_zero_min_out` is set and `min_amount_out` is 0, `MintNotInPool`
+/// if `user_token_in` holds neither of the pool's tokens,
+/// `SwapDirectionMismatch` if `user_token_out` does not hold the other one,
+/// `SlippageExceeded` if the active bin moved more than
+/// `max_active_bin_slippage` from `expected_active_id`, or with
+/// `TransferHookUnsupported` if either mint has a transfer hook.
 pub fn handle_dlmm_swap<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
     amount_in: u64,
     min_amount_out: u64,
+    reject_zero_min_out: bool,
+    preserve_inner_return_data: bool,
+    expected_active_id: Option<i32>,
+    max_active_bin_slippage: Option<i32>,
 ) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        !reject_zero_min_out || min_amount_out > 0,
+        DlmmCpiError::ZeroMinOut
+    );
+
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        swap_for_y(
+            &lb_pair,
+            accessor::mint(&ctx.accounts.user_token_in)?,
+            accessor::mint(&ctx.accounts.user_token_out)?,
+        )?;
+
+        if let (Some(expected_active_id), Some(max_active_bin_slippage)) =
+            (expected_active_id, max_active_bin_slippage)
+        {
+            let deviation = (i64::from(lb_pair.active_id) - i64::from(expected_active_id)).abs();
+            require!(
+                deviation <= i64::from(max_active_bin_slippage),
+                DlmmCpiError::SlippageExceeded
+            );
+        }
+    }
+
+    require!(
+        transfer_hook_program(&ctx.accounts.token_x_mint)?.is_none()
+            && transfer_hook_program(&ctx.accounts.token_y_mint)?.is_none(),
+        DlmmCpiError::TransferHookUnsupported
+    );
+
+    let out_balance_before = accessor::amount(&ctx.accounts.user_token_out)?;
+
     let accounts = dlmm::cpi::accounts::Swap {
         lb_pair: ctx.accounts.lb_pair.to_account_info(),
         bin_array_bitmap_extension: ctx
@@ -98,5 +198,16 @@ pub fn handle_dlmm_swap<'a, 'b, 'c, 'info>(
 
     let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
         .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    // accessor reads the account data, which reflects the balance after the CPI
+    let amount_out = accessor::amount(&ctx.accounts.user_token_out)?
+        .checked_sub(out_balance_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    if !preserve_inner_return_data {
+        set_return_data(&amount_out.to_le_bytes());
+    }
+
+    Ok(())
 }
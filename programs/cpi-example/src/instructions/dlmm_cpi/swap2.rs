@@ -0,0 +1,190 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    load_dlmm_account, swap_for_y, transfer_fee_for_received_amount, transfer_hook_program,
+    verify_event_authority,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// What `min_amount_out` is compared against in `dlmm_swap2`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinAmountOutBasis {
+    /// The amount the pool sent out, before the output mint's Token-2022
+    /// transfer fee is withheld. Same as the DLMM program's own check.
+    SwapOutput,
+    /// The amount that actually landed in `user_token_out`, after the
+    /// output mint's Token-2022 transfer fee is withheld.
+    NetReceived,
+}
+
+#[derive(Accounts)]
+pub struct DlmmSwap2<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array extension account of the pool
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Reserve account of token X
+    pub reserve_x: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Reserve account of token Y
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to sell token
+    pub user_token_in: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: User token account to buy token
+    pub user_token_out: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account of token X
+    pub token_x_mint: UncheckedAccount<'info>,
+    /// CHECK: Mint account of token Y
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Referral fee account
+    pub host_fee_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: User who's executing the swap
+    pub user: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of mint X
+    pub token_x_program: UncheckedAccount<'info>,
+    /// CHECK: Token program of mint Y
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts
+}
+
+/// Executes a Token-2022 aware DLMM swap.
+///
+/// When the output mint charges a Token-2022 transfer fee, the amount
+/// received by `user_token_out` is lower than the swap output by that fee,
+/// so the DLMM program's own `min_amount_out` check (on the swap output)
+/// can pass while the user receives less than expected.
+///
+/// This handler measures the balance change of `user_token_out`, reads the
+/// output mint's transfer fee config for the current epoch and rebuilds the
+/// swap output as `received + fee`. `min_amount_out` is then checked against
+/// either value depending on `min_amount_out_basis`. For mints without a
+/// transfer fee both values are equal.
+///
+/// Transfer hook mints are not supported: their extra accounts can't be
+/// passed through the DLMM `swap` CPI, which takes all remaining accounts
+/// as bin arrays. Such pools are rejected before the CPI.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `min_amount_out` - The minimum amount of output tokens expected a.k.a slippage
+/// * `min_amount_out_basis` - Whether `min_amount_out` applies to the swap
+///   output or to the amount received net of the transfer fee.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `TransferHookUnsupported` if either mint has a transfer hook,
+/// `MintNotInPool` if `user_token_in` holds neither of the pool's tokens,
+/// `SwapDirectionMismatch` if `user_token_out` does not hold the other one,
+/// or with `SlippageExceeded` if the checked amount is below
+/// `min_amount_out`.
+pub fn handle_dlmm_swap2<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwap2<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    min_amount_out_basis: MinAmountOutBasis,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        transfer_hook_program(&ctx.accounts.token_x_mint)?.is_none()
+            && transfer_hook_program(&ctx.accounts.token_y_mint)?.is_none(),
+        DlmmCpiError::TransferHookUnsupported
+    );
+
+    let swap_for_y = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        swap_for_y(
+            &lb_pair,
+            accessor::mint(&ctx.accounts.user_token_in)?,
+            accessor::mint(&ctx.accounts.user_token_out)?,
+        )?
+    };
+    let out_mint = if swap_for_y {
+        &ctx.accounts.token_y_mint
+    } else {
+        &ctx.accounts.token_x_mint
+    };
+
+    let out_balance_before = accessor::amount(&ctx.accounts.user_token_out)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    // The DLMM program checks the swap output, which is never below the net
+    // amount, so passing min_amount_out through is safe for both bases.
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    let received = accessor::amount(&ctx.accounts.user_token_out)?
+        .checked_sub(out_balance_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    let checked_amount = match min_amount_out_basis {
+        MinAmountOutBasis::NetReceived => received,
+        MinAmountOutBasis::SwapOutput => {
+            let transfer_fee =
+                transfer_fee_for_received_amount(out_mint, received, Clock::get()?.epoch)?;
+            received
+                .checked_add(transfer_fee)
+                .ok_or(DlmmCpiError::MathOverflow)?
+        }
+    };
+
+    require!(
+        checked_amount >= min_amount_out,
+        DlmmCpiError::SlippageExceeded
+    );
+
+    Ok(())
+}
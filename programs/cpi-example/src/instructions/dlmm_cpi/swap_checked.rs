@@ -0,0 +1,164 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::verify_event_authority;
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// Maximum gap allowed between a reserve balance change and the matching user
+/// balance change, out of 10000.
+///
+/// The input reserve can receive less than `amount_in` because the host fee
+/// share is sent to `host_fee_in` and because of an input mint Token-2022
+/// transfer fee. The user can receive less than the output reserve paid out
+/// because of an output mint transfer fee. 1% covers both for regular pools;
+/// mints charging a higher transfer fee are rejected.
+pub const RESERVE_INVARIANT_TOLERANCE_BPS: u64 = 100;
+
+#[derive(Accounts)]
+pub struct DlmmSwapChecked<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array extension account of the pool
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Reserve account of token X
+    pub reserve_x: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Reserve account of token Y
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to sell token
+    pub user_token_in: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: User token account to buy token
+    pub user_token_out: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account of token X
+    pub token_x_mint: UncheckedAccount<'info>,
+    /// CHECK: Mint account of token Y
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Referral fee account
+    pub host_fee_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: User who's executing the swap
+    pub user: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of mint X
+    pub token_x_program: UncheckedAccount<'info>,
+    /// CHECK: Token program of mint Y
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts
+}
+
+/// Returns true if `actual` is at most `expected` and at most
+/// `RESERVE_INVARIANT_TOLERANCE_BPS` below it.
+fn within_tolerance(expected: u64, actual: u64) -> bool {
+    let min_actual =
+        u128::from(expected) * u128::from(10_000 - RESERVE_INVARIANT_TOLERANCE_BPS) / 10_000;
+    actual <= expected && u128::from(actual) >= min_actual
+}
+
+/// Executes a DLMM swap and checks the pool reserves moved consistently with
+/// the user's balances.
+///
+/// After the swap, the input reserve must have grown by `amount_in` and the
+/// output reserve must have shrunk by the amount received by the user, both
+/// within `RESERVE_INVARIANT_TOLERANCE_BPS`. This catches an inner program
+/// that reports a swap without moving the reserves accordingly.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `min_amount_out` - The minimum amount of output tokens expected a.k.a slippage
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ReserveInvariantViolated` if the reserves are inconsistent with the swap.
+pub fn handle_dlmm_swap_checked<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwapChecked<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let (reserve_in, reserve_out) =
+        if accessor::mint(&ctx.accounts.user_token_in)? == ctx.accounts.token_x_mint.key() {
+            (&ctx.accounts.reserve_x, &ctx.accounts.reserve_y)
+        } else {
+            (&ctx.accounts.reserve_y, &ctx.accounts.reserve_x)
+        };
+
+    let reserve_in_before = accessor::amount(reserve_in)?;
+    let reserve_out_before = accessor::amount(reserve_out)?;
+    let user_out_before = accessor::amount(&ctx.accounts.user_token_out)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    let reserve_in_received = accessor::amount(reserve_in)?
+        .checked_sub(reserve_in_before)
+        .ok_or(DlmmCpiError::ReserveInvariantViolated)?;
+    let reserve_out_sent = reserve_out_before
+        .checked_sub(accessor::amount(reserve_out)?)
+        .ok_or(DlmmCpiError::ReserveInvariantViolated)?;
+    let user_out_received = accessor::amount(&ctx.accounts.user_token_out)?
+        .checked_sub(user_out_before)
+        .ok_or(DlmmCpiError::ReserveInvariantViolated)?;
+
+    require!(
+        within_tolerance(amount_in, reserve_in_received),
+        DlmmCpiError::ReserveInvariantViolated
+    );
+    require!(
+        within_tolerance(reserve_out_sent, user_out_received),
+        DlmmCpiError::ReserveInvariantViolated
+    );
+
+    Ok(())
+}
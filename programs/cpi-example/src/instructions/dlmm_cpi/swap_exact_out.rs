@@ -0,0 +1,89 @@
+use super::swap::DlmmSwap;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{transfer_hook_program, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+/// Executes a DLMM swap for an exact output amount.
+///
+/// Uses the same accounts as `dlmm_swap`, with the bin arrays the swap
+/// traverses passed as remaining accounts. The DLMM program fails the swap
+/// if the input needed to produce `out_amount` exceeds `max_in_amount`.
+///
+/// The DLMM program only transfers the input it needs, so the unspent part
+/// of `max_in_amount` never leaves `user_token_in` and no refund transfer is
+/// required. The unused amount is logged with `msg!`. No SOL is wrapped by
+/// this handler: if `user_token_in` is a WSOL account, the remainder stays
+/// wrapped until the caller closes or unwraps it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `max_in_amount` - The maximum amount of input tokens to spend a.k.a slippage
+/// * `out_amount` - The exact amount of output tokens to receive, in base
+///   units of the output token.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `NoBinArrays` if no remaining accounts are passed, or with
+/// `TransferHookUnsupported` if either mint has a transfer hook.
+pub fn handle_dlmm_swap_exact_out<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+    max_in_amount: u64,
+    out_amount: u64,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    require!(
+        transfer_hook_program(&ctx.accounts.token_x_mint)?.is_none()
+            && transfer_hook_program(&ctx.accounts.token_y_mint)?.is_none(),
+        DlmmCpiError::TransferHookUnsupported
+    );
+
+    let in_balance_before = accessor::amount(&ctx.accounts.user_token_in)?;
+
+    let accounts = dlmm::cpi::accounts::SwapExactOut {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap_exact_out(cpi_context, max_in_amount, out_amount)?;
+
+    let spent = in_balance_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_in)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let unused = max_in_amount.saturating_sub(spent);
+    msg!("Unused input amount: {}", unused);
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::state::PriceFeed;
+use crate::utils::{load_dlmm_account, swap_for_y, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmInitializePriceFeed<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceFeed::INIT_SPACE
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    /// CHECK: The pool whose swaps will update the feed
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// The only signer allowed to update the feed
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an empty price feed account to be updated by `dlmm_swap_feed_oracle`.
+///
+/// The feed is bound to `lb_pair` and `authority`: only swaps of that pool
+/// signed by `authority` can update it. The pool's token X and Y mints are
+/// recorded so readers know the base and quote of the price.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_initialize_price_feed(ctx: Context<DlmmInitializePriceFeed>) -> Result<()> {
+    let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+
+    let price_feed = &mut ctx.accounts.price_feed;
+    price_feed.authority = ctx.accounts.authority.key();
+    price_feed.lb_pair = ctx.accounts.lb_pair.key();
+    price_feed.token_x_mint = lb_pair.token_x_mint;
+    price_feed.token_y_mint = lb_pair.token_y_mint;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DlmmSwapFeedOracle<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array extension account of the pool
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Reserve account of token X
+    pub reserve_x: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Reserve account of token Y
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to sell token
+    pub user_token_in: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: User token account to buy token
+    pub user_token_out: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account of token X
+    pub token_x_mint: UncheckedAccount<'info>,
+    /// CHECK: Mint account of token Y
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Referral fee account
+    pub host_fee_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: User who's executing the swap
+    pub user: Signer<'info>,
+
+    /// Price feed updated with the realized price of this swap
+    #[account(mut, has_one = authority, has_one = lb_pair)]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    /// The authority of price_feed
+    pub authority: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of mint X
+    pub token_x_program: UncheckedAccount<'info>,
+    /// CHECK: Token program of mint Y
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts
+}
+
+/// Executes a DLMM swap and records the realized execution price into `price_feed`.
+///
+/// The price is measured from the user's token balance changes, so it
+/// includes swap fees. It is stored as token Y per token X in base units,
+/// in Q64.64 fixed point, together with the current slot. A swap selling
+/// token Y stores the inverse of its output per input, so the price has the
+/// same meaning in both directions.
+///
+/// Only swaps of the feed's pool signed by the feed's authority are
+/// accepted, so third parties can't overwrite the price with their own
+/// swaps.
+///
+/// Staleness: the feed is only refreshed by this instruction. Readers must
+/// check `price_feed.last_slot` against the current slot and decide
+/// themselves how old a price they accept.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `min_amount_out` - The minimum amount of output tokens expected a.k.a slippage
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `MintNotInPool` if `user_token_in` holds neither of the pool's tokens,
+/// `SwapDirectionMismatch` if `user_token_out` does not hold the other one,
+/// or `ZeroSwapAmount` if the swap consumed no input or returned no output.
+pub fn handle_dlmm_swap_feed_oracle<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwapFeedOracle<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let swap_for_y = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        swap_for_y(
+            &lb_pair,
+            accessor::mint(&ctx.accounts.user_token_in)?,
+            accessor::mint(&ctx.accounts.user_token_out)?,
+        )?
+    };
+
+    let in_balance_before = accessor::amount(&ctx.accounts.user_token_in)?;
+    let out_balance_before = accessor::amount(&ctx.accounts.user_token_out)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    let in_balance_after = accessor::amount(&ctx.accounts.user_token_in)?;
+    let out_balance_after = accessor::amount(&ctx.accounts.user_token_out)?;
+
+    let amount_in_used = in_balance_before.saturating_sub(in_balance_after);
+    let amount_out_received = out_balance_after.saturating_sub(out_balance_before);
+
+    require!(
+        amount_in_used > 0 && amount_out_received > 0,
+        DlmmCpiError::ZeroSwapAmount
+    );
+
+    let (amount_x, amount_y) = if swap_for_y {
+        (amount_in_used, amount_out_received)
+    } else {
+        (amount_out_received, amount_in_used)
+    };
+
+    // amount_y < 2^64, so shifting by 64 bits cannot overflow u128
+    let price = (u128::from(amount_y) << 64) / u128::from(amount_x);
+
+    let price_feed = &mut ctx.accounts.price_feed;
+    price_feed.last_price = price;
+    price_feed.last_slot = Clock::get()?.slot;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use super::swap::{handle_dlmm_swap, DlmmSwap};
+use super::swap_exact_out::handle_dlmm_swap_exact_out;
+use crate::errors::DlmmCpiError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::accessor;
+
+/// Which side of `dlmm_swap_flexible` is fixed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapMode {
+    /// `amount` is the exact input and `limit` the minimum output.
+    ExactIn,
+    /// `amount` is the exact output and `limit` the maximum input.
+    ExactOut,
+}
+
+/// Executes a DLMM swap for either an exact input or an exact output amount.
+///
+/// A single entry point for routers that support both kinds of swaps. It
+/// uses the same accounts as `dlmm_swap`, with the bin arrays the swap
+/// traverses passed as remaining accounts, and dispatches on `mode`:
+///
+/// - `SwapMode::ExactIn` sells exactly `amount` of the input token and fails
+///   if less than `limit` of the output token is received, like `dlmm_swap`.
+/// - `SwapMode::ExactOut` buys exactly `amount` of the output token and
+///   fails if more than `limit` of the input token is needed, like
+///   `dlmm_swap_exact_out`.
+///
+/// The amount on the other side is written as a little-endian `u64` with
+/// `set_return_data`: the output received for `ExactIn`, the input spent for
+/// `ExactOut`. Both are measured from the user's token account balances.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `mode` - Whether `amount` is the input or the output.
+/// * `amount` - The exact amount to swap, in base units of the input token
+///   for `ExactIn` and of the output token for `ExactOut`.
+/// * `limit` - The slippage bound: minimum output for `ExactIn`, maximum
+///   input for `ExactOut`.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_swap_flexible<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+    mode: SwapMode,
+    amount: u64,
+    limit: u64,
+) -> Result<()> {
+    match mode {
+        // Sets the amount out as return data
+        SwapMode::ExactIn => handle_dlmm_swap(ctx, amount, limit, false, false, None, None),
+        SwapMode::ExactOut => {
+            let user_token_in = ctx.accounts.user_token_in.to_account_info();
+            let in_balance_before = accessor::amount(&user_token_in)?;
+
+            handle_dlmm_swap_exact_out(ctx, limit, amount)?;
+
+            let amount_in = in_balance_before
+                .checked_sub(accessor::amount(&user_token_in)?)
+                .ok_or(DlmmCpiError::MathOverflow)?;
+
+            set_return_data(&amount_in.to_le_bytes());
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, swap_for_y, verify_event_authority};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use anchor_spl::token_2022::{transfer_checked, TransferChecked};
+
+#[derive(Accounts)]
+pub struct DlmmSwapSplitOutput<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array extension account of the pool
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Reserve account of token X
+    pub reserve_x: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Reserve account of token Y
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to sell token
+    pub user_token_in: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: User token account of the output token. Receives the swap
+    /// output first, then forwards all of it to the recipients, so its
+    /// balance ends where it started.
+    pub user_token_out: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token account of the output token receiving the output less the
+    /// split.
+    pub recipient_primary: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Token account of the output token receiving `split_bps` of the
+    /// output, e.g. a fee-sharing partner.
+    pub recipient_secondary: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account of token X
+    pub token_x_mint: UncheckedAccount<'info>,
+    /// CHECK: Mint account of token Y
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Referral fee account
+    pub host_fee_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: User who's executing the swap. Also the authority of
+    /// user_token_out for the transfers to the recipients.
+    pub user: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of mint X
+    pub token_x_program: UncheckedAccount<'info>,
+    /// CHECK: Token program of mint Y
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts, in swap order
+}
+
+/// Executes a DLMM swap and splits the output between two recipients.
+///
+/// The swap pays out to `user_token_out` as in `dlmm_swap`. The amount
+/// received is then forwarded with two transfers signed by `user`:
+/// `split_bps` of it, rounded down, to `recipient_secondary` and the rest to
+/// `recipient_primary`. Both transfers use `transfer_checked` with the output
+/// token's own program, so Token-2022 outputs work; a transfer fee on the
+/// output mint is taken from each transfer.
+///
+/// The accounts are those of `dlmm_swap` plus the two recipients, which
+/// must be token accounts of the output token. Bin arrays are passed as
+/// remaining accounts in swap order.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `min_amount_out` - The minimum amount `recipient_primary` must net,
+///   measured from its balance, after the split and any transfer fee.
+/// * `split_bps` - Share of the output sent to `recipient_secondary`, in
+///   basis points out of 10000.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with `InvalidBps`
+/// if `split_bps` exceeds 10000, `NoBinArrays` if no remaining accounts are
+/// passed, `MintNotInPool` if `user_token_in` holds neither of the pool's
+/// tokens, `SwapDirectionMismatch` if `user_token_out` does not hold the
+/// other one, or `SlippageExceeded` if `recipient_primary` nets less than
+/// `min_amount_out`.
+pub fn handle_dlmm_swap_split_output<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwapSplitOutput<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+    split_bps: u16,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(split_bps <= 10_000, DlmmCpiError::InvalidBps);
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    let swap_for_y = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        swap_for_y(
+            &lb_pair,
+            accessor::mint(&ctx.accounts.user_token_in)?,
+            accessor::mint(&ctx.accounts.user_token_out)?,
+        )?
+    };
+    let (token_out_mint, token_out_program) = if swap_for_y {
+        (&ctx.accounts.token_y_mint, &ctx.accounts.token_y_program)
+    } else {
+        (&ctx.accounts.token_x_mint, &ctx.accounts.token_x_program)
+    };
+
+    let out_balance_before = accessor::amount(&ctx.accounts.user_token_out)?;
+    let primary_balance_before = accessor::amount(&ctx.accounts.recipient_primary)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    // The primary's share is at most the total, so the same bound fails early
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    let amount_out = accessor::amount(&ctx.accounts.user_token_out)?
+        .checked_sub(out_balance_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    let amount_secondary = u64::try_from(u128::from(amount_out) * u128::from(split_bps) / 10_000)
+        .map_err(|_| DlmmCpiError::MathOverflow)?;
+    let amount_primary = amount_out - amount_secondary;
+
+    let decimals = {
+        let data = token_out_mint.try_borrow_data()?;
+        StateWithExtensions::<Token2022Mint>::unpack(&data)?
+            .base
+            .decimals
+    };
+
+    for (recipient, amount) in [
+        (&ctx.accounts.recipient_secondary, amount_secondary),
+        (&ctx.accounts.recipient_primary, amount_primary),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer_checked(
+            CpiContext::new(
+                token_out_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_out.to_account_info(),
+                    mint: token_out_mint.to_account_info(),
+                    to: recipient.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+            decimals,
+        )?;
+    }
+
+    let primary_received = accessor::amount(&ctx.accounts.recipient_primary)?
+        .checked_sub(primary_balance_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    require!(
+        primary_received >= min_amount_out,
+        DlmmCpiError::SlippageExceeded
+    );
+
+    Ok(())
+}
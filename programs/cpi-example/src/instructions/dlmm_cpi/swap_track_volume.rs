@@ -0,0 +1,185 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::state::VolumeStats;
+use crate::utils::verify_event_authority;
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmInitializeVolumeStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VolumeStats::INIT_SPACE
+    )]
+    pub stats: Account<'info, VolumeStats>,
+
+    /// The only signer allowed to add volume to stats
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an empty volume stats account to be updated by `dlmm_swap_track_volume`.
+///
+/// Only swaps signed by `authority` can add to it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_initialize_volume_stats(ctx: Context<DlmmInitializeVolumeStats>) -> Result<()> {
+    ctx.accounts.stats.authority = ctx.accounts.authority.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DlmmSwapTrackVolume<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Bin array extension account of the pool
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: Reserve account of token X
+    pub reserve_x: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Reserve account of token Y
+    pub reserve_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account to sell token
+    pub user_token_in: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: User token account to buy token
+    pub user_token_out: UncheckedAccount<'info>,
+
+    /// CHECK: Mint account of token X
+    pub token_x_mint: UncheckedAccount<'info>,
+    /// CHECK: Mint account of token Y
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Referral fee account
+    pub host_fee_in: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: User who's executing the swap
+    pub user: Signer<'info>,
+
+    /// Volume stats the swap is added to
+    #[account(mut, has_one = authority)]
+    pub stats: Account<'info, VolumeStats>,
+
+    /// The authority of stats
+    pub authority: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of mint X
+    pub token_x_program: UncheckedAccount<'info>,
+    /// CHECK: Token program of mint Y
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays need to be passed using remaining accounts
+}
+
+/// Executes a DLMM swap and adds it to the volume recorded in `stats`.
+///
+/// The amounts are measured from the user's token balance changes: the
+/// input includes swap fees and the output is after any Token-2022 transfer
+/// fee. The stats authority must sign, so third parties can't inflate the
+/// recorded volume with swaps of their own.
+///
+/// Overflow: the totals are `u128`, so they cannot overflow from `u64`
+/// swap amounts in practice, but every update uses checked arithmetic and
+/// fails the whole swap with `MathOverflow` rather than wrapping.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `min_amount_out` - The minimum amount of output tokens expected a.k.a slippage
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_swap_track_volume<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwapTrackVolume<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    let in_balance_before = accessor::amount(&ctx.accounts.user_token_in)?;
+    let out_balance_before = accessor::amount(&ctx.accounts.user_token_out)?;
+
+    let accounts = dlmm::cpi::accounts::Swap {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap(cpi_context, amount_in, min_amount_out)?;
+
+    let amount_in_used = in_balance_before
+        .checked_sub(accessor::amount(&ctx.accounts.user_token_in)?)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let amount_out_received = accessor::amount(&ctx.accounts.user_token_out)?
+        .checked_sub(out_balance_before)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    let stats = &mut ctx.accounts.stats;
+    stats.total_in = stats
+        .total_in
+        .checked_add(u128::from(amount_in_used))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    stats.total_out = stats
+        .total_out
+        .checked_add(u128::from(amount_out_received))
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    stats.swap_count = stats
+        .swap_count
+        .checked_add(1)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use super::swap::DlmmSwap;
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{transfer_hook_program, verify_event_authority};
+use anchor_lang::prelude::*;
+
+/// Executes a DLMM swap bounded by a maximum price impact.
+///
+/// Uses the same accounts as `dlmm_swap`, with the bin arrays the swap
+/// traverses passed as remaining accounts. Instead of a minimum output, the
+/// DLMM program fails the swap if the price moves by more than
+/// `max_price_impact_bps` from the price at `active_id`.
+///
+/// `active_id` sets the reference price of that check:
+/// - `Some(id)`: the impact is measured from the price of bin `id`, observed
+///   off-chain, so price movement before execution counts against
+///   `max_price_impact_bps`. This enforces the bin slippage as well.
+/// - `None`: the impact is measured from the pool's active bin at execution
+///   time, so there is no protection against movement before execution.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing accounts and programs.
+/// * `amount_in` - The amount of input tokens to be swapped.
+/// * `active_id` - The active bin ID observed off-chain, or None to skip the
+///   bin slippage check.
+/// * `max_price_impact_bps` - Maximum price impact allowed, out of 10000.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with `InvalidBps`
+/// if `max_price_impact_bps` exceeds 10000, `NoBinArrays` if no remaining
+/// accounts are passed, or with `TransferHookUnsupported` if either mint has
+/// a transfer hook.
+pub fn handle_dlmm_swap_with_price_impact<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+    amount_in: u64,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(max_price_impact_bps <= 10_000, DlmmCpiError::InvalidBps);
+
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    require!(
+        transfer_hook_program(&ctx.accounts.token_x_mint)?.is_none()
+            && transfer_hook_program(&ctx.accounts.token_y_mint)?.is_none(),
+        DlmmCpiError::TransferHookUnsupported
+    );
+
+    let accounts = dlmm::cpi::accounts::SwapWithPriceImpact {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        user_token_in: ctx.accounts.user_token_in.to_account_info(),
+        user_token_out: ctx.accounts.user_token_out.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        oracle: ctx.accounts.oracle.to_account_info(),
+        host_fee_in: ctx
+            .accounts
+            .host_fee_in
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user: ctx.accounts.user.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    dlmm::cpi::swap_with_price_impact(cpi_context, amount_in, active_id, max_price_impact_bps)
+}
@@ -0,0 +1,79 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::load_dlmm_account;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Token programs of a DLMM pool's mints, returned by `dlmm_token_programs`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolTokenPrograms {
+    /// Token (spl-token) or Token-2022, owner of the token X mint.
+    pub token_x_program: Pubkey,
+    /// Token (spl-token) or Token-2022, owner of the token Y mint.
+    pub token_y_program: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct DlmmTokenPrograms<'info> {
+    /// CHECK: The pool account. Read only.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+}
+
+/// Returns the program owning `mint`, which must be Token or Token-2022.
+fn mint_token_program(mint: &AccountInfo) -> Result<Pubkey> {
+    require!(
+        *mint.owner == anchor_spl::token::ID || *mint.owner == anchor_spl::token_2022::ID,
+        DlmmCpiError::UnknownTokenProgram
+    );
+    Ok(*mint.owner)
+}
+
+/// Reads which token program each side of a Meteora DLMM pool uses.
+///
+/// The pool itself doesn't record it, so the owners of the two mint
+/// accounts are read instead. Pass the results as `token_x_program` and
+/// `token_y_program` when assembling swap and liquidity instructions; a
+/// pool may mix spl-token and Token-2022.
+///
+/// The Borsh serialized `PoolTokenPrograms` is written with
+/// `set_return_data`.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `PoolMintMismatch` if a mint account is not the pool's mint of that side,
+/// or `UnknownTokenProgram` if a mint is owned by neither token program.
+pub fn handle_dlmm_token_programs(ctx: Context<DlmmTokenPrograms>) -> Result<()> {
+    {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        require_keys_eq!(
+            ctx.accounts.token_x_mint.key(),
+            lb_pair.token_x_mint,
+            DlmmCpiError::PoolMintMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.token_y_mint.key(),
+            lb_pair.token_y_mint,
+            DlmmCpiError::PoolMintMismatch
+        );
+    }
+
+    let token_programs = PoolTokenPrograms {
+        token_x_program: mint_token_program(&ctx.accounts.token_x_mint)?,
+        token_y_program: mint_token_program(&ctx.accounts.token_y_mint)?,
+    };
+
+    set_return_data(&token_programs.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::dlmm;
+use crate::utils::{verify_dlmm_owned, verify_event_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DlmmUpdatePositionOperator<'info> {
+    #[account(mut)]
+    /// CHECK: The position account whose operator is updated.
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: The current owner of the position. Must sign the transaction.
+    pub owner: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+}
+
+/// Sets the operator of a Meteora DLMM position.
+///
+/// Only the current owner can set a new operator. The operator may then add
+/// and remove liquidity on the position without owning it, which lets the
+/// owner delegate position management to a bot or another key. Withdrawn
+/// tokens still go to whichever token accounts the operator passes, so only
+/// set an operator that is trusted with the position's liquidity. Setting
+/// the owner's own key removes the delegation.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `operator` - The new operator of the position.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure.
+pub fn handle_dlmm_update_position_operator(
+    ctx: Context<DlmmUpdatePositionOperator>,
+    operator: Pubkey,
+) -> Result<()> {
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+
+    let accounts = dlmm::cpi::accounts::UpdatePositionOperator {
+        position: ctx.accounts.position.to_account_info(),
+        owner: ctx.accounts.owner.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::update_position_operator(cpi_context, operator)
+}
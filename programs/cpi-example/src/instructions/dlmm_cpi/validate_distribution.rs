@@ -0,0 +1,167 @@
+use crate::dlmm;
+use crate::utils::{has_duplicate_bins, load_dlmm_account, verify_dlmm_owned};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Result of `dlmm_validate_distribution`, returned as a single byte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DistributionStatus {
+    /// The distribution can be deposited.
+    Valid = 0,
+    /// A bin is on the wrong side of the active bin for the deposited token.
+    WrongSide = 1,
+    /// A bin is outside the position's range.
+    OutOfRange = 2,
+    /// The same bin appears more than once.
+    DuplicateBin = 3,
+    /// The distribution is empty or all its weights are zero.
+    ZeroWeight = 4,
+}
+
+#[derive(Accounts)]
+pub struct DlmmValidateDistribution<'info> {
+    /// CHECK: The position the distribution would be deposited into.
+    pub position: UncheckedAccount<'info>,
+}
+
+/// Checks a single-sided distribution against the rules of
+/// `add_liquidity_one_side`, in the order the reasons are listed in
+/// `DistributionStatus`, and returns the first failure.
+pub fn check_distribution(
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    active_id: i32,
+    bin_liquidity_dist: &[dlmm::types::BinLiquidityDistributionByWeight],
+    deposit_is_x: bool,
+) -> DistributionStatus {
+    let wrong_side = bin_liquidity_dist.iter().any(|dist| {
+        if deposit_is_x {
+            dist.bin_id <= active_id
+        } else {
+            dist.bin_id > active_id
+        }
+    });
+    if wrong_side {
+        return DistributionStatus::WrongSide;
+    }
+
+    if bin_liquidity_dist
+        .iter()
+        .any(|dist| dist.bin_id < lower_bin_id || dist.bin_id > upper_bin_id)
+    {
+        return DistributionStatus::OutOfRange;
+    }
+
+    if has_duplicate_bins(bin_liquidity_dist.iter().map(|dist| dist.bin_id)) {
+        return DistributionStatus::DuplicateBin;
+    }
+
+    if bin_liquidity_dist.iter().all(|dist| dist.weight == 0) {
+        return DistributionStatus::ZeroWeight;
+    }
+
+    DistributionStatus::Valid
+}
+
+/// Checks whether a single-sided distribution can be deposited into a
+/// Meteora DLMM position, without attempting the deposit.
+///
+/// Runs the side rule (token X strictly above `active_id`, token Y at or
+/// below it), the position range check, the duplicate bin check and the
+/// weight sum check. Instead of failing, the resulting `DistributionStatus`
+/// is written as a single byte with `set_return_data`, so UIs can simulate
+/// this instruction for instant feedback.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `active_id` - The active bin ID the deposit would be made at.
+/// * `bin_liquidity_dist` - Per-bin weight distribution, see
+///   `dlmm_add_liquidity_one_side`.
+/// * `deposit_is_x` - Whether token X (true) or token Y (false) is deposited.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Only fails if the
+/// position account can't be read.
+pub fn handle_dlmm_validate_distribution(
+    ctx: Context<DlmmValidateDistribution>,
+    active_id: i32,
+    bin_liquidity_dist: Vec<dlmm::types::BinLiquidityDistributionByWeight>,
+    deposit_is_x: bool,
+) -> Result<()> {
+    verify_dlmm_owned(&ctx.accounts.position)?;
+
+    let position = load_dlmm_account::<dlmm::accounts::PositionV2>(&ctx.accounts.position)?;
+
+    let status = check_distribution(
+        position.lower_bin_id,
+        position.upper_bin_id,
+        active_id,
+        &bin_liquidity_dist,
+        deposit_is_x,
+    );
+
+    set_return_data(&[status as u8]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dlmm::types::BinLiquidityDistributionByWeight;
+
+    fn dist(bins: &[(i32, u16)]) -> Vec<BinLiquidityDistributionByWeight> {
+        bins.iter()
+            .map(|&(bin_id, weight)| BinLiquidityDistributionByWeight { bin_id, weight })
+            .collect()
+    }
+
+    // Position covers [90, 109], active bin is 100
+    fn check(bins: &[(i32, u16)], deposit_is_x: bool) -> DistributionStatus {
+        check_distribution(90, 109, 100, &dist(bins), deposit_is_x)
+    }
+
+    #[test]
+    fn test_check_distribution_valid() {
+        assert_eq!(
+            check(&[(101, 1), (109, 1)], true),
+            DistributionStatus::Valid
+        );
+        assert_eq!(
+            check(&[(90, 1), (100, 1)], false),
+            DistributionStatus::Valid
+        );
+    }
+
+    #[test]
+    fn test_check_distribution_wrong_side() {
+        assert_eq!(check(&[(100, 1)], true), DistributionStatus::WrongSide);
+        assert_eq!(check(&[(101, 1)], false), DistributionStatus::WrongSide);
+    }
+
+    #[test]
+    fn test_check_distribution_out_of_range() {
+        assert_eq!(check(&[(110, 1)], true), DistributionStatus::OutOfRange);
+        assert_eq!(check(&[(89, 1)], false), DistributionStatus::OutOfRange);
+    }
+
+    #[test]
+    fn test_check_distribution_duplicate_bin() {
+        assert_eq!(
+            check(&[(102, 1), (101, 1), (102, 1)], true),
+            DistributionStatus::DuplicateBin
+        );
+    }
+
+    #[test]
+    fn test_check_distribution_zero_weight() {
+        assert_eq!(check(&[], true), DistributionStatus::ZeroWeight);
+        assert_eq!(
+            check(&[(101, 0), (102, 0)], true),
+            DistributionStatus::ZeroWeight
+        );
+    }
+}
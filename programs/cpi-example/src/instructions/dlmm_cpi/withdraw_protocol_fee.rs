@@ -0,0 +1,121 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{load_dlmm_account, verify_reserves};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmWithdrawProtocolFee<'info> {
+    #[account(mut)]
+    /// CHECK: The pool account whose accumulated protocol fee is withdrawn.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token X account of the fee owner receiving the protocol fee.
+    pub receiver_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Token Y account of the fee owner receiving the protocol fee.
+    pub receiver_token_y: UncheckedAccount<'info>,
+
+    /// CHECK: The protocol fee owner. Must sign the transaction and own
+    /// receiver_token_x and receiver_token_y.
+    pub fee_owner: Signer<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+}
+
+/// Withdraws the protocol fee accumulated by a Meteora DLMM pool.
+///
+/// Only the authorized protocol fee owner can collect it. The DLMM program
+/// itself takes no signer: it only pays into token accounts of the fee
+/// owner it is configured with, and rejects any other receiver. This
+/// wrapper additionally requires `fee_owner` to sign and to own both
+/// receiver accounts, so a caller cannot spend the CPI on receivers the
+/// DLMM program will refuse. A pool creator can only collect the fee this
+/// way if they are that fee owner.
+///
+/// The amounts withdrawn are `max_amount_x` and `max_amount_y`, capped at
+/// the fee accumulated in the pool, so `u64::MAX` withdraws all of it.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `max_amount_x` - Maximum amount of token X to withdraw.
+/// * `max_amount_y` - Maximum amount of token Y to withdraw.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `ReservesSwapped` or `ReserveMismatch` if the reserves are not the
+/// pool's, or `FeeOwnerMismatch` if a receiver account is not owned by
+/// `fee_owner`.
+pub fn handle_dlmm_withdraw_protocol_fee(
+    ctx: Context<DlmmWithdrawProtocolFee>,
+    max_amount_x: u64,
+    max_amount_y: u64,
+) -> Result<()> {
+    for receiver in [
+        &ctx.accounts.receiver_token_x,
+        &ctx.accounts.receiver_token_y,
+    ] {
+        require_keys_eq!(
+            accessor::authority(receiver)?,
+            ctx.accounts.fee_owner.key(),
+            DlmmCpiError::FeeOwnerMismatch
+        );
+    }
+
+    let (amount_x, amount_y) = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        verify_reserves(
+            &lb_pair,
+            ctx.accounts.reserve_x.key(),
+            ctx.accounts.reserve_y.key(),
+        )?;
+        (
+            max_amount_x.min(lb_pair.protocol_fee.amount_x),
+            max_amount_y.min(lb_pair.protocol_fee.amount_y),
+        )
+    };
+
+    let accounts = dlmm::cpi::accounts::WithdrawProtocolFee {
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        receiver_token_x: ctx.accounts.receiver_token_x.to_account_info(),
+        receiver_token_y: ctx.accounts.receiver_token_y.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::withdraw_protocol_fee(cpi_context, amount_x, amount_y)
+}
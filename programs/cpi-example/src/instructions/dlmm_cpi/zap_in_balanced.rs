@@ -0,0 +1,303 @@
+use crate::dlmm;
+use crate::errors::DlmmCpiError;
+use crate::utils::{
+    bin_array_index, load_dlmm_account, mul_q64, price_from_bin_id, require_compute_units,
+    verify_bin_array, verify_dlmm_owned, verify_event_authority, ADD_LIQUIDITY_COMPUTE_UNITS,
+    SWAP_COMPUTE_UNITS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::accessor;
+
+#[derive(Accounts)]
+pub struct DlmmZapInBalanced<'info> {
+    #[account(mut)]
+    /// CHECK: The user's position account. Must cover the bins within `range`
+    /// of the active bin.
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool account. Must match the lb_pair stored inside position,
+    /// bin_array_bitmap_extension, bin_array_lower, and bin_array_upper.
+    pub lb_pair: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Bin array bitmap extension account of the pool. Only required
+    /// when the active bin falls outside the main bitmap range (|bin_id| > 512).
+    /// Pass None if not needed.
+    pub bin_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    /// CHECK: User token account for token X. Source of the swap and of the
+    /// token X deposit.
+    pub user_token_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: User token account for token Y. Receives the swap output, which
+    /// is then deposited.
+    pub user_token_y: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token X. Derived from lb_pair.reserve_x.
+    pub reserve_x: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The pool's reserve vault for token Y. Derived from lb_pair.reserve_y.
+    pub reserve_y: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token X. Must match lb_pair.token_x_mint.
+    pub token_x_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Mint of token Y. Must match lb_pair.token_y_mint.
+    pub token_y_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The lower bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(lower_bin_id / 70)]
+    pub bin_array_lower: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: The upper bin array account covering the position's bin range.
+    /// PDA: ["bin_array", lb_pair, floor(upper_bin_id / 70)]
+    /// May be the same account as bin_array_lower if the position fits in one array.
+    pub bin_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Oracle account of the pool. Used by the swap.
+    pub oracle: UncheckedAccount<'info>,
+
+    /// CHECK: The authority that owns the position. Must sign the transaction.
+    pub sender: Signer<'info>,
+
+    #[account(address = dlmm::ID)]
+    /// CHECK: DLMM program
+    pub dlmm_program: UncheckedAccount<'info>,
+
+    /// CHECK: DLMM program event authority for event CPI.
+    /// PDA derived as: find_program_address(&[b"__event_authority"], &dlmm::ID)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token X mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_x_program: UncheckedAccount<'info>,
+
+    /// CHECK: Token program of token Y mint.
+    /// Use Token (spl-token) or Token-2022 depending on the pool's token program.
+    pub token_y_program: UncheckedAccount<'info>,
+    // Bin arrays for the swap need to be passed using remaining accounts,
+    // starting with the active bin array
+}
+
+/// Returns how much of `amount_in_x` to swap to token Y so that the rest and
+/// the swap output split in the same value ratio as the active bin's
+/// `bin_amount_x` and `bin_amount_y` at `price`. An empty active bin is
+/// treated as an even split.
+pub fn balanced_swap_amount(
+    amount_in_x: u64,
+    bin_amount_x: u64,
+    bin_amount_y: u64,
+    price: u128,
+) -> Option<u64> {
+    let value_x = mul_q64(u128::from(bin_amount_x), price)?;
+    let value_y = u128::from(bin_amount_y);
+    let total_value = value_x.checked_add(value_y)?;
+
+    if total_value == 0 {
+        return Some(amount_in_x / 2);
+    }
+
+    u64::try_from(u128::from(amount_in_x).checked_mul(value_y)? / total_value).ok()
+}
+
+/// Zaps token X into a Meteora DLMM position on both sides of the active bin.
+///
+/// Part of `amount_in_x` is swapped to token Y, then the remaining token X
+/// and the swap output are deposited with a Spot strategy over the bins
+/// within `range` of the active bin. The swap size follows the value ratio
+/// of token X and Y in the active bin, valued at its price, so the deposit
+/// lands in the pool's current proportions. This is the two-sided
+/// counterpart of `dlmm_add_liquidity_one_side`.
+///
+/// Accounts are the `dlmm_add_liquidity` accounts plus the pool `oracle`.
+/// The bin arrays the swap traverses must be passed as remaining accounts,
+/// starting from the active bin array, exactly like `dlmm_swap`. The first
+/// one is also read for the active bin's composition. Selling token X moves
+/// the active bin down.
+///
+/// Only the token Y received from the swap is deposited; any token Y the
+/// user already held is left untouched.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing all required accounts.
+/// * `amount_in_x` - Total amount of token X to zap in, in base units.
+/// * `range` - Number of bins on each side of the active bin to deposit
+///   into. 0 deposits into the active bin only.
+/// * `min_swap_out` - Minimum amount of token Y the swap must return.
+///
+/// # Returns
+///
+/// Returns a `Result` indicating success or failure. Fails with
+/// `InvalidAmount` if `amount_in_x` is 0, `InvalidBinRange` if `range` is
+/// negative, `NoBinArrays` if no remaining accounts are passed,
+/// `InvalidBinArrayPda` if the first one is not the active bin array, or
+/// `SlippageExceeded` if the swap returns less than `min_swap_out`.
+pub fn handle_dlmm_zap_in_balanced<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DlmmZapInBalanced<'info>>,
+    amount_in_x: u64,
+    range: i32,
+    min_swap_out: u64,
+) -> Result<()> {
+    require!(amount_in_x > 0, DlmmCpiError::InvalidAmount);
+
+    verify_event_authority(ctx.accounts.event_authority.key())?;
+
+    require!(range >= 0, DlmmCpiError::InvalidBinRange);
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        DlmmCpiError::NoBinArrays
+    );
+
+    verify_dlmm_owned(&ctx.accounts.position)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_lower)?;
+    verify_dlmm_owned(&ctx.accounts.bin_array_upper)?;
+
+    // 1. Size the swap from the active bin's composition
+    let (active_id, swap_amount) = {
+        let lb_pair = load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?;
+        let active_bin_array_index = bin_array_index(lb_pair.active_id);
+
+        let active_bin_array = &ctx.remaining_accounts[0];
+        verify_bin_array(
+            ctx.accounts.lb_pair.key(),
+            active_bin_array.key(),
+            active_bin_array_index,
+        )?;
+        let bin_array = load_dlmm_account::<dlmm::accounts::BinArray>(active_bin_array)?;
+
+        let offset = i64::from(lb_pair.active_id)
+            - active_bin_array_index * dlmm::constants::MAX_BIN_PER_ARRAY as i64;
+        let active_bin = &bin_array.bins[offset as usize];
+        let price = price_from_bin_id(lb_pair.active_id, lb_pair.bin_step)
+            .ok_or(DlmmCpiError::MathOverflow)?;
+
+        let swap_amount =
+            balanced_swap_amount(amount_in_x, active_bin.amount_x, active_bin.amount_y, price)
+                .ok_or(DlmmCpiError::MathOverflow)?;
+
+        (lb_pair.active_id, swap_amount)
+    };
+
+    let min_bin_id = active_id
+        .checked_sub(range)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+    let max_bin_id = active_id
+        .checked_add(range)
+        .ok_or(DlmmCpiError::MathOverflow)?;
+
+    // 2. Swap part of token X to token Y
+    let y_before = accessor::amount(&ctx.accounts.user_token_y)?;
+
+    if swap_amount > 0 {
+        require_compute_units(SWAP_COMPUTE_UNITS)?;
+
+        let accounts = dlmm::cpi::accounts::Swap {
+            lb_pair: ctx.accounts.lb_pair.to_account_info(),
+            bin_array_bitmap_extension: ctx
+                .accounts
+                .bin_array_bitmap_extension
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            reserve_x: ctx.accounts.reserve_x.to_account_info(),
+            reserve_y: ctx.accounts.reserve_y.to_account_info(),
+            user_token_in: ctx.accounts.user_token_x.to_account_info(),
+            user_token_out: ctx.accounts.user_token_y.to_account_info(),
+            token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+            token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+            oracle: ctx.accounts.oracle.to_account_info(),
+            host_fee_in: None,
+            user: ctx.accounts.sender.to_account_info(),
+            token_x_program: ctx.accounts.token_x_program.to_account_info(),
+            token_y_program: ctx.accounts.token_y_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.dlmm_program.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts)
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        dlmm::cpi::swap(cpi_context, swap_amount, min_swap_out)?;
+    }
+
+    // The swap output as received, net of any transfer fee
+    let amount_y = accessor::amount(&ctx.accounts.user_token_y)?.saturating_sub(y_before);
+    require!(amount_y >= min_swap_out, DlmmCpiError::SlippageExceeded);
+
+    // 3. Deposit both sides around the active bin, which the swap may have moved
+    let active_id_after_swap =
+        load_dlmm_account::<dlmm::accounts::LbPair>(&ctx.accounts.lb_pair)?.active_id;
+
+    let liquidity_parameter = dlmm::types::LiquidityParameterByStrategy {
+        amount_x: amount_in_x - swap_amount,
+        amount_y,
+        active_id: active_id_after_swap,
+        max_active_bin_slippage: 0,
+        strategy_parameters: dlmm::types::StrategyParameters {
+            min_bin_id,
+            max_bin_id,
+            strategy_type: dlmm::types::StrategyType::SpotImBalanced,
+            parameteres: [0; 64],
+        },
+    };
+
+    require_compute_units(ADD_LIQUIDITY_COMPUTE_UNITS)?;
+
+    let accounts = dlmm::cpi::accounts::AddLiquidityByStrategy {
+        position: ctx.accounts.position.to_account_info(),
+        lb_pair: ctx.accounts.lb_pair.to_account_info(),
+        bin_array_bitmap_extension: ctx
+            .accounts
+            .bin_array_bitmap_extension
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        user_token_x: ctx.accounts.user_token_x.to_account_info(),
+        user_token_y: ctx.accounts.user_token_y.to_account_info(),
+        reserve_x: ctx.accounts.reserve_x.to_account_info(),
+        reserve_y: ctx.accounts.reserve_y.to_account_info(),
+        token_x_mint: ctx.accounts.token_x_mint.to_account_info(),
+        token_y_mint: ctx.accounts.token_y_mint.to_account_info(),
+        bin_array_lower: ctx.accounts.bin_array_lower.to_account_info(),
+        bin_array_upper: ctx.accounts.bin_array_upper.to_account_info(),
+        sender: ctx.accounts.sender.to_account_info(),
+        token_x_program: ctx.accounts.token_x_program.to_account_info(),
+        token_y_program: ctx.accounts.token_y_program.to_account_info(),
+        event_authority: ctx.accounts.event_authority.to_account_info(),
+        program: ctx.accounts.dlmm_program.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new(ctx.accounts.dlmm_program.to_account_info(), accounts);
+
+    dlmm::cpi::add_liquidity_by_strategy(cpi_context, liquidity_parameter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ONE_Q64;
+
+    #[test]
+    fn test_balanced_swap_amount_follows_active_bin() {
+        // Three quarters of the bin's value is token Y
+        assert_eq!(balanced_swap_amount(1_000, 250, 750, ONE_Q64), Some(750));
+        // At a price of 2, 250 token X is worth as much as 500 token Y
+        assert_eq!(
+            balanced_swap_amount(1_000, 250, 500, 2 * ONE_Q64),
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn test_balanced_swap_amount_edge_bins() {
+        assert_eq!(balanced_swap_amount(1_000, 100, 0, ONE_Q64), Some(0));
+        assert_eq!(balanced_swap_amount(1_000, 0, 100, ONE_Q64), Some(1_000));
+        assert_eq!(balanced_swap_amount(1_001, 0, 0, ONE_Q64), Some(500));
+    }
+}
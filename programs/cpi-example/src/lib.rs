@@ -2,7 +2,12 @@
 #![allow(unexpected_cfgs)]
 use anchor_lang::prelude::*;
 
+pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod state;
+pub mod utils;
+pub use errors::DlmmCpiError;
 pub use instructions::*;
 
 declare_program!(dlmm);
@@ -16,6 +21,61 @@ use crate::dlmm_add_liquidty_one_side::*;
 use crate::dlmm_close_position::*;
 use crate::dlmm_remove_liquidity::*;
 use crate::dlmm_remove_all_liquidity::*;
+use crate::dlmm_initialize_position_pda_idempotent::*;
+use crate::dlmm_open_cost::*;
+use crate::dlmm_swap_feed_oracle::*;
+use crate::dlmm_claim_reward::*;
+use crate::dlmm_remove_and_balance::*;
+use crate::dlmm_swap2::*;
+use crate::dlmm_read_authorities::*;
+use crate::dlmm_swap_checked::*;
+use crate::dlmm_add_liquidity::*;
+use crate::dlmm_position_value_in_quote::*;
+use crate::dlmm_initialize_position::*;
+use crate::dlmm_initialize_position_pda::*;
+use crate::dlmm_claim_fee::*;
+use crate::dlmm_validate_distribution::*;
+use crate::dlmm_shift_liquidity::*;
+use crate::dlmm_read_oracle::*;
+use crate::dlmm_exit_position::*;
+use crate::dlmm_activation_info::*;
+use crate::dlmm_batch_read_positions::*;
+use crate::dlmm_initialize_bin_array::*;
+use crate::dlmm_add_liquidity_smart::*;
+use crate::dlmm_initialize_bin_array_bitmap_extension::*;
+use crate::dlmm_read_fee_growth::*;
+use crate::dlmm_swap_flexible::*;
+use crate::dlmm_estimate_apr::*;
+use crate::dlmm_merge_positions::*;
+use crate::dlmm_update_position_operator::*;
+use crate::dlmm_read_lb_pair::*;
+use crate::dlmm_pool_has_mint::*;
+use crate::dlmm_swap_track_volume::*;
+use crate::dlmm_open_wide::*;
+use crate::dlmm_open_and_add::*;
+use crate::dlmm_last_activity::*;
+use crate::dlmm_claim_all_rewards::*;
+use crate::dlmm_add_price_impact::*;
+use crate::dlmm_initialize_lb_pair::*;
+use crate::dlmm_remove_liquidity_signed::*;
+use crate::dlmm_add_liquidity_one_side_signed::*;
+use crate::dlmm_close_position_signed::*;
+use crate::dlmm_exit_preview::*;
+use crate::dlmm_swap_split_output::*;
+use crate::dlmm_batch_remove_liquidity::*;
+use crate::dlmm_suggest_one_side_dist::*;
+use crate::dlmm_read_position_fees::*;
+use crate::dlmm_token_programs::*;
+use crate::dlmm_position_price_ladder::*;
+use crate::dlmm_quote_swap::*;
+use crate::dlmm_zap_in_balanced::*;
+use crate::dlmm_max_input_for_bins::*;
+use crate::dlmm_pool_depth::*;
+use crate::dlmm_withdraw_protocol_fee::*;
+use crate::dlmm_bootstrap_position::*;
+use crate::dlmm_read_protocol_fees::*;
+use crate::dlmm_add_liquidity_by_strategy_auto::*;
+use crate::dlmm_batch_close::*;
 
 fn assert_eq_admin(_key: Pubkey) -> bool {
     true
@@ -34,6 +94,8 @@ pub mod cpi_example {
         active_id: i32,
         max_active_bin_slippage: i32,
         bin_liquidity_dist: Vec<dlmm::types::BinLiquidityDistributionByWeight>,
+        min_net_amount: Option<u64>,
+        max_weight_per_bin: Option<u16>,
     ) -> Result<()> {
         instructions::dlmm_cpi::dlmm_add_liquidty_one_side::handle_dlmm_add_liquidity_one_side(
             ctx,
@@ -41,14 +103,28 @@ pub mod cpi_example {
             active_id,
             max_active_bin_slippage,
             bin_liquidity_dist,
+            min_net_amount,
+            max_weight_per_bin,
         )
     }
     pub fn dlmm_swap<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
         amount_in: u64,
         min_amount_out: u64,
+        reject_zero_min_out: bool,
+        preserve_inner_return_data: bool,
+        expected_active_id: Option<i32>,
+        max_active_bin_slippage: Option<i32>,
     ) -> Result<()> {
-        instructions::dlmm_cpi::dlmm_swap::handle_dlmm_swap(ctx, amount_in, min_amount_out)
+        instructions::dlmm_cpi::dlmm_swap::handle_dlmm_swap(
+            ctx,
+            amount_in,
+            min_amount_out,
+            reject_zero_min_out,
+            preserve_inner_return_data,
+            expected_active_id,
+            max_active_bin_slippage,
+        )
     }
 
     pub fn dlmm_close_position(
@@ -63,10 +139,14 @@ pub mod cpi_example {
     pub fn dlmm_remove_liquidity(
         ctx: Context<DlmmRemoveLiquidity>,
         bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+        min_amount_x: u64,
+        min_amount_y: u64,
     ) -> Result<()> {
         instructions::dlmm_cpi::dlmm_remove_liquidity::handle_dlmm_remove_liquidity(
             ctx,
             bin_liquidity_removal,
+            min_amount_x,
+            min_amount_y,
         )
     }
 
@@ -75,6 +155,644 @@ pub mod cpi_example {
     ) -> Result<()> {
         instructions::dlmm_cpi::dlmm_remove_all_liquidity::handle_dlmm_remove_all_liquidity(ctx)
     }
+
+    /// Initializes a position at a PDA. Succeeds without re-initializing
+    /// if the position already exists with the same bin range.
+    pub fn dlmm_initialize_position_pda_idempotent(
+        ctx: Context<DlmmInitializePositionPdaIdempotent>,
+        lower_bin_id: i32,
+        width: i32,
+        base: Pubkey,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_position_pda_idempotent::handle_dlmm_initialize_position_pda_idempotent(
+            ctx,
+            lower_bin_id,
+            width,
+            base,
+        )
+    }
+
+    /// Returns, via return data, the lamports needed to open a position over
+    /// the given range, including any bin arrays that don't exist yet.
+    pub fn dlmm_open_cost(
+        ctx: Context<DlmmOpenCost>,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_open_cost::handle_dlmm_open_cost(ctx, lower_bin_id, width)
+    }
+
+    pub fn dlmm_initialize_price_feed(ctx: Context<DlmmInitializePriceFeed>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_feed_oracle::handle_dlmm_initialize_price_feed(ctx)
+    }
+
+    /// Swaps and writes the realized execution price (Q64.64, token Y per token X) and slot
+    /// into `price_feed`. Only the feed's authority can swap its pool into it.
+    pub fn dlmm_swap_feed_oracle<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwapFeedOracle<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_feed_oracle::handle_dlmm_swap_feed_oracle(
+            ctx,
+            amount_in,
+            min_amount_out,
+        )
+    }
+
+    /// Claims the reward at `reward_index` after checking the reward mint and
+    /// vault against the pool's reward info.
+    pub fn dlmm_claim_reward(ctx: Context<DlmmClaimReward>, reward_index: u64) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_claim_reward::handle_dlmm_claim_reward(ctx, reward_index)
+    }
+
+    /// Removes liquidity from specific bins, then swaps the excess side of the
+    /// withdrawn amounts so token X makes up `target_ratio_bps` of their value.
+    pub fn dlmm_remove_and_balance<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmRemoveAndBalance<'info>>,
+        bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+        target_ratio_bps: u16,
+        max_swap_slippage_bps: u16,
+        expected_active_id: i32,
+        max_active_bin_slippage: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_remove_and_balance::handle_dlmm_remove_and_balance(
+            ctx,
+            bin_liquidity_removal,
+            target_ratio_bps,
+            max_swap_slippage_bps,
+            expected_active_id,
+            max_active_bin_slippage,
+        )
+    }
+
+    /// Swap with a slippage check that accounts for the output mint's
+    /// Token-2022 transfer fee.
+    pub fn dlmm_swap2<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwap2<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        min_amount_out_basis: MinAmountOutBasis,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap2::handle_dlmm_swap2(
+            ctx,
+            amount_in,
+            min_amount_out,
+            min_amount_out_basis,
+        )
+    }
+
+    /// Returns the pool creator, fee owner and reward funders via return data.
+    pub fn dlmm_read_authorities(ctx: Context<DlmmReadAuthorities>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_authorities::handle_dlmm_read_authorities(ctx)
+    }
+
+    /// Swaps through DLMM and checks the reserves moved consistently with the swap.
+    pub fn dlmm_swap_checked<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwapChecked<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_checked::handle_dlmm_swap_checked(
+            ctx,
+            amount_in,
+            min_amount_out,
+        )
+    }
+
+    /// Adds token X and token Y liquidity to a DLMM position.
+    pub fn dlmm_add_liquidity(
+        ctx: Context<DlmmAddLiquidity>,
+        liquidity_parameter: dlmm::types::LiquidityParameter,
+        active_id: i32,
+        max_active_bin_slippage: i32,
+        min_deposit_x: Option<u64>,
+        min_deposit_y: Option<u64>,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_liquidity::handle_dlmm_add_liquidity(
+            ctx,
+            liquidity_parameter,
+            active_id,
+            max_active_bin_slippage,
+            min_deposit_x,
+            min_deposit_y,
+        )
+    }
+
+    /// Adds liquidity to a DLMM position using a Spot, Curve or BidAsk strategy.
+    pub fn dlmm_add_liquidity_by_strategy(
+        ctx: Context<DlmmAddLiquidity>,
+        liquidity_parameter: dlmm::types::LiquidityParameterByStrategy,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_liquidity_by_strategy::handle_dlmm_add_liquidity_by_strategy(
+            ctx,
+            liquidity_parameter,
+        )
+    }
+
+    /// Returns the value of a DLMM position in token X or Y via return data.
+    pub fn dlmm_position_value_in_quote(
+        ctx: Context<DlmmPositionValueInQuote>,
+        quote_is_y: bool,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_position_value_in_quote::handle_dlmm_position_value_in_quote(
+            ctx, quote_is_y,
+        )
+    }
+
+    /// Creates a DLMM position at a fresh keypair account.
+    pub fn dlmm_initialize_position(
+        ctx: Context<DlmmInitializePosition>,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_position::handle_dlmm_initialize_position(
+            ctx,
+            lower_bin_id,
+            width,
+        )
+    }
+
+    /// Creates a DLMM position at a PDA, owned by a PDA of this program.
+    pub fn dlmm_initialize_position_pda(
+        ctx: Context<DlmmInitializePositionPda>,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_position_pda::handle_dlmm_initialize_position_pda(
+            ctx,
+            lower_bin_id,
+            width,
+        )
+    }
+
+    /// Claims the swap fees accrued by a DLMM position.
+    pub fn dlmm_claim_fee(ctx: Context<DlmmClaimFee>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_claim_fee::handle_dlmm_claim_fee(ctx)
+    }
+
+    /// Returns whether a one-sided distribution can be deposited into a position.
+    pub fn dlmm_validate_distribution(
+        ctx: Context<DlmmValidateDistribution>,
+        active_id: i32,
+        bin_liquidity_dist: Vec<dlmm::types::BinLiquidityDistributionByWeight>,
+        deposit_is_x: bool,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_validate_distribution::handle_dlmm_validate_distribution(
+            ctx,
+            active_id,
+            bin_liquidity_dist,
+            deposit_is_x,
+        )
+    }
+
+    /// Moves liquidity between bins of the same DLMM position.
+    pub fn dlmm_shift_liquidity(
+        ctx: Context<DlmmShiftLiquidity>,
+        from_removals: Vec<dlmm::types::BinLiquidityReduction>,
+        to_add: dlmm::types::LiquidityParameterByStrategy,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_shift_liquidity::handle_dlmm_shift_liquidity(
+            ctx,
+            from_removals,
+            to_add,
+        )
+    }
+
+    /// Swaps through DLMM for an exact output amount.
+    pub fn dlmm_swap_exact_out<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+        max_in_amount: u64,
+        out_amount: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_exact_out::handle_dlmm_swap_exact_out(
+            ctx,
+            max_in_amount,
+            out_amount,
+        )
+    }
+
+    /// Returns the latest DLMM oracle observation, failing if it is stale.
+    pub fn dlmm_read_oracle(
+        ctx: Context<DlmmReadOracle>,
+        max_staleness_seconds: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_oracle::handle_dlmm_read_oracle(
+            ctx,
+            max_staleness_seconds,
+        )
+    }
+
+    /// Swaps through DLMM bounded by a maximum price impact.
+    pub fn dlmm_swap_with_price_impact<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+        amount_in: u64,
+        active_id: Option<i32>,
+        max_price_impact_bps: u16,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_with_price_impact::handle_dlmm_swap_with_price_impact(
+            ctx,
+            amount_in,
+            active_id,
+            max_price_impact_bps,
+        )
+    }
+
+    /// Creates a DLMM position centered on the pool's active bin.
+    pub fn dlmm_open_centered(ctx: Context<DlmmInitializePosition>, half_width: i32) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_open_centered::handle_dlmm_open_centered(ctx, half_width)
+    }
+
+    /// Removes all liquidity, claims fees and closes a DLMM position in one instruction.
+    pub fn dlmm_exit_position(ctx: Context<DlmmExitPosition>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_exit_position::handle_dlmm_exit_position(ctx)
+    }
+
+    /// Returns when a DLMM pool activates via return data.
+    pub fn dlmm_activation_info(ctx: Context<DlmmActivationInfo>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_activation_info::handle_dlmm_activation_info(ctx)
+    }
+
+    /// Returns the owner, range and total shares of several DLMM positions via return data.
+    pub fn dlmm_batch_read_positions(
+        ctx: Context<DlmmBatchReadPositions>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_batch_read_positions::handle_dlmm_batch_read_positions(
+            ctx, count,
+        )
+    }
+
+    /// Creates a DLMM bin array.
+    pub fn dlmm_initialize_bin_array(
+        ctx: Context<DlmmInitializeBinArray>,
+        index: i64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_bin_array::handle_dlmm_initialize_bin_array(
+            ctx, index,
+        )
+    }
+
+    /// Adds DLMM liquidity by strategy, creating missing bin arrays and the bitmap extension first.
+    pub fn dlmm_add_liquidity_smart(
+        ctx: Context<DlmmAddLiquiditySmart>,
+        params: dlmm::types::LiquidityParameterByStrategy,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_liquidity_smart::handle_dlmm_add_liquidity_smart(
+            ctx, params,
+        )
+    }
+
+    /// Creates the bin array bitmap extension of a DLMM pool.
+    pub fn dlmm_initialize_bin_array_bitmap_extension(
+        ctx: Context<DlmmInitializeBinArrayBitmapExtension>,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_bin_array_bitmap_extension::handle_dlmm_initialize_bin_array_bitmap_extension(ctx)
+    }
+
+    /// Returns the fees a DLMM position earned per bin since it was last updated.
+    pub fn dlmm_read_fee_growth(ctx: Context<DlmmReadFeeGrowth>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_fee_growth::handle_dlmm_read_fee_growth(ctx)
+    }
+
+    /// Swaps through DLMM for an exact input or an exact output amount.
+    pub fn dlmm_swap_flexible<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwap<'info>>,
+        mode: SwapMode,
+        amount: u64,
+        limit: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_flexible::handle_dlmm_swap_flexible(
+            ctx, mode, amount, limit,
+        )
+    }
+
+    /// Returns the APR of a DLMM position estimated from fees earned since its last update.
+    pub fn dlmm_estimate_apr(ctx: Context<DlmmEstimateApr>, lookback_seconds: u64) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_estimate_apr::handle_dlmm_estimate_apr(ctx, lookback_seconds)
+    }
+
+    /// Merges a DLMM position into another one covering its bins, closing the source.
+    pub fn dlmm_merge_positions(ctx: Context<DlmmMergePositions>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_merge_positions::handle_dlmm_merge_positions(ctx)
+    }
+    /// Sets the operator allowed to manage a DLMM position on the owner's behalf.
+    pub fn dlmm_update_position_operator(
+        ctx: Context<DlmmUpdatePositionOperator>,
+        operator: Pubkey,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_update_position_operator::handle_dlmm_update_position_operator(
+            ctx, operator,
+        )
+    }
+    /// Returns the active bin, bin step, mints, reserves and status of a DLMM pool.
+    pub fn dlmm_read_lb_pair(ctx: Context<DlmmReadLbPair>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_lb_pair::handle_dlmm_read_lb_pair(ctx)
+    }
+    /// Returns whether a DLMM pool trades the given mint as token X or Y.
+    pub fn dlmm_pool_has_mint(ctx: Context<DlmmPoolHasMint>, mint: Pubkey) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_pool_has_mint::handle_dlmm_pool_has_mint(ctx, mint)
+    }
+    /// Removes the same share of liquidity from every bin in a range of a DLMM position.
+    pub fn dlmm_remove_liquidity_by_range(
+        ctx: Context<DlmmRemoveLiquidity>,
+        from_bin_id: i32,
+        to_bin_id: i32,
+        bps_to_remove: u16,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_remove_liquidity_by_range::handle_dlmm_remove_liquidity_by_range(
+            ctx,
+            from_bin_id,
+            to_bin_id,
+            bps_to_remove,
+        )
+    }
+    pub fn dlmm_initialize_volume_stats(ctx: Context<DlmmInitializeVolumeStats>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_track_volume::handle_dlmm_initialize_volume_stats(ctx)
+    }
+
+    /// Swaps and adds the amounts in and out to the volume recorded in `stats`. Only the
+    /// stats authority can record volume.
+    pub fn dlmm_swap_track_volume<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwapTrackVolume<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_track_volume::handle_dlmm_swap_track_volume(
+            ctx,
+            amount_in,
+            min_amount_out,
+        )
+    }
+
+    /// Creates a DLMM position together with any bin array its range is missing.
+    pub fn dlmm_open_wide<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmOpenWide<'info>>,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_open_wide::handle_dlmm_open_wide(ctx, lower_bin_id, width)
+    }
+
+    /// Creates a DLMM position and adds liquidity by strategy to it in one instruction.
+    pub fn dlmm_open_and_add(
+        ctx: Context<DlmmOpenAndAdd>,
+        lower_bin_id: i32,
+        width: i32,
+        liquidity_parameter: dlmm::types::LiquidityParameterByStrategy,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_open_and_add::handle_dlmm_open_and_add(
+            ctx,
+            lower_bin_id,
+            width,
+            liquidity_parameter,
+        )
+    }
+
+    /// Returns the seconds since a DLMM pool was last swapped against via return data.
+    pub fn dlmm_last_activity(ctx: Context<DlmmLastActivity>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_last_activity::handle_dlmm_last_activity(ctx)
+    }
+
+    /// Claims both reward slots of a DLMM position in one instruction.
+    pub fn dlmm_claim_all_rewards(ctx: Context<DlmmClaimAllRewards>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_claim_all_rewards::handle_dlmm_claim_all_rewards(ctx)
+    }
+
+    /// Returns how much a prospective DLMM strategy deposit shifts the active bin via return data.
+    pub fn dlmm_add_price_impact(
+        ctx: Context<DlmmAddPriceImpact>,
+        params: dlmm::types::LiquidityParameterByStrategy,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_price_impact::handle_dlmm_add_price_impact(ctx, params)
+    }
+
+    /// Creates a customizable permissionless DLMM pool.
+    pub fn dlmm_initialize_lb_pair(
+        ctx: Context<DlmmInitializeLbPair>,
+        params: dlmm::types::CustomizableParams,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_initialize_lb_pair::handle_dlmm_initialize_lb_pair(ctx, params)
+    }
+
+    /// Removes liquidity from a position owned by this program's position_owner PDA.
+    pub fn dlmm_remove_liquidity_signed(
+        ctx: Context<DlmmRemoveLiquiditySigned>,
+        position_owner_bump: u8,
+        bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_remove_liquidity_signed::handle_dlmm_remove_liquidity_signed(
+            ctx,
+            position_owner_bump,
+            bin_liquidity_removal,
+        )
+    }
+
+    /// Adds one-sided liquidity to a position owned by this program's position_owner PDA.
+    pub fn dlmm_add_liquidity_one_side_signed(
+        ctx: Context<DlmmAddLiquidityOneSideSigned>,
+        position_owner_bump: u8,
+        liquidity_parameter: dlmm::types::LiquidityOneSideParameter,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_liquidity_one_side_signed::handle_dlmm_add_liquidity_one_side_signed(
+            ctx,
+            position_owner_bump,
+            liquidity_parameter,
+        )
+    }
+
+    /// Closes a position owned by this program's position_owner PDA.
+    pub fn dlmm_close_position_signed(
+        ctx: Context<DlmmClosePositionSigned>,
+        position_owner_bump: u8,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_close_position_signed::handle_dlmm_close_position_signed(
+            ctx,
+            position_owner_bump,
+        )
+    }
+
+    /// Previews the tokens and fees a full exit of a DLMM position would pay out.
+    pub fn dlmm_exit_preview(ctx: Context<DlmmExitPreview>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_exit_preview::handle_dlmm_exit_preview(ctx)
+    }
+
+    /// Swaps on DLMM and splits the output between two recipients by basis points.
+    pub fn dlmm_swap_split_output<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmSwapSplitOutput<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        split_bps: u16,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_swap_split_output::handle_dlmm_swap_split_output(
+            ctx,
+            amount_in,
+            min_amount_out,
+            split_bps,
+        )
+    }
+
+    /// Removes all liquidity from up to 4 DLMM positions of one pool in a single call.
+    pub fn dlmm_batch_remove_liquidity<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmBatchRemoveLiquidity<'info>>,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_batch_remove_liquidity::handle_dlmm_batch_remove_liquidity(
+            ctx,
+        )
+    }
+
+    /// Suggests a uniform, linear or concentrated single-sided distribution for a bin range.
+    pub fn dlmm_suggest_one_side_dist(
+        ctx: Context<DlmmSuggestOneSideDist>,
+        from_bin_id: i32,
+        to_bin_id: i32,
+        shape: u8,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_suggest_one_side_dist::handle_dlmm_suggest_one_side_dist(
+            ctx,
+            from_bin_id,
+            to_bin_id,
+            shape,
+        )
+    }
+
+    /// Removes liquidity after checking the position's pool and owner against pinned values.
+    pub fn dlmm_remove_liquidity_guarded(
+        ctx: Context<DlmmRemoveLiquidity>,
+        expected_lb_pair: Pubkey,
+        expected_owner: Pubkey,
+        bin_liquidity_removal: Vec<dlmm::types::BinLiquidityReduction>,
+        min_amount_x: u64,
+        min_amount_y: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_remove_liquidity_guarded::handle_dlmm_remove_liquidity_guarded(
+            ctx,
+            expected_lb_pair,
+            expected_owner,
+            bin_liquidity_removal,
+            min_amount_x,
+            min_amount_y,
+        )
+    }
+
+    /// Returns the fees and rewards settled into a V1 or V2 DLMM position.
+    pub fn dlmm_read_position_fees(ctx: Context<DlmmReadPositionFees>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_position_fees::handle_dlmm_read_position_fees(ctx)
+    }
+
+    /// Returns the token program of each of a DLMM pool's mints.
+    pub fn dlmm_token_programs(ctx: Context<DlmmTokenPrograms>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_token_programs::handle_dlmm_token_programs(ctx)
+    }
+
+    /// Returns the bin IDs and prices across a DLMM position's range.
+    pub fn dlmm_position_price_ladder(
+        ctx: Context<DlmmPositionPriceLadder>,
+        start_offset: u8,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_position_price_ladder::handle_dlmm_position_price_ladder(
+            ctx,
+            start_offset,
+        )
+    }
+
+    /// Quotes a DLMM exact input swap without executing it.
+    pub fn dlmm_quote_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmQuoteSwap<'info>>,
+        amount_in: u64,
+        swap_for_y: bool,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_quote_swap::handle_dlmm_quote_swap(ctx, amount_in, swap_for_y)
+    }
+
+    /// Swaps part of token X to token Y and deposits both around the active bin.
+    pub fn dlmm_zap_in_balanced<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmZapInBalanced<'info>>,
+        amount_in_x: u64,
+        range: i32,
+        min_swap_out: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_zap_in_balanced::handle_dlmm_zap_in_balanced(
+            ctx,
+            amount_in_x,
+            range,
+            min_swap_out,
+        )
+    }
+
+    /// Returns the largest DLMM swap input that moves the active bin at most `max_bins`.
+    pub fn dlmm_max_input_for_bins<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmMaxInputForBins<'info>>,
+        max_bins: u16,
+        swap_for_y: bool,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_max_input_for_bins::handle_dlmm_max_input_for_bins(
+            ctx,
+            max_bins,
+            swap_for_y,
+        )
+    }
+
+    /// Reads the reserves of each bin within `radius` of a DLMM pool's active bin.
+    pub fn dlmm_pool_depth<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmPoolDepth<'info>>,
+        radius: u16,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_pool_depth::handle_dlmm_pool_depth(ctx, radius)
+    }
+
+    /// Withdraws a DLMM pool's protocol fee. Only the protocol fee owner can call this.
+    pub fn dlmm_withdraw_protocol_fee(
+        ctx: Context<DlmmWithdrawProtocolFee>,
+        max_amount_x: u64,
+        max_amount_y: u64,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_withdraw_protocol_fee::handle_dlmm_withdraw_protocol_fee(
+            ctx,
+            max_amount_x,
+            max_amount_y,
+        )
+    }
+
+    /// Creates the accounts a first DLMM deposit needs, skipping those that exist.
+    pub fn dlmm_bootstrap_position<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmBootstrapPosition<'info>>,
+        lower_bin_id: i32,
+        width: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_bootstrap_position::handle_dlmm_bootstrap_position(
+            ctx,
+            lower_bin_id,
+            width,
+        )
+    }
+
+    /// Reads the protocol fees a DLMM pool holds for withdrawal.
+    pub fn dlmm_read_protocol_fees(ctx: Context<DlmmReadProtocolFees>) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_read_protocol_fees::handle_dlmm_read_protocol_fees(ctx)
+    }
+
+    /// Adds DLMM liquidity by strategy around the active bin read on-chain.
+    pub fn dlmm_add_liquidity_by_strategy_auto(
+        ctx: Context<DlmmAddLiquidity>,
+        liquidity_parameter: LiquidityParameterByStrategyAuto,
+        max_slippage: i32,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_add_liquidity_by_strategy_auto::handle_dlmm_add_liquidity_by_strategy_auto(
+            ctx,
+            liquidity_parameter,
+            max_slippage,
+        )
+    }
+
+    /// Closes up to 8 empty DLMM positions of a pool, skipping any with liquidity left.
+    pub fn dlmm_batch_close<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DlmmBatchClose<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::dlmm_cpi::dlmm_batch_close::handle_dlmm_batch_close(ctx, count)
+    }
+
     
     pub fn initialize_dynamic_amm_customizable_permissionless_pool(
         ctx: Context<DynamicAmmInitializeCustomizablePermissionlessPool>,
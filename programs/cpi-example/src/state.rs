@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+/// Price feed maintained by this program from its own DLMM swaps.
+///
+/// The feed is bound to one pool and one authority at creation, and is only
+/// written when `authority` signs a swap of that pool through
+/// `dlmm_swap_feed_oracle`. It can therefore be arbitrarily old. Consumers
+/// must compare `last_slot` against `Clock::get()?.slot` and reject prices
+/// older than their own staleness tolerance.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeed {
+    /// The only signer allowed to update the feed.
+    pub authority: Pubkey,
+    /// The pool whose swaps update the feed.
+    pub lb_pair: Pubkey,
+    /// Token X mint of the pool, the base of `last_price`.
+    pub token_x_mint: Pubkey,
+    /// Token Y mint of the pool, the quote of `last_price`.
+    pub token_y_mint: Pubkey,
+    /// Realized execution price of the last swap, token Y per token X in
+    /// base units, as a Q64.64 fixed point number, whichever the swap
+    /// direction.
+    pub last_price: u128,
+    /// Slot of the last swap that updated the feed.
+    pub last_slot: u64,
+}
+
+/// Swap volume routed through `dlmm_swap_track_volume` with this account.
+///
+/// Only swaps signed by `authority` are recorded. Amounts are in base units
+/// of whichever token was sold or bought, so the totals are only meaningful
+/// when the account is used for one direction of one pool, or for pools
+/// sharing the same input and output mints.
+#[account]
+#[derive(InitSpace)]
+pub struct VolumeStats {
+    /// The only signer allowed to add volume to the account.
+    pub authority: Pubkey,
+    /// Total input tokens taken from users.
+    pub total_in: u128,
+    /// Total output tokens received by users.
+    pub total_out: u128,
+    /// Number of swaps recorded.
+    pub swap_count: u64,
+}
@@ -0,0 +1,968 @@
+use crate::dlmm;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_lang::{Discriminator, ZeroCopy};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as Token2022Mint;
+use std::cell::Ref;
+
+/// Derives the DLMM position PDA.
+/// PDA: ["position", lb_pair, base, lower_bin_id, width]
+pub fn derive_position_pda(
+    lb_pair: Pubkey,
+    base: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"position".as_ref(),
+            lb_pair.as_ref(),
+            base.as_ref(),
+            lower_bin_id.to_le_bytes().as_ref(),
+            width.to_le_bytes().as_ref(),
+        ],
+        &dlmm::ID,
+    )
+}
+
+/// Builds a `CpiContext` signed by a PDA of this program, for CPIs where a
+/// program owned account (position owner, pool creator, ...) must sign.
+///
+/// `seeds` are the PDA seeds including the bump, e.g.
+/// `&[&[b"position_owner", &[bump]]]`. The account infos live for `'info`
+/// while the seeds usually borrow locals of the handler, so they keep
+/// separate lifetimes.
+pub fn pda_cpi_context<'a, 'b, 'c, 'info, T>(
+    program: AccountInfo<'info>,
+    accounts: T,
+    seeds: &'a [&'b [&'c [u8]]],
+) -> CpiContext<'a, 'b, 'c, 'info, T>
+where
+    T: ToAccountMetas + ToAccountInfos<'info>,
+{
+    CpiContext::new_with_signer(program, accounts, seeds)
+}
+
+/// Borrows a zero-copy DLMM account (LbPair, PositionV2, BinArray, ...) from an
+/// unchecked account without copying it onto the stack.
+///
+/// Validates the account owner and discriminator, the same way `AccountLoader`
+/// does, but works with any `AccountInfo` lifetime.
+pub fn load_dlmm_account<'a, T: ZeroCopy + Owner + Discriminator>(
+    account: &'a AccountInfo,
+) -> Result<Ref<'a, T>> {
+    if account.owner != &T::owner() {
+        return Err(Error::from(ErrorCode::AccountOwnedByWrongProgram)
+            .with_pubkeys((*account.owner, T::owner())));
+    }
+
+    let data = account.try_borrow_data()?;
+    let disc = T::DISCRIMINATOR;
+    if data.len() < disc.len() + std::mem::size_of::<T>() {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    if &data[..disc.len()] != disc {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    Ok(Ref::map(data, |data| {
+        bytemuck::from_bytes(&data[disc.len()..disc.len() + std::mem::size_of::<T>()])
+    }))
+}
+
+/// Derives the DLMM bin array PDA.
+/// PDA: ["bin_array", lb_pair, bin_array_index]
+///
+/// Bin arrays have a fixed size of `MAX_BIN_PER_ARRAY` bins and DLMM has no
+/// instruction to reallocate or extend them. When an instruction fails on a
+/// neighbouring bin array, the array doesn't exist yet and has to be created
+/// with `initialize_bin_array`.
+pub fn derive_bin_array_pda(lb_pair: Pubkey, bin_array_index: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"bin_array".as_ref(),
+            lb_pair.as_ref(),
+            bin_array_index.to_le_bytes().as_ref(),
+        ],
+        &dlmm::ID,
+    )
+}
+
+/// Checks that `bin_array` is the DLMM bin array PDA of `lb_pair` at
+/// `expected_index`, so a wrong account fails with a clear error instead of
+/// inside the DLMM program.
+pub fn verify_bin_array(lb_pair: Pubkey, bin_array: Pubkey, expected_index: i64) -> Result<()> {
+    let (expected_bin_array, _bump) = derive_bin_array_pda(lb_pair, expected_index);
+    require_keys_eq!(
+        bin_array,
+        expected_bin_array,
+        crate::errors::DlmmCpiError::InvalidBinArrayPda
+    );
+    Ok(())
+}
+
+/// Checks that `account` is owned by the DLMM program. Positions and bin
+/// arrays are passed unchecked, so this rejects a spoofed account before its
+/// data is trusted or forwarded to the CPI.
+pub fn verify_dlmm_owned(account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(
+        *account.owner,
+        dlmm::ID,
+        crate::errors::DlmmCpiError::AccountNotOwnedByDlmm
+    );
+    Ok(())
+}
+
+/// Derives the DLMM program event authority PDA.
+/// PDA: ["__event_authority"]
+pub fn derive_event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority".as_ref()], &dlmm::ID)
+}
+
+/// Checks that `event_authority` is the DLMM program's event authority. The
+/// DLMM program emits its events through a CPI to itself signed by this PDA,
+/// so a wrong account would otherwise fail inside that inner CPI.
+pub fn verify_event_authority(event_authority: Pubkey) -> Result<()> {
+    let (expected_event_authority, _bump) = derive_event_authority();
+    require_keys_eq!(
+        event_authority,
+        expected_event_authority,
+        crate::errors::DlmmCpiError::InvalidEventAuthority
+    );
+    Ok(())
+}
+
+/// Checks that `reserve_x` and `reserve_y` are the pool's reserves, each in
+/// its own slot. Passing them the wrong way round would send token X to the
+/// token Y account and vice versa, so it gets its own error.
+pub fn verify_reserves(
+    lb_pair: &dlmm::accounts::LbPair,
+    reserve_x: Pubkey,
+    reserve_y: Pubkey,
+) -> Result<()> {
+    if reserve_x == lb_pair.reserve_y && reserve_y == lb_pair.reserve_x {
+        return err!(crate::errors::DlmmCpiError::ReservesSwapped);
+    }
+
+    require_keys_eq!(
+        reserve_x,
+        lb_pair.reserve_x,
+        crate::errors::DlmmCpiError::ReserveMismatch
+    );
+    require_keys_eq!(
+        reserve_y,
+        lb_pair.reserve_y,
+        crate::errors::DlmmCpiError::ReserveMismatch
+    );
+    Ok(())
+}
+
+/// Returns true if `mint` is token X or token Y of the pool.
+pub fn pool_contains_mint(lb_pair: &dlmm::accounts::LbPair, mint: &Pubkey) -> bool {
+    lb_pair.token_x_mint == *mint || lb_pair.token_y_mint == *mint
+}
+
+/// Returns true if a swap from `mint_in` to `mint_out` sells token X of the
+/// pool for token Y, false if it sells token Y for token X.
+///
+/// Fails with `MintNotInPool` if `mint_in` is neither pool token, and
+/// `SwapDirectionMismatch` if `mint_out` is not the other one.
+pub fn swap_for_y(
+    lb_pair: &dlmm::accounts::LbPair,
+    mint_in: Pubkey,
+    mint_out: Pubkey,
+) -> Result<bool> {
+    let (swap_for_y, expected_mint_out) = if mint_in == lb_pair.token_x_mint {
+        (true, lb_pair.token_y_mint)
+    } else if mint_in == lb_pair.token_y_mint {
+        (false, lb_pair.token_x_mint)
+    } else {
+        return err!(crate::errors::DlmmCpiError::MintNotInPool);
+    };
+
+    require_keys_eq!(
+        mint_out,
+        expected_mint_out,
+        crate::errors::DlmmCpiError::SwapDirectionMismatch
+    );
+
+    Ok(swap_for_y)
+}
+
+/// Derives the DLMM bin array bitmap extension PDA.
+/// PDA: ["bitmap", lb_pair]
+pub fn derive_bin_array_bitmap_extension(lb_pair: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bitmap".as_ref(), lb_pair.as_ref()], &dlmm::ID)
+}
+
+/// Returns true if the DLMM program accepts `bin_step` and `base_factor` for
+/// a customizable pool: a bin step of 1 to `MAX_BIN_STEP` basis points and a
+/// base fee, `base_factor * bin_step * 10` in `FEE_PRECISION` units, from
+/// `MIN_BASE_FEE` to `MAX_BASE_FEE`.
+pub fn is_valid_bin_step(bin_step: u16, base_factor: u16) -> bool {
+    let base_fee = u128::from(base_factor) * u128::from(bin_step) * 10;
+
+    (1..=dlmm::constants::MAX_BIN_STEP).contains(&bin_step)
+        && (dlmm::constants::MIN_BASE_FEE..=dlmm::constants::MAX_BASE_FEE).contains(&base_fee)
+}
+
+/// Returns true if the bin array at `bin_array_index` is outside the pool's
+/// built-in bitmap, which covers indexes -512 to 511. Bin arrays outside it
+/// are tracked by the bin array bitmap extension account instead.
+pub fn is_overflow_default_bin_array_bitmap(bin_array_index: i64) -> bool {
+    let bitmap_size = i64::from(dlmm::constants::BIN_ARRAY_BITMAP_SIZE);
+    !(-bitmap_size..bitmap_size).contains(&bin_array_index)
+}
+
+/// Returns the index of the bin array containing `bin_id`, i.e. floor(bin_id / 70).
+pub fn bin_array_index(bin_id: i32) -> i64 {
+    i64::from(bin_id).div_euclid(dlmm::constants::MAX_BIN_PER_ARRAY as i64)
+}
+
+/// Returns the index of every bin array the bins `lower_bin_id` to
+/// `upper_bin_id` span, in ascending order. Empty if the range is reversed.
+pub fn required_bin_array_indices(lower_bin_id: i32, upper_bin_id: i32) -> Vec<i64> {
+    if lower_bin_id > upper_bin_id {
+        return vec![];
+    }
+    (bin_array_index(lower_bin_id)..=bin_array_index(upper_bin_id)).collect()
+}
+
+/// Returns the index of the bin array containing the pool's active bin.
+pub fn active_bin_array_index(lb_pair: &dlmm::accounts::LbPair) -> i64 {
+    bin_array_index(lb_pair.active_id)
+}
+
+/// Derives the bin array PDA containing the pool's active bin. This is the
+/// first bin array a swap needs in its remaining accounts.
+pub fn derive_active_bin_array(
+    lb_pair_key: Pubkey,
+    lb_pair: &dlmm::accounts::LbPair,
+) -> (Pubkey, u8) {
+    derive_bin_array_pda(lb_pair_key, active_bin_array_index(lb_pair))
+}
+
+/// Number of fractional bits of the Q64.64 prices used by DLMM.
+pub const SCALE_OFFSET: u8 = 64;
+
+/// 1.0 in Q64.64.
+pub const ONE_Q64: u128 = 1u128 << SCALE_OFFSET;
+
+/// Multiplies two Q64.64 numbers, rounding down. Returns None on overflow.
+pub fn mul_q64(a: u128, b: u128) -> Option<u128> {
+    let mask = u128::from(u64::MAX);
+    let (a_hi, a_lo) = (a >> SCALE_OFFSET, a & mask);
+    let (b_hi, b_lo) = (b >> SCALE_OFFSET, b & mask);
+
+    // (a_hi * 2^64 + a_lo) * (b_hi * 2^64 + b_lo) / 2^64
+    let hi = a_hi.checked_mul(b_hi)?;
+    if hi > mask {
+        return None;
+    }
+
+    (hi << SCALE_OFFSET)
+        .checked_add(a_hi.checked_mul(b_lo)?)?
+        .checked_add(a_lo.checked_mul(b_hi)?)?
+        .checked_add((a_lo * b_lo) >> SCALE_OFFSET)
+}
+
+/// Returns the price of `bin_id` as a Q64.64 number of token Y base units per
+/// token X base unit: (1 + bin_step / 10000) ^ bin_id.
+pub fn price_from_bin_id(bin_id: i32, bin_step: u16) -> Option<u128> {
+    let bps = (u128::from(bin_step) << SCALE_OFFSET)
+        / u128::from(dlmm::constants::BASIS_POINT_MAX as u32);
+    let base = ONE_Q64.checked_add(bps)?;
+
+    // Negative bin ids use the inverted base, 2^128 / base ~= u128::MAX / base
+    let mut base = if bin_id < 0 { u128::MAX / base } else { base };
+    let mut exp = bin_id.unsigned_abs();
+    let mut result = ONE_Q64;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_q64(result, base)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_q64(base, base)?;
+        }
+    }
+
+    Some(result)
+}
+
+/// Converts an amount of token Y base units into token X base units at a
+/// Q64.64 `price`, rounding down. Returns None on overflow.
+pub fn div_by_price(amount_y: u128, price: u128) -> Option<u128> {
+    if amount_y > u128::from(u64::MAX) || price == 0 {
+        return None;
+    }
+
+    Some((amount_y << SCALE_OFFSET) / price)
+}
+
+/// Returns the part of a bin's `bin_amount` owned by `liquidity_share` out of
+/// the bin's `liquidity_supply`, rounding down.
+///
+/// Shares are Q64.64 scaled, so both share and supply are shifted right until
+/// the share fits in 64 bits to keep the product within u128. The relative
+/// error is below 2^-64 of the result.
+pub fn amount_for_share(bin_amount: u64, liquidity_share: u128, liquidity_supply: u128) -> u64 {
+    if liquidity_share == 0 || liquidity_supply == 0 {
+        return 0;
+    }
+
+    let shift = 64u32.saturating_sub(liquidity_share.leading_zeros());
+    let share = liquidity_share >> shift;
+    let supply = liquidity_supply >> shift;
+    if supply == 0 {
+        return 0;
+    }
+
+    // share < 2^64, so the product cannot overflow
+    let amount = u128::from(bin_amount) * share / supply;
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+/// Returns the fee earned by `liquidity_share` since the position last
+/// settled the bin, rounding down like the DLMM program does: the growth of
+/// the bin's Q64.64 fee per token since `fee_per_token_complete`, times the
+/// share without its fractional bits. Returns None on overflow or if the
+/// bin's fee per token is behind the position's.
+pub fn fee_since_update(
+    liquidity_share: u128,
+    fee_per_token_stored: u128,
+    fee_per_token_complete: u128,
+) -> Option<u64> {
+    let growth = fee_per_token_stored.checked_sub(fee_per_token_complete)?;
+    let fee = mul_q64(liquidity_share >> SCALE_OFFSET, growth)?;
+    u64::try_from(fee).ok()
+}
+
+/// Returns token X's share of the value of `amount_x` and `amount_y`, in
+/// basis points, with token X valued at `price` (Q64.64) in token Y. Returns
+/// None if both amounts are zero, or on overflow.
+pub fn x_value_share_bps(amount_x: u128, amount_y: u128, price: u128) -> Option<u64> {
+    let value_x = mul_q64(amount_x, price)?;
+    let value = value_x.checked_add(amount_y)?;
+    if value == 0 {
+        return None;
+    }
+
+    let share = value_x.checked_mul(dlmm::constants::BASIS_POINT_MAX as u128)? / value;
+    u64::try_from(share).ok()
+}
+
+/// Number of seconds in a 365 day year, used to annualize returns.
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Annualizes `earned` over `elapsed_seconds` as a simple (not compounded)
+/// rate of `principal`, in basis points, rounding down. Returns None if
+/// `principal` or `elapsed_seconds` is zero, or on overflow.
+pub fn annualized_bps(earned: u128, principal: u128, elapsed_seconds: u64) -> Option<u64> {
+    if principal == 0 || elapsed_seconds == 0 {
+        return None;
+    }
+
+    let numerator = earned
+        .checked_mul(dlmm::constants::BASIS_POINT_MAX as u128)?
+        .checked_mul(u128::from(SECONDS_PER_YEAR))?;
+    let denominator = principal.checked_mul(u128::from(elapsed_seconds))?;
+    u64::try_from(numerator / denominator).ok()
+}
+
+/// Returns true if any bin ID appears more than once in a liquidity
+/// distribution. Shared by every add liquidity handler so duplicated bins
+/// are rejected before the deposit reaches the DLMM program.
+pub fn has_duplicate_bins(bin_ids: impl IntoIterator<Item = i32>) -> bool {
+    let mut bin_ids: Vec<i32> = bin_ids.into_iter().collect();
+    bin_ids.sort_unstable();
+    bin_ids.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+/// Most entries `dlmm_remove_liquidity` accepts in `bin_liquidity_removal`.
+/// A position spans at most 70 bins, and a longer list would only fail once
+/// the CPI has used up the transaction's compute.
+pub const MAX_BINS_PER_REMOVE: usize = 70;
+
+/// Most entries `dlmm_add_liquidity_one_side` accepts in
+/// `bin_liquidity_dist`, capped like `MAX_BINS_PER_REMOVE`.
+pub const MAX_BINS_PER_ADD: usize = 70;
+
+/// Checks a per-bin removal list before it is forwarded to the DLMM program,
+/// which would spend the CPI only to reject or ignore it. Fails with
+/// `EmptyBinList` if there are no entries, or with `InvalidAmount` if an
+/// entry removes 0 bps.
+pub fn verify_bin_liquidity_removal(
+    bin_liquidity_removal: &[dlmm::types::BinLiquidityReduction],
+) -> Result<()> {
+    require!(
+        !bin_liquidity_removal.is_empty(),
+        crate::errors::DlmmCpiError::EmptyBinList
+    );
+    require!(
+        bin_liquidity_removal
+            .iter()
+            .all(|removal| removal.bps_to_remove > 0),
+        crate::errors::DlmmCpiError::InvalidAmount
+    );
+    Ok(())
+}
+
+// Compute units each DLMM CPI of a composite instruction needs at least, for
+// a position of a few bins. They are lower bounds: the cost grows with the
+// bins touched, and a full 70 bin `remove_all_liquidity` takes ~250k. A step
+// started with less than its estimate is sure to run out mid-CPI.
+
+/// Estimated compute units of an `initialize_position` CPI.
+pub const INITIALIZE_POSITION_COMPUTE_UNITS: u64 = 20_000;
+/// Estimated compute units of an `initialize_bin_array` or
+/// `initialize_bin_array_bitmap_extension` CPI.
+pub const INITIALIZE_BIN_ARRAY_COMPUTE_UNITS: u64 = 15_000;
+/// Estimated compute units of an `add_liquidity` or
+/// `add_liquidity_by_strategy` CPI.
+pub const ADD_LIQUIDITY_COMPUTE_UNITS: u64 = 40_000;
+/// Estimated compute units of a `remove_liquidity` or
+/// `remove_all_liquidity` CPI.
+pub const REMOVE_LIQUIDITY_COMPUTE_UNITS: u64 = 40_000;
+/// Estimated compute units of a `swap` CPI crossing a single bin.
+pub const SWAP_COMPUTE_UNITS: u64 = 40_000;
+/// Estimated compute units of a `claim_fee` CPI.
+pub const CLAIM_FEE_COMPUTE_UNITS: u64 = 20_000;
+/// Estimated compute units of a `close_position` CPI.
+pub const CLOSE_POSITION_COMPUTE_UNITS: u64 = 10_000;
+
+/// Checks that at least `required` compute units remain before a composite
+/// instruction starts its next CPI. Running out fails the whole transaction
+/// either way; this fails it with `InsufficientCompute` and logs the
+/// shortfall instead of erroring deep inside the DLMM program.
+///
+/// Outside the BPF runtime the syscall stub reports 0 remaining units, so
+/// tests of composite instructions must run the program with `prefer_bpf`.
+pub fn require_compute_units(required: u64) -> Result<()> {
+    let remaining = sol_remaining_compute_units();
+    if remaining < required {
+        msg!(
+            "{} compute units remaining, next step needs at least {}",
+            remaining,
+            required
+        );
+        return err!(crate::errors::DlmmCpiError::InsufficientCompute);
+    }
+    Ok(())
+}
+
+/// Returns the Token-2022 transfer fee charged on a transfer that delivered
+/// `post_fee_amount` of `mint` during `epoch`.
+///
+/// Returns 0 for SPL Token mints and Token-2022 mints without the transfer
+/// fee extension.
+pub fn transfer_fee_for_received_amount(
+    mint: &AccountInfo,
+    post_fee_amount: u64,
+    epoch: u64,
+) -> Result<u64> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_inverse_epoch_fee(epoch, post_fee_amount)
+            .ok_or(crate::errors::DlmmCpiError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Returns the Token-2022 transfer fee charged when `pre_fee_amount` of
+/// `mint` is sent during `epoch`, the counterpart of
+/// `transfer_fee_for_received_amount`.
+///
+/// Returns 0 for SPL Token mints and Token-2022 mints without the transfer
+/// fee extension.
+pub fn transfer_fee_for_sent_amount(
+    mint: &AccountInfo,
+    pre_fee_amount: u64,
+    epoch: u64,
+) -> Result<u64> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_epoch_fee(epoch, pre_fee_amount)
+            .ok_or(crate::errors::DlmmCpiError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Returns the transfer hook program of `mint`, if it is a Token-2022 mint
+/// with the transfer hook extension and a hook program set.
+///
+/// The DLMM `swap` instruction passes its remaining accounts to the program
+/// as bin arrays, so it has no way to carry the extra accounts a hook needs;
+/// transfers of such a mint fail inside the DLMM program.
+pub fn transfer_hook_program(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+
+    match mint.get_extension::<TransferHook>() {
+        Ok(transfer_hook) => Ok(Option::<Pubkey>::from(transfer_hook.program_id)),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_bin_liquidity_removal() {
+        let removal = |bin_id, bps_to_remove| dlmm::types::BinLiquidityReduction {
+            bin_id,
+            bps_to_remove,
+        };
+
+        assert!(verify_bin_liquidity_removal(&[removal(1, 10_000), removal(2, 1)]).is_ok());
+        assert_eq!(
+            verify_bin_liquidity_removal(&[]).unwrap_err(),
+            crate::errors::DlmmCpiError::EmptyBinList.into()
+        );
+        assert_eq!(
+            verify_bin_liquidity_removal(&[removal(1, 10_000), removal(2, 0)]).unwrap_err(),
+            crate::errors::DlmmCpiError::InvalidAmount.into()
+        );
+    }
+
+    #[test]
+    fn test_required_bin_array_indices() {
+        // Within one array
+        assert_eq!(required_bin_array_indices(70, 139), [1]);
+        assert_eq!(required_bin_array_indices(5, 5), [0]);
+        // Spanning two
+        assert_eq!(required_bin_array_indices(100, 169), [1, 2]);
+        // Crossing index 0
+        assert_eq!(required_bin_array_indices(-35, 34), [-1, 0]);
+        assert_eq!(required_bin_array_indices(-71, 70), [-2, -1, 0, 1]);
+
+        assert!(required_bin_array_indices(10, 9).is_empty());
+    }
+
+    #[test]
+    fn test_bin_array_index_boundaries() {
+        assert_eq!(bin_array_index(0), 0);
+        assert_eq!(bin_array_index(69), 0);
+        assert_eq!(bin_array_index(70), 1);
+        assert_eq!(bin_array_index(139), 1);
+        assert_eq!(bin_array_index(140), 2);
+        assert_eq!(bin_array_index(-1), -1);
+        assert_eq!(bin_array_index(-70), -1);
+        assert_eq!(bin_array_index(-71), -2);
+        assert_eq!(bin_array_index(-140), -2);
+        assert_eq!(bin_array_index(dlmm::constants::MAX_BIN_ID), 6337);
+        assert_eq!(bin_array_index(dlmm::constants::MIN_BIN_ID), -6338);
+    }
+
+    #[test]
+    fn test_is_overflow_default_bin_array_bitmap() {
+        assert!(!is_overflow_default_bin_array_bitmap(0));
+        assert!(!is_overflow_default_bin_array_bitmap(511));
+        assert!(!is_overflow_default_bin_array_bitmap(-512));
+        assert!(is_overflow_default_bin_array_bitmap(512));
+        assert!(is_overflow_default_bin_array_bitmap(-513));
+        assert!(is_overflow_default_bin_array_bitmap(bin_array_index(
+            dlmm::constants::MAX_BIN_ID
+        )));
+    }
+
+    #[test]
+    fn test_fee_since_update() {
+        let share = 1_000u128 << SCALE_OFFSET;
+
+        assert_eq!(fee_since_update(share, ONE_Q64, 0), Some(1_000));
+        assert_eq!(fee_since_update(share, 3 * ONE_Q64, ONE_Q64), Some(2_000));
+        assert_eq!(fee_since_update(share, ONE_Q64 / 2, 0), Some(500));
+        assert_eq!(fee_since_update(share, ONE_Q64, ONE_Q64), Some(0));
+        // Fractional share bits are dropped before multiplying
+        assert_eq!(fee_since_update(share + 1, ONE_Q64, 0), Some(1_000));
+        assert_eq!(fee_since_update(share, 0, ONE_Q64), None);
+    }
+
+    #[test]
+    fn test_verify_dlmm_owned() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+
+        let dlmm_owned = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &dlmm::ID,
+            false,
+            0,
+        );
+        assert!(verify_dlmm_owned(&dlmm_owned).is_ok());
+
+        let mut lamports = 0;
+        let mut data = [];
+        let system_owned = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_lang::system_program::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            verify_dlmm_owned(&system_owned).unwrap_err(),
+            crate::errors::DlmmCpiError::AccountNotOwnedByDlmm.into()
+        );
+    }
+
+    #[test]
+    fn test_annualized_bps() {
+        let day = 24 * 60 * 60;
+
+        // 0.1% in a day is 36.5% a year
+        assert_eq!(annualized_bps(1_000, 1_000_000, day), Some(3_650));
+        // 1% in a year
+        assert_eq!(
+            annualized_bps(10_000, 1_000_000, SECONDS_PER_YEAR),
+            Some(100)
+        );
+        // Doubling in half a year
+        assert_eq!(
+            annualized_bps(1_000_000, 1_000_000, SECONDS_PER_YEAR / 2),
+            Some(20_000)
+        );
+        assert_eq!(annualized_bps(0, 1_000_000, day), Some(0));
+        assert_eq!(annualized_bps(1_000, 0, day), None);
+        assert_eq!(annualized_bps(1_000, 1_000_000, 0), None);
+    }
+
+    #[test]
+    fn test_verify_bin_array() {
+        let lb_pair = Pubkey::new_unique();
+        let (bin_array, _bump) = derive_bin_array_pda(lb_pair, -2);
+
+        assert!(verify_bin_array(lb_pair, bin_array, -2).is_ok());
+        assert_eq!(
+            verify_bin_array(lb_pair, bin_array, -1).unwrap_err(),
+            crate::errors::DlmmCpiError::InvalidBinArrayPda.into()
+        );
+        assert!(verify_bin_array(Pubkey::new_unique(), bin_array, -2).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_bin_step() {
+        // 0.25% base fee with a 25 bps bin step
+        assert!(is_valid_bin_step(25, 10_000));
+        assert!(is_valid_bin_step(dlmm::constants::MAX_BIN_STEP, 250));
+        assert!(!is_valid_bin_step(0, 10_000));
+        assert!(!is_valid_bin_step(dlmm::constants::MAX_BIN_STEP + 1, 250));
+        // Base fee of 0.001%, below the 0.01% minimum
+        assert!(!is_valid_bin_step(1, 1_000));
+        // Base fee of 12%, above the 10% maximum
+        assert!(!is_valid_bin_step(400, 30_000));
+    }
+
+    #[test]
+    fn test_x_value_share_bps() {
+        assert_eq!(x_value_share_bps(1, 1, ONE_Q64), Some(5_000));
+        assert_eq!(x_value_share_bps(3, 1, ONE_Q64), Some(7_500));
+        assert_eq!(x_value_share_bps(0, 5, ONE_Q64), Some(0));
+        assert_eq!(x_value_share_bps(5, 0, ONE_Q64), Some(10_000));
+        // At a price of 2 Y per X, one X is worth as much as two Y
+        assert_eq!(x_value_share_bps(1, 2, 2 * ONE_Q64), Some(5_000));
+        assert_eq!(x_value_share_bps(0, 0, ONE_Q64), None);
+    }
+
+    #[test]
+    fn test_verify_event_authority() {
+        let (event_authority, _bump) = derive_event_authority();
+
+        assert!(verify_event_authority(event_authority).is_ok());
+        assert_eq!(
+            verify_event_authority(Pubkey::new_unique()).unwrap_err(),
+            crate::errors::DlmmCpiError::InvalidEventAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_reserves() {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.reserve_x = Pubkey::new_unique();
+        lb_pair.reserve_y = Pubkey::new_unique();
+
+        assert!(verify_reserves(&lb_pair, lb_pair.reserve_x, lb_pair.reserve_y).is_ok());
+        assert_eq!(
+            verify_reserves(&lb_pair, lb_pair.reserve_y, lb_pair.reserve_x).unwrap_err(),
+            crate::errors::DlmmCpiError::ReservesSwapped.into()
+        );
+        assert_eq!(
+            verify_reserves(&lb_pair, lb_pair.reserve_x, lb_pair.reserve_x).unwrap_err(),
+            crate::errors::DlmmCpiError::ReserveMismatch.into()
+        );
+        assert_eq!(
+            verify_reserves(&lb_pair, lb_pair.reserve_x, Pubkey::new_unique()).unwrap_err(),
+            crate::errors::DlmmCpiError::ReserveMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_pool_contains_mint() {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.token_x_mint = Pubkey::new_unique();
+        lb_pair.token_y_mint = Pubkey::new_unique();
+
+        assert!(pool_contains_mint(&lb_pair, &lb_pair.token_x_mint));
+        assert!(pool_contains_mint(&lb_pair, &lb_pair.token_y_mint));
+        assert!(!pool_contains_mint(&lb_pair, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_swap_for_y() {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.token_x_mint = Pubkey::new_unique();
+        lb_pair.token_y_mint = Pubkey::new_unique();
+        let (x, y) = (lb_pair.token_x_mint, lb_pair.token_y_mint);
+
+        assert!(swap_for_y(&lb_pair, x, y).unwrap());
+        assert!(!swap_for_y(&lb_pair, y, x).unwrap());
+
+        assert_eq!(
+            swap_for_y(&lb_pair, Pubkey::new_unique(), y).unwrap_err(),
+            crate::errors::DlmmCpiError::MintNotInPool.into()
+        );
+        for mint_out in [x, Pubkey::new_unique()] {
+            assert_eq!(
+                swap_for_y(&lb_pair, x, mint_out).unwrap_err(),
+                crate::errors::DlmmCpiError::SwapDirectionMismatch.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_active_bin_array_index_boundaries() {
+        let mut lb_pair: dlmm::accounts::LbPair = bytemuck::Zeroable::zeroed();
+
+        for (active_id, expected) in [(69, 0), (70, 1), (-70, -1), (-71, -2)] {
+            lb_pair.active_id = active_id;
+            assert_eq!(active_bin_array_index(&lb_pair), expected);
+
+            let lb_pair_key = Pubkey::new_unique();
+            assert_eq!(
+                derive_active_bin_array(lb_pair_key, &lb_pair),
+                derive_bin_array_pda(lb_pair_key, expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_has_duplicate_bins() {
+        assert!(!has_duplicate_bins([]));
+        assert!(!has_duplicate_bins([-1, 0, 1]));
+        assert!(has_duplicate_bins([5, -3, 7, -3]));
+    }
+
+    fn transfer_fee_mint_data(transfer_fee_basis_points: u16, maximum_fee: u64) -> Vec<u8> {
+        use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+
+        let len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        let mut data = vec![0u8; len];
+
+        let mut state =
+            StateWithExtensionsMut::<Token2022Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        };
+        let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        config.older_transfer_fee = transfer_fee;
+        config.newer_transfer_fee = transfer_fee;
+
+        state.base = Token2022Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    #[test]
+    fn test_transfer_fee_for_received_amount() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        // 1% transfer fee
+        let mut data = transfer_fee_mint_data(100, u64::MAX);
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token_2022::ID,
+            false,
+            0,
+        );
+
+        // 10_000 sent, 100 withheld, 9_900 received
+        assert_eq!(
+            transfer_fee_for_received_amount(&mint, 9_900, 0).unwrap(),
+            100
+        );
+        assert_eq!(transfer_fee_for_received_amount(&mint, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_transfer_fee_for_sent_amount() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        // 1% transfer fee, capped at 50
+        let mut data = transfer_fee_mint_data(100, 50);
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token_2022::ID,
+            false,
+            0,
+        );
+
+        // 1_000 sent, 10 withheld, 990 received
+        assert_eq!(transfer_fee_for_sent_amount(&mint, 1_000, 0).unwrap(), 10);
+        assert_eq!(transfer_fee_for_sent_amount(&mint, 10_000, 0).unwrap(), 50);
+        assert_eq!(transfer_fee_for_sent_amount(&mint, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_transfer_fee_for_received_amount_spl_token() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; anchor_spl::token::Mint::LEN];
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token::ID,
+            false,
+            0,
+        );
+
+        assert_eq!(
+            transfer_fee_for_received_amount(&mint, 9_900, 0).unwrap(),
+            0
+        );
+    }
+
+    fn transfer_hook_mint_data(program_id: Option<Pubkey>) -> Vec<u8> {
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+
+        let len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+            ExtensionType::TransferHook,
+        ])
+        .unwrap();
+        let mut data = vec![0u8; len];
+
+        let mut state =
+            StateWithExtensionsMut::<Token2022Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_hook = state.init_extension::<TransferHook>(true).unwrap();
+        transfer_hook.program_id = program_id.try_into().unwrap();
+
+        state.base = Token2022Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        data
+    }
+
+    #[test]
+    fn test_transfer_hook_program() {
+        let key = Pubkey::new_unique();
+        let hook_program = Pubkey::new_unique();
+
+        for (program_id, expected) in [(Some(hook_program), Some(hook_program)), (None, None)] {
+            let mut lamports = 0;
+            let mut data = transfer_hook_mint_data(program_id);
+            let mint = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &anchor_spl::token_2022::ID,
+                false,
+                0,
+            );
+
+            assert_eq!(transfer_hook_program(&mint).unwrap(), expected);
+        }
+
+        // Token-2022 mint without the extension
+        let mut lamports = 0;
+        let mut data = transfer_fee_mint_data(100, u64::MAX);
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token_2022::ID,
+            false,
+            0,
+        );
+        assert_eq!(transfer_hook_program(&mint).unwrap(), None);
+    }
+
+    #[test]
+    fn test_amount_for_share() {
+        assert_eq!(amount_for_share(1_000, 0, 0), 0);
+        assert_eq!(amount_for_share(1_000, 1, 4), 250);
+
+        // Q64.64 scaled shares that don't fit in 64 bits
+        let supply = 3_000_000u128 << SCALE_OFFSET;
+        assert_eq!(amount_for_share(u64::MAX, supply, supply), u64::MAX);
+        assert_eq!(amount_for_share(3_000_000, supply / 3, supply), 1_000_000);
+    }
+}
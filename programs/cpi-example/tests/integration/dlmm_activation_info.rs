@@ -0,0 +1,66 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::dlmm_activation_info::ActivationInfo;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    account::AccountSharedData, instruction::Instruction, signature::Keypair, signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_activation_info_future_slot() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let mut context = test.start_with_context().await;
+
+    // Turn the pool into a slot activated pool that opens 1_000 slots from now
+    let current_slot = context.banks_client.get_root_slot().await.unwrap();
+    let activation_point = current_slot + 1_000;
+
+    let mut pool_account = context
+        .banks_client
+        .get_account(USDC_USDT_POOL)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut pool_state: LbPair = read_zero_copy_account(&pool_account.data);
+    pool_state.activation_type = 0;
+    pool_state.activation_point = activation_point;
+    pool_account.data[8..8 + std::mem::size_of::<LbPair>()]
+        .copy_from_slice(bytemuck::bytes_of(&pool_state));
+    context.set_account(&USDC_USDT_POOL, &AccountSharedData::from(pool_account));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmActivationInfo {}.data(),
+        accounts: cpi_example::accounts::DlmmActivationInfo {
+            lb_pair: USDC_USDT_POOL,
+        }
+        .to_account_metas(None),
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut context.banks_client)
+            .await;
+    let info = ActivationInfo::try_from_slice(&return_data).unwrap();
+
+    assert_eq!(info.activation_type, 0);
+    assert_eq!(info.activation_point, activation_point);
+    assert!(info.current_slot_or_time >= current_slot);
+    assert!(info.remaining > 0);
+    assert_eq!(info.remaining, activation_point - info.current_slot_or_time);
+}
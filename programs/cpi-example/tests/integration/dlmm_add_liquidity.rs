@@ -0,0 +1,316 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::dlmm::types::{BinLiquidityDistribution, LiquidityParameter};
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn add_liquidity_ix(
+    owner: &Keypair,
+    pool_state: &LbPair,
+    position: &PositionContext,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    data: cpi_example::instruction::DlmmAddLiquidity,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: data.data(),
+        accounts: cpi_example::accounts::DlmmAddLiquidity {
+            position: position.position,
+            lb_pair: position.lb_pair,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+async fn setup() -> (BanksClient, Keypair, PoolSetupContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The position may cross into a neighbouring bin array
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_setup.pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (banks_client, _, _) = test.start().await;
+
+    (banks_client, mock_user, pool_setup)
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_balanced_around_active_bin() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            user_token_y,
+        },
+    ) = setup().await;
+
+    let active_id = pool_state.active_id;
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        active_id - 2,
+        5,
+    )
+    .await;
+
+    // Token Y goes to the active bin and the 2 bins below, token X to the active bin
+    // and the 2 bins above
+    let bin_liquidity_dist = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityDistribution {
+            bin_id,
+            distribution_x: if bin_id >= active_id { 3333 } else { 0 },
+            distribution_y: if bin_id <= active_id { 3333 } else { 0 },
+        })
+        .collect();
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let instruction = add_liquidity_ix(
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        user_token_y,
+        cpi_example::instruction::DlmmAddLiquidity {
+            liquidity_parameter: LiquidityParameter {
+                amount_x: 1_000_000,
+                amount_y: 1_000_000,
+                bin_liquidity_dist,
+            },
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 0,
+            min_deposit_x: None,
+            min_deposit_y: None,
+        },
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    let deposited_x = x_before - get_token_balance(&mut banks_client, user_token_x).await;
+    let deposited_y = y_before - get_token_balance(&mut banks_client, user_token_y).await;
+
+    assert!(deposited_x > 0 && deposited_x <= 1_000_000);
+    assert!(deposited_y > 0 && deposited_y <= 1_000_000);
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_zero_y_distribution() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            user_token_y,
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        4,
+    )
+    .await;
+
+    let bin_liquidity_dist = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityDistribution {
+            bin_id,
+            distribution_x: 2500,
+            distribution_y: 0,
+        })
+        .collect();
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let instruction = add_liquidity_ix(
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        user_token_y,
+        cpi_example::instruction::DlmmAddLiquidity {
+            liquidity_parameter: LiquidityParameter {
+                amount_x: 1_000_000,
+                amount_y: 0,
+                bin_liquidity_dist,
+            },
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 0,
+            min_deposit_x: None,
+            min_deposit_y: None,
+        },
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    assert!(get_token_balance(&mut banks_client, user_token_x).await < x_before);
+    assert_eq!(
+        get_token_balance(&mut banks_client, user_token_y).await,
+        y_before
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_below_min_deposit() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            user_token_y,
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        4,
+    )
+    .await;
+
+    // The whole range is above the active bin, so none of amount_y is taken,
+    // as if the price had moved below the range since the deposit was built
+    let bin_liquidity_dist = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityDistribution {
+            bin_id,
+            distribution_x: 2500,
+            distribution_y: 0,
+        })
+        .collect();
+
+    let instruction = add_liquidity_ix(
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        user_token_y,
+        cpi_example::instruction::DlmmAddLiquidity {
+            liquidity_parameter: LiquidityParameter {
+                amount_x: 1_000_000,
+                amount_y: 1_000_000,
+                bin_liquidity_dist,
+            },
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 0,
+            min_deposit_x: Some(1),
+            min_deposit_y: Some(500_000),
+        },
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::DepositBelowMinimum.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_active_bin_drifted() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            user_token_y,
+        },
+    ) = setup().await;
+
+    let active_id = pool_state.active_id;
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        active_id - 2,
+        5,
+    )
+    .await;
+
+    let bin_liquidity_dist = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityDistribution {
+            bin_id,
+            distribution_x: if bin_id >= active_id { 3333 } else { 0 },
+            distribution_y: if bin_id <= active_id { 3333 } else { 0 },
+        })
+        .collect();
+
+    // Built when the active bin was 3 bins lower, beyond the allowed 2
+    let instruction = add_liquidity_ix(
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        user_token_y,
+        cpi_example::instruction::DlmmAddLiquidity {
+            liquidity_parameter: LiquidityParameter {
+                amount_x: 1_000_000,
+                amount_y: 1_000_000,
+                bin_liquidity_dist,
+            },
+            active_id: active_id - 3,
+            max_active_bin_slippage: 2,
+            min_deposit_x: None,
+            min_deposit_y: None,
+        },
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::SlippageExceeded.into(),
+    )
+    .await;
+}
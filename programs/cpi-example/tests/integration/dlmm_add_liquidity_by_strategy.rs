@@ -0,0 +1,170 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, AnchorSerialize, Discriminator,
+    InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{LiquidityParameterByStrategy, StrategyParameters, StrategyType};
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn add_liquidity_by_strategy_ix(liquidity_parameter: LiquidityParameterByStrategy) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddLiquidityByStrategy {
+            liquidity_parameter,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmAddLiquidity {
+            position: Pubkey::new_unique(),
+            lb_pair: Pubkey::new_unique(),
+            bin_array_bitmap_extension: None,
+            user_token_x: Pubkey::new_unique(),
+            user_token_y: Pubkey::new_unique(),
+            reserve_x: Pubkey::new_unique(),
+            reserve_y: Pubkey::new_unique(),
+            token_x_mint: Pubkey::new_unique(),
+            token_y_mint: Pubkey::new_unique(),
+            bin_array_lower: Pubkey::new_unique(),
+            bin_array_upper: Pubkey::new_unique(),
+            sender: Pubkey::new_unique(),
+            dlmm_program: dlmm::ID,
+            event_authority: Pubkey::new_unique(),
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[test]
+fn test_dlmm_add_liquidity_by_strategy_builds_for_each_strategy() {
+    let active_id = 100;
+
+    // (strategy, min_bin_id, max_bin_id): a symmetric range, a single bin and a
+    // range entirely above the active bin
+    let cases = [
+        (StrategyType::SpotBalanced, active_id - 5, active_id + 5),
+        (StrategyType::CurveBalanced, active_id, active_id),
+        (StrategyType::BidAskBalanced, active_id + 1, active_id + 10),
+    ];
+
+    for (strategy_type, min_bin_id, max_bin_id) in cases {
+        let liquidity_parameter = LiquidityParameterByStrategy {
+            amount_x: 1_000_000,
+            amount_y: 1_000_000,
+            active_id,
+            max_active_bin_slippage: 3,
+            strategy_parameters: StrategyParameters {
+                min_bin_id,
+                max_bin_id,
+                strategy_type,
+                parameteres: [0; 64],
+            },
+        };
+        let expected = liquidity_parameter.try_to_vec().unwrap();
+
+        let instruction = add_liquidity_by_strategy_ix(liquidity_parameter);
+
+        let discriminator = cpi_example::instruction::DlmmAddLiquidityByStrategy::DISCRIMINATOR;
+        assert_eq!(&instruction.data[..discriminator.len()], discriminator);
+
+        // Parameters reach the program unchanged
+        let decoded = cpi_example::instruction::DlmmAddLiquidityByStrategy::try_from_slice(
+            &instruction.data[discriminator.len()..],
+        )
+        .unwrap();
+        assert_eq!(decoded.liquidity_parameter.try_to_vec().unwrap(), expected);
+
+        assert_eq!(instruction.accounts.len(), 16);
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_by_strategy_bid_ask_outside_position() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    // The BidAsk range runs 5 bins past the upper edge of the position
+    let ix_data = cpi_example::instruction::DlmmAddLiquidityByStrategy {
+        liquidity_parameter: LiquidityParameterByStrategy {
+            amount_x: 1_000_000,
+            amount_y: 0,
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 3,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: position.lower_bin_id,
+                max_bin_id: position.upper_bin_id() + 5,
+                strategy_type: StrategyType::BidAskImBalanced,
+                parameteres: [0; 64],
+            },
+        },
+    }
+    .data();
+
+    let accounts = cpi_example::accounts::DlmmAddLiquidity {
+        position: position.position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower: position.bin_array_lower(),
+        bin_array_upper: position.bin_array_upper(),
+        sender: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::InvalidStrategyRange.into(),
+    )
+    .await;
+}
@@ -0,0 +1,146 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{LiquidityParameterByStrategy, StrategyParameters, StrategyType};
+use cpi_example::dlmm_add_liquidity_by_strategy_auto::LiquidityParameterByStrategyAuto;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// DLMM `ExceededBinSlippageTolerance` error code
+const EXCEEDED_BIN_SLIPPAGE_TOLERANCE: u32 = 6004;
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_by_strategy_auto_uses_active_bin() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The position may cross into a neighbouring bin array
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id - 5,
+        11,
+    )
+    .await;
+
+    let accounts = cpi_example::accounts::DlmmAddLiquidity {
+        position: position.position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower: position.bin_array_lower(),
+        bin_array_upper: position.bin_array_upper(),
+        sender: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let strategy_parameters = StrategyParameters {
+        min_bin_id: position.lower_bin_id,
+        max_bin_id: position.upper_bin_id(),
+        strategy_type: StrategyType::SpotBalanced,
+        parameteres: [0; 64],
+    };
+
+    // A stale observation 5 bins off fails the plain strategy add with no
+    // slippage allowed
+    let stale = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddLiquidityByStrategy {
+            liquidity_parameter: LiquidityParameterByStrategy {
+                amount_x: 1_000_000,
+                amount_y: 1_000_000,
+                active_id: pool_state.active_id + 5,
+                max_active_bin_slippage: 0,
+                strategy_parameters: strategy_parameters.clone(),
+            },
+        }
+        .data(),
+        accounts: accounts.clone(),
+    };
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            stale,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        EXCEEDED_BIN_SLIPPAGE_TOLERANCE,
+    )
+    .await;
+
+    // Reading the active bin on-chain passes the same check
+    let auto = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddLiquidityByStrategyAuto {
+            liquidity_parameter: LiquidityParameterByStrategyAuto {
+                amount_x: 1_000_000,
+                amount_y: 1_000_000,
+                strategy_parameters,
+            },
+            max_slippage: 0,
+        }
+        .data(),
+        accounts,
+    };
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            auto,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    // The range straddles the active bin, so both tokens are deposited
+    assert!(get_token_balance(&mut banks_client, user_token_x).await < x_before);
+    assert!(get_token_balance(&mut banks_client, user_token_y).await < y_before);
+}
@@ -0,0 +1,432 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityDistributionByWeight;
+use cpi_example::errors::DlmmCpiError;
+use cpi_example::utils::MAX_BINS_PER_ADD;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+async fn setup() -> (BanksClient, Keypair, PoolSetupContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The position may cross into a neighbouring bin array
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_setup.pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (banks_client, _, _) = test.start().await;
+
+    (banks_client, mock_user, pool_setup)
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_x_at_active_bin() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    // Token X must go strictly above the active bin, the first bin is the active one
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id,
+        10,
+    )
+    .await;
+
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBinForSide.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_x_above_active_bin() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    assert!(get_token_balance(&mut banks_client, user_token_x).await < x_before);
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_y_at_and_below_active_bin() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_y,
+            ..
+        },
+    ) = setup().await;
+
+    // Token Y may go to the active bin and below, the last bin is the active one
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id - 9,
+        10,
+    )
+    .await;
+
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_y,
+        false,
+        1_000_000,
+    );
+
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    assert!(get_token_balance(&mut banks_client, user_token_y).await < y_before);
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_foreign_mint() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let mut instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+    // token_mint
+    instruction.accounts[5].pubkey = Pubkey::new_unique();
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::MintNotInPool.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_reserve_of_other_side() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let mut instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+    // reserve, token X is deposited into the token Y reserve
+    instruction.accounts[4].pubkey = pool_state.reserve_y;
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::ReserveMismatch.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_zero_amount() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        0,
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidAmount.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_max_bins() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        MAX_BINS_PER_ADD as i32,
+    )
+    .await;
+
+    // One bin over the cap, past the position's upper bin
+    let mut instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+    instruction.data = cpi_example::instruction::DlmmAddLiquidityOneSide {
+        amount: 1_000_000,
+        active_id: pool_state.active_id,
+        max_active_bin_slippage: 3,
+        bin_liquidity_dist: (0..=MAX_BINS_PER_ADD as i32)
+            .map(|offset| BinLiquidityDistributionByWeight {
+                bin_id: position.lower_bin_id + offset,
+                weight: 1,
+            })
+            .collect(),
+        min_net_amount: None,
+        max_weight_per_bin: None,
+    }
+    .data();
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::TooManyBins.into(),
+    )
+    .await;
+
+    // Exactly at the cap, one entry per bin of the position
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_one_side_max_weight_per_bin() {
+    let (
+        mut banks_client,
+        mock_user,
+        PoolSetupContext {
+            pool_state,
+            user_token_x,
+            ..
+        },
+    ) = setup().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let max_weight_per_bin = 200;
+    let instruction_with_weights = |weights: [u16; 10]| {
+        let mut instruction = add_liquidity_one_side_ix(
+            mock_user.pubkey(),
+            &pool_state,
+            &position,
+            user_token_x,
+            true,
+            1_000_000,
+        );
+        instruction.data = cpi_example::instruction::DlmmAddLiquidityOneSide {
+            amount: 1_000_000,
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 3,
+            bin_liquidity_dist: (position.lower_bin_id..)
+                .zip(weights)
+                .map(|(bin_id, weight)| BinLiquidityDistributionByWeight { bin_id, weight })
+                .collect(),
+            min_net_amount: None,
+            max_weight_per_bin: Some(max_weight_per_bin),
+        }
+        .data();
+        instruction
+    };
+
+    // Over half of the deposit in the first bin, 1000 of a total weight of
+    // 1900
+    let mut concentrated = [100; 10];
+    concentrated[0] = 1_000;
+
+    process_and_assert_err(
+        &[instruction_with_weights(concentrated)],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::BinWeightTooHigh.into(),
+    )
+    .await;
+
+    // An even spread puts 10% of the deposit in each bin, at most the cap
+    let balance_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    process_and_assert_ok(
+        &[instruction_with_weights([max_weight_per_bin; 10])],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    assert_eq!(
+        balance_before - get_token_balance(&mut banks_client, user_token_x).await,
+        1_000_000
+    );
+}
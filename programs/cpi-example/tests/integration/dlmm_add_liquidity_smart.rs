@@ -0,0 +1,130 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{LiquidityParameterByStrategy, StrategyParameters, StrategyType};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{get_token_balance, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_add_liquidity_smart_fresh_range() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Only the active bin array is loaded into the test validator. Straddle the
+    // boundary between two bin arrays above it so both have to be created.
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let lower_bin_id = (active_bin_array_idx + 3) * 70 - 5;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        lower_bin_id,
+        10,
+    )
+    .await;
+
+    let bin_array_lower = position.bin_array_lower();
+    let bin_array_upper = position.bin_array_upper();
+    assert_ne!(bin_array_lower, bin_array_upper);
+    for bin_array in [bin_array_lower, bin_array_upper] {
+        assert!(banks_client.get_account(bin_array).await.unwrap().is_none());
+    }
+
+    // The whole range is above the active bin, so only token X is deposited
+    let ix_data = cpi_example::instruction::DlmmAddLiquiditySmart {
+        params: LiquidityParameterByStrategy {
+            amount_x: 1_000_000,
+            amount_y: 0,
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 3,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: position.lower_bin_id,
+                max_bin_id: position.upper_bin_id(),
+                strategy_type: StrategyType::SpotImBalanced,
+                parameteres: [0; 64],
+            },
+        },
+    }
+    .data();
+
+    let accounts = cpi_example::accounts::DlmmAddLiquiditySmart {
+        position: position.position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower,
+        bin_array_upper,
+        sender: mock_user.pubkey(),
+        payer: mock_user.pubkey(),
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    for (bin_array, index) in [
+        (bin_array_lower, active_bin_array_idx + 2),
+        (bin_array_upper, active_bin_array_idx + 3),
+    ] {
+        let account = banks_client.get_account(bin_array).await.unwrap().unwrap();
+        assert_eq!(account.owner, dlmm::ID);
+
+        let bin_array_state: dlmm::accounts::BinArray = read_zero_copy_account(&account.data);
+        assert_eq!(bin_array_state.index, i64::from(index));
+    }
+
+    let x_after = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_after = get_token_balance(&mut banks_client, user_token_y).await;
+    assert!(x_before - x_after > 0);
+    assert_eq!(y_before, y_after);
+}
@@ -0,0 +1,94 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::BinArray;
+use cpi_example::dlmm::types::{LiquidityParameterByStrategy, StrategyParameters, StrategyType};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    account::AccountSharedData, instruction::Instruction, signature::Keypair, signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn add_price_impact_ix(bin_array: Pubkey, active_id: i32, amount_x: u64) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddPriceImpact {
+            params: LiquidityParameterByStrategy {
+                amount_x,
+                amount_y: 0,
+                active_id,
+                max_active_bin_slippage: 0,
+                strategy_parameters: StrategyParameters {
+                    min_bin_id: active_id - 5,
+                    max_bin_id: active_id + 5,
+                    strategy_type: StrategyType::SpotImBalanced,
+                    parameteres: [0; 64],
+                },
+            },
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmAddPriceImpact {
+            lb_pair: USDC_USDT_POOL,
+            bin_array,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_add_price_impact_grows_with_size() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let mut context = test.start_with_context().await;
+
+    // Give the active bin an even mix of both tokens so the result doesn't
+    // depend on the cluster state
+    let active_id = pool_state.active_id;
+    let idx = bin_id_to_bin_array_index(active_id).unwrap();
+    let (bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, idx.into());
+
+    let mut account = context
+        .banks_client
+        .get_account(bin_array)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut state: BinArray = read_zero_copy_account(&account.data);
+    let bin = &mut state.bins[(active_id - idx * 70) as usize];
+    bin.amount_x = 1_000_000_000;
+    bin.amount_y = 1_000_000_000;
+    account.data[8..8 + std::mem::size_of::<BinArray>()]
+        .copy_from_slice(bytemuck::bytes_of(&state));
+    context.set_account(&bin_array, &AccountSharedData::from(account));
+
+    let mut impact_bps = Vec::new();
+    for amount_x in [1_000_000, 1_000_000_000_000] {
+        let return_data = simulate_and_get_return_data(
+            &[add_price_impact_ix(bin_array, active_id, amount_x)],
+            &mock_user,
+            &[],
+            &mut context.banks_client,
+        )
+        .await;
+        impact_bps.push(u64::from_le_bytes(return_data.try_into().unwrap()));
+    }
+
+    // 1 token X into a 2_000 token bin barely moves it, 1M tokens make it
+    // almost entirely token X
+    let (small, large) = (impact_bps[0], impact_bps[1]);
+    assert!(small < 10);
+    assert!(large > 4_000);
+    assert!(small < large);
+}
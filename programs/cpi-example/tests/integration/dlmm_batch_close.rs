@@ -0,0 +1,181 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm_batch_close::MAX_BATCH_CLOSE_POSITIONS;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn batch_close_ix(
+    sender: Pubkey,
+    rent_receiver: Option<Pubkey>,
+    count: u8,
+    positions: &[&PositionContext],
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmBatchClose {
+        lb_pair: USDC_USDT_POOL,
+        sender,
+        rent_receiver,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+    }
+    .to_account_metas(None);
+
+    for position in positions {
+        accounts.extend([
+            AccountMeta::new(position.position, false),
+            AccountMeta::new(position.bin_array_lower(), false),
+            AccountMeta::new(position.bin_array_upper(), false),
+        ]);
+    }
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmBatchClose { count }.data(),
+        accounts,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_batch_close() {
+    let mock_user = Keypair::new();
+    let vault = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let mut positions = vec![];
+    for width in [1, 5, 10] {
+        positions.push(
+            initialize_position(
+                &mut banks_client,
+                &mock_user,
+                USDC_USDT_POOL,
+                pool_state.active_id + 1,
+                width,
+            )
+            .await,
+        );
+    }
+
+    // The last position holds liquidity, the first two are empty
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &positions[2],
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    let mut rent = 0;
+    for position in &positions[..2] {
+        let account = banks_client.get_account(position.position).await.unwrap();
+        rent += account.unwrap().lamports;
+    }
+
+    process_and_assert_ok(
+        &[batch_close_ix(
+            mock_user.pubkey(),
+            Some(vault),
+            3,
+            &[&positions[0], &positions[1], &positions[2]],
+        )],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    // Both empty positions are closed with their rent sent to the vault, the
+    // one with liquidity is skipped
+    for position in &positions[..2] {
+        assert!(banks_client
+            .get_account(position.position)
+            .await
+            .unwrap()
+            .is_none());
+    }
+    let vault_account = banks_client.get_account(vault).await.unwrap().unwrap();
+    assert_eq!(vault_account.lamports, rent);
+
+    let remaining = banks_client
+        .get_account(positions[2].position)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(remaining.owner, dlmm::ID);
+}
+
+#[tokio::test]
+async fn test_dlmm_batch_close_invalid_count() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id,
+        1,
+    )
+    .await;
+
+    // Counts outside the cap, and a count that doesn't match the groups
+    for (count, error) in [
+        (0, DlmmCpiError::InvalidBatchSize),
+        (
+            MAX_BATCH_CLOSE_POSITIONS + 1,
+            DlmmCpiError::InvalidBatchSize,
+        ),
+        (2, DlmmCpiError::InvalidRemainingAccounts),
+    ] {
+        process_and_assert_err(
+            &[batch_close_ix(
+                mock_user.pubkey(),
+                None,
+                count,
+                &[&position],
+            )],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+            error.into(),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,92 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_batch_read_positions::PositionSummary;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_batch_read_three_positions() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let mut positions = vec![];
+    for (lower_offset, width) in [(1, 5), (6, 10), (16, 20)] {
+        positions.push(
+            initialize_position(
+                &mut banks_client,
+                &mock_user,
+                USDC_USDT_POOL,
+                pool_state.active_id + lower_offset,
+                width,
+            )
+            .await,
+        );
+    }
+
+    // Only the first position holds liquidity
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &positions[0],
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    let mut accounts = cpi_example::accounts::DlmmBatchReadPositions {}.to_account_metas(None);
+    accounts.extend(
+        positions
+            .iter()
+            .map(|position| AccountMeta::new_readonly(position.position, false)),
+    );
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmBatchReadPositions { count: 3 }.data(),
+        accounts,
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let summaries = Vec::<PositionSummary>::try_from_slice(&return_data).unwrap();
+
+    assert_eq!(summaries.len(), 3);
+    for (summary, position) in summaries.iter().zip(&positions) {
+        assert_eq!(summary.owner, mock_user.pubkey());
+        assert_eq!(summary.lower_bin_id, position.lower_bin_id);
+        assert_eq!(summary.upper_bin_id, position.upper_bin_id());
+    }
+
+    assert!(summaries[0].total_shares > 0);
+    assert_eq!(summaries[1].total_shares, 0);
+    assert_eq!(summaries[2].total_shares, 0);
+}
@@ -0,0 +1,194 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn batch_remove_liquidity_ix(
+    owner: Pubkey,
+    pool_state: &dlmm::accounts::LbPair,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmBatchRemoveLiquidity {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        sender: owner,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(remaining_accounts);
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmBatchRemoveLiquidity {}.data(),
+        accounts,
+    }
+}
+
+fn position_group(position: &PositionContext) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(position.position, false),
+        AccountMeta::new(position.bin_array_lower(), false),
+        AccountMeta::new(position.bin_array_upper(), false),
+    ]
+}
+
+#[tokio::test]
+async fn test_dlmm_batch_remove_liquidity_two_positions() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Two side by side positions above the active bin, both holding token X
+    let mut positions = vec![];
+    for lower_bin_id in [pool_state.active_id + 1, pool_state.active_id + 11] {
+        let position = initialize_position(
+            &mut banks_client,
+            &mock_user,
+            USDC_USDT_POOL,
+            lower_bin_id,
+            10,
+        )
+        .await;
+
+        add_liquidity_one_side(
+            &mut banks_client,
+            &mock_user,
+            &pool_state,
+            &position,
+            user_token_x,
+            true,
+            1_000_000,
+        )
+        .await;
+
+        positions.push(position);
+    }
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    let instruction = batch_remove_liquidity_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        user_token_x,
+        user_token_y,
+        positions.iter().flat_map(position_group).collect(),
+    );
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    // Both deposits come back, less rounding
+    let received = get_token_balance(&mut banks_client, user_token_x).await - x_before;
+    assert!(received <= 2_000_000);
+    assert!(received >= 1_999_980);
+
+    for position in positions {
+        let position_account = banks_client
+            .get_account(position.position)
+            .await
+            .unwrap()
+            .unwrap();
+        let position_state: dlmm::accounts::PositionV2 =
+            read_zero_copy_account(&position_account.data);
+        assert!(position_state
+            .liquidity_shares
+            .iter()
+            .all(|share| *share == 0));
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_batch_remove_liquidity_incomplete_group() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    // The upper bin array of the group is missing
+    let mut remaining_accounts = position_group(&position);
+    remaining_accounts.pop();
+
+    let instruction = batch_remove_liquidity_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        user_token_x,
+        user_token_y,
+        remaining_accounts,
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidRemainingAccounts.into(),
+    )
+    .await;
+}
@@ -0,0 +1,259 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn bootstrap_position_ix(
+    owner: Pubkey,
+    position: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+    bin_array_indices: &[i64],
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmBootstrapPosition {
+        payer: owner,
+        position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        owner,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(
+        bin_array_indices
+            .iter()
+            .map(|index| AccountMeta::new(derive_bin_array_pda(USDC_USDT_POOL, *index).0, false)),
+    );
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmBootstrapPosition {
+            lower_bin_id,
+            width,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+async fn assert_position(
+    banks_client: &mut BanksClient,
+    position: Pubkey,
+    owner: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) {
+    let account = banks_client.get_account(position).await.unwrap().unwrap();
+    let state: dlmm::accounts::PositionV2 = read_zero_copy_account(&account.data);
+    assert_eq!(state.lb_pair, USDC_USDT_POOL);
+    assert_eq!(state.owner, owner);
+    assert_eq!(state.lower_bin_id, lower_bin_id);
+    assert_eq!(state.upper_bin_id, lower_bin_id + width - 1);
+}
+
+#[tokio::test]
+async fn test_dlmm_bootstrap_position_cold_start() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // The widest position, straddling two bin arrays above the active one
+    // that are not loaded into the validator
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let lower_bin_id = (active_bin_array_idx + 3) * 70 - 35;
+    let width = dlmm::constants::MAX_BIN_PER_POSITION as i32;
+    let indices = [
+        i64::from(active_bin_array_idx + 2),
+        i64::from(active_bin_array_idx + 3),
+    ];
+
+    for index in indices {
+        let bin_array = derive_bin_array_pda(USDC_USDT_POOL, index).0;
+        assert!(banks_client.get_account(bin_array).await.unwrap().is_none());
+    }
+
+    let instruction = bootstrap_position_ix(
+        mock_user.pubkey(),
+        position.pubkey(),
+        lower_bin_id,
+        width,
+        &indices,
+    );
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction.clone(),
+        ],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+
+    for index in indices {
+        let bin_array = derive_bin_array_pda(USDC_USDT_POOL, index).0;
+        let account = banks_client.get_account(bin_array).await.unwrap().unwrap();
+        assert_eq!(account.owner, dlmm::ID);
+
+        let bin_array_state: dlmm::accounts::BinArray = read_zero_copy_account(&account.data);
+        assert_eq!(bin_array_state.index, index);
+    }
+
+    assert_position(
+        &mut banks_client,
+        position.pubkey(),
+        mock_user.pubkey(),
+        lower_bin_id,
+        width,
+    )
+    .await;
+
+    // Retrying once everything exists is a no-op. The compute budget
+    // instruction keeps the transaction distinct from the first one.
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_000_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_bootstrap_position_warm_start() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The active bin array is loaded from the cluster, load the one above
+    // it too
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Straddle the boundary between the two existing bin arrays
+    let lower_bin_id = (active_bin_array_idx + 1) * 70 - 10;
+    let width = 20;
+    let indices =
+        cpi_example::utils::required_bin_array_indices(lower_bin_id, lower_bin_id + width - 1);
+
+    let mut bin_arrays_before = vec![];
+    for index in &indices {
+        let bin_array = derive_bin_array_pda(USDC_USDT_POOL, *index).0;
+        bin_arrays_before.push(banks_client.get_account(bin_array).await.unwrap().unwrap());
+    }
+
+    process_and_assert_ok(
+        &[bootstrap_position_ix(
+            mock_user.pubkey(),
+            position.pubkey(),
+            lower_bin_id,
+            width,
+            &indices,
+        )],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+
+    // The existing bin arrays are left as they were
+    for (index, before) in indices.iter().zip(bin_arrays_before) {
+        let bin_array = derive_bin_array_pda(USDC_USDT_POOL, *index).0;
+        let after = banks_client.get_account(bin_array).await.unwrap().unwrap();
+        assert_eq!(after, before);
+    }
+
+    assert_position(
+        &mut banks_client,
+        position.pubkey(),
+        mock_user.pubkey(),
+        lower_bin_id,
+        width,
+    )
+    .await;
+
+    // The position exists now, for this range only
+    process_and_assert_err(
+        &[bootstrap_position_ix(
+            mock_user.pubkey(),
+            position.pubkey(),
+            lower_bin_id,
+            width - 1,
+            &indices,
+        )],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+        DlmmCpiError::PositionRangeMismatch.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_bootstrap_position_missing_bitmap_extension() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Bin array 512 is the first past the pool's built-in bitmap
+    let lower_bin_id = dlmm::constants::BIN_ARRAY_BITMAP_SIZE * 70;
+
+    process_and_assert_err(
+        &[bootstrap_position_ix(
+            mock_user.pubkey(),
+            position.pubkey(),
+            lower_bin_id,
+            10,
+            &[512],
+        )],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+        DlmmCpiError::InvalidBitmapExtension.into(),
+    )
+    .await;
+}
@@ -0,0 +1,122 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Accounts claiming reward 0 with the pool's own mint and vault, and no
+/// reward 1 set. The checks happen before the CPIs, so the position doesn't
+/// need to exist.
+fn claim_all_rewards_accounts(
+    sender: Pubkey,
+    pool_state: &LbPair,
+    user_token: Pubkey,
+) -> cpi_example::accounts::DlmmClaimAllRewards {
+    let (bin_array, _bump) = derive_bin_array_pda(
+        USDC_USDT_POOL,
+        bin_id_to_bin_array_index(pool_state.active_id)
+            .unwrap()
+            .into(),
+    );
+
+    cpi_example::accounts::DlmmClaimAllRewards {
+        lb_pair: USDC_USDT_POOL,
+        position: Pubkey::new_unique(),
+        bin_array_lower: bin_array,
+        bin_array_upper: bin_array,
+        sender,
+        reward_vault_0: pool_state.reward_infos[0].vault,
+        reward_mint_0: pool_state.reward_infos[0].mint,
+        user_token_account_0: user_token,
+        token_program_0: anchor_spl::token::ID,
+        reward_vault_1: None,
+        reward_mint_1: None,
+        user_token_account_1: None,
+        token_program_1: None,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+    }
+}
+
+async fn assert_claim_all_rewards_err(
+    accounts: impl FnOnce(Pubkey, &LbPair, Pubkey) -> cpi_example::accounts::DlmmClaimAllRewards,
+    expected_error: DlmmCpiError,
+) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClaimAllRewards {}.data(),
+        accounts: accounts(mock_user.pubkey(), &pool_state, user_token_x).to_account_metas(None),
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        expected_error.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_all_rewards_both_rewards_mismatched_reward_1_mint() {
+    // Reward 0 matches the pool, so the failure comes from checking reward 1
+    assert_claim_all_rewards_err(
+        |sender, pool_state, user_token| cpi_example::accounts::DlmmClaimAllRewards {
+            reward_vault_1: Some(pool_state.reward_infos[1].vault),
+            reward_mint_1: Some(Pubkey::new_unique()),
+            user_token_account_1: Some(user_token),
+            token_program_1: Some(anchor_spl::token::ID),
+            ..claim_all_rewards_accounts(sender, pool_state, user_token)
+        },
+        DlmmCpiError::RewardMintMismatch,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_all_rewards_single_reward_mismatched_reward_0_vault() {
+    // Without a reward 1 set only reward 0 is checked
+    assert_claim_all_rewards_err(
+        |sender, pool_state, user_token| cpi_example::accounts::DlmmClaimAllRewards {
+            reward_vault_0: Pubkey::new_unique(),
+            ..claim_all_rewards_accounts(sender, pool_state, user_token)
+        },
+        DlmmCpiError::RewardVaultMismatch,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_all_rewards_partial_reward_1_set() {
+    assert_claim_all_rewards_err(
+        |sender, pool_state, user_token| cpi_example::accounts::DlmmClaimAllRewards {
+            reward_mint_1: Some(pool_state.reward_infos[1].mint),
+            ..claim_all_rewards_accounts(sender, pool_state, user_token)
+        },
+        DlmmCpiError::IncompleteRewardAccounts,
+    )
+    .await;
+}
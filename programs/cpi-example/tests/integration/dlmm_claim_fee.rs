@@ -0,0 +1,256 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    add_packable_account, get_token_balance, process_and_assert_err, process_and_assert_ok,
+    setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[test]
+fn test_dlmm_claim_fee_accounts_match_dlmm_claim_fee() {
+    let lb_pair = Pubkey::new_unique();
+    let position = Pubkey::new_unique();
+    let bin_array_lower = Pubkey::new_unique();
+    let bin_array_upper = Pubkey::new_unique();
+    let sender = Pubkey::new_unique();
+    let reserve_x = Pubkey::new_unique();
+    let reserve_y = Pubkey::new_unique();
+    let user_token_x = Pubkey::new_unique();
+    let user_token_y = Pubkey::new_unique();
+    let token_x_mint = Pubkey::new_unique();
+    let token_y_mint = Pubkey::new_unique();
+    let event_authority = Pubkey::new_unique();
+
+    let accounts = cpi_example::accounts::DlmmClaimFee {
+        lb_pair,
+        position,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        reserve_x,
+        reserve_y,
+        user_token_x,
+        user_token_y,
+        token_x_mint,
+        token_y_mint,
+        token_program: anchor_spl::token::ID,
+        event_authority,
+        dlmm_program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let dlmm_accounts = dlmm::client::accounts::ClaimFee {
+        lb_pair,
+        position,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        reserve_x,
+        reserve_y,
+        user_token_x,
+        user_token_y,
+        token_x_mint,
+        token_y_mint,
+        token_program: anchor_spl::token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    assert_eq!(accounts, dlmm_accounts);
+}
+
+struct TreasuryClaimContext {
+    context: ProgramTestContext,
+    mock_user: Keypair,
+    pool_setup: PoolSetupContext,
+    position: PositionContext,
+    treasury_x: Pubkey,
+    treasury_y: Pubkey,
+}
+
+/// A position with fees to claim in both tokens, and empty treasury token
+/// accounts of both mints owned by a different wallet
+async fn setup_treasury_claim() -> TreasuryClaimContext {
+    let mock_user = Keypair::new();
+    let treasury = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+    let pool_state = &pool_setup.pool_state;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let treasury_x = Pubkey::new_unique();
+    let treasury_y = Pubkey::new_unique();
+    for (token_account, mint) in [
+        (treasury_x, pool_state.token_x_mint),
+        (treasury_y, pool_state.token_y_mint),
+    ] {
+        let state = TokenAccount {
+            mint,
+            owner: treasury,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+
+        add_packable_account(&mut test, state, anchor_spl::token::ID, token_account);
+    }
+
+    let mut context = test.start_with_context().await;
+
+    let position = initialize_position(
+        &mut context.banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut context.banks_client,
+        &mock_user,
+        pool_state,
+        &position,
+        pool_setup.user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    // One token X and one token Y of fees per unit of liquidity in one bin
+    let one = 1u128 << 64;
+    add_bin_fee_growth(
+        &mut context,
+        USDC_USDT_POOL,
+        position.lower_bin_id + 2,
+        one,
+        one,
+    )
+    .await;
+
+    TreasuryClaimContext {
+        context,
+        mock_user,
+        pool_setup,
+        position,
+        treasury_x,
+        treasury_y,
+    }
+}
+
+fn claim_fee_ix(
+    sender: Pubkey,
+    pool_setup: &PoolSetupContext,
+    position: &PositionContext,
+    fee_token_x: Pubkey,
+    fee_token_y: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClaimFee {}.data(),
+        accounts: cpi_example::accounts::DlmmClaimFee {
+            lb_pair: USDC_USDT_POOL,
+            position: position.position,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender,
+            reserve_x: pool_setup.pool_state.reserve_x,
+            reserve_y: pool_setup.pool_state.reserve_y,
+            user_token_x: fee_token_x,
+            user_token_y: fee_token_y,
+            token_x_mint: pool_setup.pool_state.token_x_mint,
+            token_y_mint: pool_setup.pool_state.token_y_mint,
+            token_program: anchor_spl::token::ID,
+            event_authority: derive_event_authority_pda().0,
+            dlmm_program: dlmm::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_fee_to_treasury() {
+    let TreasuryClaimContext {
+        mut context,
+        mock_user,
+        pool_setup,
+        position,
+        treasury_x,
+        treasury_y,
+    } = setup_treasury_claim().await;
+
+    let banks_client = &mut context.banks_client;
+
+    let user_x_before = get_token_balance(banks_client, pool_setup.user_token_x).await;
+    let user_y_before = get_token_balance(banks_client, pool_setup.user_token_y).await;
+
+    process_and_assert_ok(
+        &[claim_fee_ix(
+            mock_user.pubkey(),
+            &pool_setup,
+            &position,
+            treasury_x,
+            treasury_y,
+        )],
+        &mock_user,
+        &[],
+        banks_client,
+    )
+    .await;
+
+    // The fees went to the treasury, not to the position owner
+    assert!(get_token_balance(banks_client, treasury_x).await > 0);
+    assert!(get_token_balance(banks_client, treasury_y).await > 0);
+    assert_eq!(
+        get_token_balance(banks_client, pool_setup.user_token_x).await,
+        user_x_before
+    );
+    assert_eq!(
+        get_token_balance(banks_client, pool_setup.user_token_y).await,
+        user_y_before
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_fee_swapped_fee_accounts() {
+    let TreasuryClaimContext {
+        mut context,
+        mock_user,
+        pool_setup,
+        position,
+        treasury_x,
+        treasury_y,
+    } = setup_treasury_claim().await;
+
+    // The token Y treasury account can't receive the token X fee
+    process_and_assert_err(
+        &[claim_fee_ix(
+            mock_user.pubkey(),
+            &pool_setup,
+            &position,
+            treasury_y,
+            treasury_x,
+        )],
+        &mock_user,
+        &[],
+        &mut context.banks_client,
+        DlmmCpiError::UserTokenMintMismatch.into(),
+    )
+    .await;
+}
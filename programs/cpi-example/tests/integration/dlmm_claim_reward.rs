@@ -0,0 +1,89 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Sends a claim_reward for `reward_index` with a wrong reward mint and expects `expected_error`
+async fn assert_claim_reward_err(reward_index: u64, expected_error: DlmmCpiError) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let reward_vault = pool_state
+        .reward_infos
+        .get(reward_index as usize)
+        .map_or(Pubkey::new_unique(), |reward_info| reward_info.vault);
+
+    let (bin_array, _bump) = derive_bin_array_pda(
+        USDC_USDT_POOL,
+        bin_id_to_bin_array_index(pool_state.active_id)
+            .unwrap()
+            .into(),
+    );
+
+    let ix_data = cpi_example::instruction::DlmmClaimReward { reward_index }.data();
+
+    // The checks happen before the CPI, so the position doesn't need to exist
+    let accounts = cpi_example::accounts::DlmmClaimReward {
+        lb_pair: USDC_USDT_POOL,
+        position: Pubkey::new_unique(),
+        bin_array_lower: bin_array,
+        bin_array_upper: bin_array,
+        sender: mock_user.pubkey(),
+        reward_vault,
+        reward_mint: Pubkey::new_unique(),
+        user_token_account: user_token_x,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        expected_error.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_reward_index_0_mismatched_reward_mint() {
+    assert_claim_reward_err(0, DlmmCpiError::RewardMintMismatch).await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_reward_index_1_mismatched_reward_mint() {
+    assert_claim_reward_err(1, DlmmCpiError::RewardMintMismatch).await;
+}
+
+#[tokio::test]
+async fn test_dlmm_claim_reward_index_out_of_range() {
+    assert_claim_reward_err(2, DlmmCpiError::InvalidRewardIndex).await;
+}
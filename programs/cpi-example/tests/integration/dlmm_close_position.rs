@@ -0,0 +1,163 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn close_position_ix(
+    position: &PositionContext,
+    sender: Pubkey,
+    rent_receiver: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClosePosition {}.data(),
+        accounts: cpi_example::accounts::DlmmClosePosition {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender,
+            rent_receiver,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Closes an empty position with `rent_receiver`, paying the transaction fee
+/// from a separate payer, and returns the rent reclaimed and the lamports
+/// gained by the owner and by `rent_receiver`
+async fn close_empty_position(rent_receiver: Option<Pubkey>) -> (u64, u64, u64) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, payer, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id,
+        1,
+    )
+    .await;
+
+    let lamports = |account: Option<solana_sdk::account::Account>| {
+        account.map_or(0, |account| account.lamports)
+    };
+    let rent = lamports(banks_client.get_account(position.position).await.unwrap());
+    let owner_before = lamports(banks_client.get_account(mock_user.pubkey()).await.unwrap());
+    let receiver = rent_receiver.unwrap_or(mock_user.pubkey());
+    let receiver_before = lamports(banks_client.get_account(receiver).await.unwrap());
+
+    process_and_assert_ok(
+        &[close_position_ix(
+            &position,
+            mock_user.pubkey(),
+            rent_receiver,
+        )],
+        &payer,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let owner_after = lamports(banks_client.get_account(mock_user.pubkey()).await.unwrap());
+    let receiver_after = lamports(banks_client.get_account(receiver).await.unwrap());
+
+    (
+        rent,
+        owner_after - owner_before,
+        receiver_after - receiver_before,
+    )
+}
+
+#[tokio::test]
+async fn test_dlmm_close_position_rent_to_sender_by_default() {
+    let (rent, owner_gain, _) = close_empty_position(None).await;
+
+    assert!(rent > 0);
+    assert_eq!(owner_gain, rent);
+}
+
+#[tokio::test]
+async fn test_dlmm_close_position_rent_to_explicit_receiver() {
+    let vault = Pubkey::new_unique();
+
+    let (rent, owner_gain, vault_gain) = close_empty_position(Some(vault)).await;
+
+    assert!(rent > 0);
+    assert_eq!(owner_gain, 0);
+    assert_eq!(vault_gain, rent);
+}
+
+#[tokio::test]
+async fn test_dlmm_close_position_with_liquidity() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    // Leave liquidity in the position
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    process_and_assert_err(
+        &[close_position_ix(
+            &position,
+            mock_user.pubkey(),
+            Some(mock_user.pubkey()),
+        )],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::PositionNotEmpty.into(),
+    )
+    .await;
+}
@@ -0,0 +1,114 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{clock::Clock, instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+const ONE_DAY: u64 = 86_400;
+
+async fn setup_position() -> (ProgramTestContext, Keypair, PositionContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let mut context = test.start_with_context().await;
+
+    let position = initialize_position(
+        &mut context.banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut context.banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    (context, mock_user, position)
+}
+
+fn estimate_apr_ix(position: &PositionContext, lookback_seconds: u64) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmEstimateApr { lookback_seconds }.data(),
+        accounts: cpi_example::accounts::DlmmEstimateApr {
+            lb_pair: USDC_USDT_POOL,
+            position: position.position,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_estimate_apr() {
+    let (mut context, mock_user, position) = setup_position().await;
+
+    // Fees of 1% of the liquidity of every bin, earned over one day
+    let growth = (1u128 << 64) / 100;
+    for bin_id in position.lower_bin_id..=position.upper_bin_id() {
+        add_bin_fee_growth(&mut context, USDC_USDT_POOL, bin_id, growth, 0).await;
+    }
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += ONE_DAY as i64;
+    context.set_sysvar(&clock);
+
+    let return_data = simulate_and_get_return_data(
+        &[estimate_apr_ix(&position, ONE_DAY)],
+        &mock_user,
+        &[],
+        &mut context.banks_client,
+    )
+    .await;
+    let apr_bps = u64::from_le_bytes(return_data.try_into().unwrap());
+
+    // About 1% a day, 365% a year. The stable pair trades close to 1, so the
+    // fees in X and the deposit in X are valued at nearly the same price.
+    assert!(
+        (36_000..=37_000).contains(&apr_bps),
+        "unexpected APR {apr_bps} bps"
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_estimate_apr_window_too_short() {
+    let (mut context, mock_user, position) = setup_position().await;
+
+    process_and_assert_err(
+        &[estimate_apr_ix(&position, ONE_DAY)],
+        &mock_user,
+        &[&mock_user],
+        &mut context.banks_client,
+        DlmmCpiError::AprWindowTooShort.into(),
+    )
+    .await;
+}
@@ -0,0 +1,167 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityReduction;
+use cpi_example::events::{LiquidityAddedEvent, LiquidityRemovedEvent, PositionClosedEvent};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{decode_event, process_and_get_logs, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_liquidity_events() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    // 1. Add token X only, above the active bin
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    );
+
+    let logs = process_and_get_logs(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let event = decode_event::<LiquidityAddedEvent>(&logs).unwrap();
+
+    assert_eq!(event.position, position.position);
+    assert_eq!(event.lb_pair, USDC_USDT_POOL);
+    assert_eq!(event.sender, mock_user.pubkey());
+    assert_eq!(event.amount_x, 1_000_000);
+    assert_eq!(event.amount_y, 0);
+
+    // 2. Remove everything from the three lowest bins
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquidity {
+            bin_liquidity_removal: (position.lower_bin_id..position.lower_bin_id + 3)
+                .map(|bin_id| BinLiquidityReduction {
+                    bin_id,
+                    bps_to_remove: 10_000,
+                })
+                .collect(),
+            min_amount_x: 0,
+            min_amount_y: 0,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    let logs = process_and_get_logs(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let event = decode_event::<LiquidityRemovedEvent>(&logs).unwrap();
+
+    assert_eq!(event.position, position.position);
+    assert_eq!(event.bin_count, 3);
+    assert!(event.amount_x > 0);
+    let removed_x = event.amount_x;
+
+    // 3. Remove the rest
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveAllLiquidity {}.data(),
+        accounts: cpi_example::accounts::DlmmRemoveAllLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    let logs = process_and_get_logs(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let event = decode_event::<LiquidityRemovedEvent>(&logs).unwrap();
+
+    assert_eq!(event.bin_count, 10);
+    assert!(removed_x + event.amount_x <= 1_000_000);
+
+    // 4. Close the empty position
+    let rent = banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClosePosition {}.data(),
+        accounts: cpi_example::accounts::DlmmClosePosition {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            rent_receiver: None,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    };
+
+    let logs = process_and_get_logs(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let event = decode_event::<PositionClosedEvent>(&logs).unwrap();
+
+    assert_eq!(event.position, position.position);
+    assert_eq!(event.rent_receiver, mock_user.pubkey());
+    assert_eq!(event.rent_reclaimed, rent);
+}
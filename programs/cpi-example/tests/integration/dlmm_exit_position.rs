@@ -0,0 +1,206 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{get_token_balance, process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn exit_position_ix(
+    owner: Pubkey,
+    pool_state: &dlmm::accounts::LbPair,
+    position: &PositionContext,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmExitPosition {}.data(),
+        accounts: cpi_example::accounts::DlmmExitPosition {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner,
+            rent_receiver: owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_exit_position() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    let instruction = exit_position_ix(
+        mock_user.pubkey(),
+        &pool_state,
+        &position,
+        user_token_x,
+        user_token_y,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    // The three DLMM CPIs must run in exit order
+    let log_messages = result.metadata.unwrap().log_messages;
+    let log_index = |name: &str| {
+        let log = format!("Program log: Instruction: {name}");
+        log_messages
+            .iter()
+            .position(|message| *message == log)
+            .unwrap_or_else(|| panic!("missing {name} CPI"))
+    };
+
+    let remove_all_liquidity = log_index("RemoveAllLiquidity");
+    let claim_fee = log_index("ClaimFee");
+    let close_position = log_index("ClosePosition");
+
+    assert!(remove_all_liquidity < claim_fee);
+    assert!(claim_fee < close_position);
+
+    assert!(get_token_balance(&mut banks_client, user_token_x).await > x_before);
+    assert!(banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_dlmm_exit_position_insufficient_compute() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    // Enough for the account checks, not for remove_all_liquidity
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(30_000),
+            exit_position_ix(
+                mock_user.pubkey(),
+                &pool_state,
+                &position,
+                user_token_x,
+                user_token_y,
+            ),
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InsufficientCompute.into(),
+    )
+    .await;
+
+    // Nothing ran, the position is still open
+    assert!(banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .is_some());
+}
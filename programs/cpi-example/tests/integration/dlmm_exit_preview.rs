@@ -0,0 +1,99 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::PositionV2;
+use cpi_example::dlmm_exit_preview::ExitPreview;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_exit_preview() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let mut context = test.start_with_context().await;
+
+    let position = initialize_position(
+        &mut context.banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut context.banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    // Fees of one token X per unit of liquidity in one bin, two tokens Y in another
+    let one = 1u128 << 64;
+    let bin_x = position.lower_bin_id + 2;
+    let bin_y = position.lower_bin_id + 5;
+    add_bin_fee_growth(&mut context, USDC_USDT_POOL, bin_x, one, 0).await;
+    add_bin_fee_growth(&mut context, USDC_USDT_POOL, bin_y, 0, 2 * one).await;
+
+    let position_account = context
+        .banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_state: PositionV2 = read_zero_copy_account(&position_account.data);
+    let liquidity = |bin_id: i32| {
+        let share = position_state.liquidity_shares[(bin_id - position.lower_bin_id) as usize];
+        u64::try_from(share >> 64).unwrap()
+    };
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmExitPreview {}.data(),
+        accounts: cpi_example::accounts::DlmmExitPreview {
+            lb_pair: USDC_USDT_POOL,
+            position: position.position,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+        }
+        .to_account_metas(None),
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut context.banks_client)
+            .await;
+    let preview = ExitPreview::try_from_slice(&return_data).unwrap();
+
+    // The deposit comes back less rounding, all in token X as the bins sit
+    // above the active bin
+    assert!(preview.amount_x <= 1_000_000);
+    assert!(preview.amount_x >= 999_990);
+    assert_eq!(preview.amount_y, 0);
+    assert_eq!(preview.fee_x, liquidity(bin_x));
+    assert_eq!(preview.fee_y, 2 * liquidity(bin_y));
+}
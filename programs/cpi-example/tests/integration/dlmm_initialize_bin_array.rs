@@ -0,0 +1,86 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer, system_program};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn initialize_bin_array_ix(funder: Pubkey, bin_array: Pubkey, index: i64) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializeBinArray { index }.data(),
+        accounts: cpi_example::accounts::DlmmInitializeBinArray {
+            lb_pair: USDC_USDT_POOL,
+            bin_array,
+            funder,
+            system_program: system_program::ID,
+            dlmm_program: dlmm::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_bin_array_negative_index() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Bins -700 to -631, below the USDC/USDT price of about 1
+    let index = -10;
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    assert!(index < i64::from(active_bin_array_idx));
+
+    let (bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, index);
+    assert!(banks_client.get_account(bin_array).await.unwrap().is_none());
+
+    let instruction = initialize_bin_array_ix(mock_user.pubkey(), bin_array, index);
+
+    process_and_assert_ok(&[instruction], &mock_user, &[], &mut banks_client).await;
+
+    let account = banks_client.get_account(bin_array).await.unwrap().unwrap();
+    assert_eq!(account.owner, dlmm::ID);
+
+    let bin_array_state: dlmm::accounts::BinArray = read_zero_copy_account(&account.data);
+    assert_eq!(bin_array_state.index, index);
+    assert_eq!(bin_array_state.lb_pair, USDC_USDT_POOL);
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_bin_array_wrong_pda() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let (bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, -11);
+    let instruction = initialize_bin_array_ix(mock_user.pubkey(), bin_array, -10);
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::InvalidBinArrayPda.into(),
+    )
+    .await;
+}
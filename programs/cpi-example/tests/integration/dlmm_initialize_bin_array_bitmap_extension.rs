@@ -0,0 +1,58 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, Discriminator, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use helpers::dlmm_pda::*;
+use solana_sdk::{instruction::Instruction, system_program, sysvar};
+
+#[test]
+fn test_dlmm_initialize_bin_array_bitmap_extension_instruction() {
+    let lb_pair = Pubkey::new_unique();
+    let funder = Pubkey::new_unique();
+    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializeBinArrayBitmapExtension {}.data(),
+        accounts: cpi_example::accounts::DlmmInitializeBinArrayBitmapExtension {
+            lb_pair,
+            bin_array_bitmap_extension,
+            funder,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    // No arguments, only the discriminator
+    assert_eq!(
+        instruction.data,
+        cpi_example::instruction::DlmmInitializeBinArrayBitmapExtension::DISCRIMINATOR
+    );
+
+    // (pubkey, is_signer, is_writable)
+    let expected = [
+        (lb_pair, false, false),
+        (bin_array_bitmap_extension, false, true),
+        (funder, true, true),
+        (system_program::ID, false, false),
+        (sysvar::rent::ID, false, false),
+        (dlmm::ID, false, false),
+    ];
+
+    assert_eq!(instruction.accounts.len(), expected.len());
+    for (meta, (pubkey, is_signer, is_writable)) in instruction.accounts.iter().zip(expected) {
+        assert_eq!(meta.pubkey, pubkey);
+        assert_eq!(meta.is_signer, is_signer);
+        assert_eq!(meta.is_writable, is_writable);
+    }
+
+    // The extension is the DLMM PDA of the pool, which the program checks
+    let (expected_extension, _bump) =
+        Pubkey::find_program_address(&[b"bitmap".as_ref(), lb_pair.as_ref()], &dlmm::ID);
+    assert_eq!(bin_array_bitmap_extension, expected_extension);
+    assert_eq!(
+        cpi_example::utils::derive_bin_array_bitmap_extension(lb_pair).0,
+        expected_extension
+    );
+}
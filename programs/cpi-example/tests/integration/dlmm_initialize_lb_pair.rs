@@ -0,0 +1,119 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, AnchorSerialize, Discriminator,
+    InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::CustomizableParams;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{account::Account, instruction::Instruction, signature::Keypair, signer::Signer};
+
+fn initialize_lb_pair_ix(funder: Pubkey, params: CustomizableParams) -> Instruction {
+    let token_mint_x = Pubkey::new_unique();
+    let token_mint_y = Pubkey::new_unique();
+    let (lb_pair, _bump) = derive_customizable_permissionless_lb_pair(token_mint_x, token_mint_y);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializeLbPair { params }.data(),
+        accounts: cpi_example::accounts::DlmmInitializeLbPair {
+            lb_pair,
+            bin_array_bitmap_extension: None,
+            token_mint_x,
+            token_mint_y,
+            reserve_x: derive_reserve_pda(token_mint_x, lb_pair).0,
+            reserve_y: derive_reserve_pda(token_mint_y, lb_pair).0,
+            oracle: derive_oracle_pda(lb_pair).0,
+            user_token_x: Pubkey::new_unique(),
+            funder,
+            token_program: anchor_spl::token::ID,
+            system_program: solana_sdk::system_program::ID,
+            rent: solana_sdk::sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority,
+        }
+        .to_account_metas(None),
+    }
+}
+
+fn customizable_params(bin_step: u16, base_factor: u16) -> CustomizableParams {
+    CustomizableParams {
+        active_id: -1_234,
+        bin_step,
+        base_factor,
+        activation_type: 1,
+        has_alpha_vault: true,
+        activation_point: Some(1_800_000_000),
+        padding: [0; 64],
+    }
+}
+
+#[test]
+fn test_dlmm_initialize_lb_pair_serializes_params() {
+    let params = customizable_params(25, 10_000);
+    let expected = params.try_to_vec().unwrap();
+
+    let instruction = initialize_lb_pair_ix(Pubkey::new_unique(), params);
+
+    let discriminator = cpi_example::instruction::DlmmInitializeLbPair::DISCRIMINATOR;
+    assert_eq!(&instruction.data[..discriminator.len()], discriminator);
+
+    // Parameters reach the program unchanged
+    let decoded = cpi_example::instruction::DlmmInitializeLbPair::try_from_slice(
+        &instruction.data[discriminator.len()..],
+    )
+    .unwrap();
+    assert_eq!(decoded.params.try_to_vec().unwrap(), expected);
+    assert_eq!(decoded.params.active_id, -1_234);
+    assert_eq!(decoded.params.bin_step, 25);
+    assert_eq!(decoded.params.base_factor, 10_000);
+    assert_eq!(decoded.params.activation_point, Some(1_800_000_000));
+    assert!(decoded.params.has_alpha_vault);
+
+    // The bitmap extension is passed as the program ID when omitted
+    assert_eq!(instruction.accounts.len(), 15);
+    assert_eq!(instruction.accounts[1].pubkey, cpi_example::id());
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_lb_pair_invalid_bin_step() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+    test.add_account(
+        mock_user.pubkey(),
+        Account {
+            lamports: u32::MAX.into(),
+            data: vec![],
+            owner: solana_sdk::system_program::ID,
+            ..Default::default()
+        },
+    );
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // (bin_step, base_factor): no bin step, above MAX_BIN_STEP, a base fee
+    // below the minimum and one above the maximum
+    let cases = [(0, 10_000), (401, 250), (1, 1_000), (400, 30_000)];
+
+    for (bin_step, base_factor) in cases {
+        process_and_assert_err(
+            &[initialize_lb_pair_ix(
+                mock_user.pubkey(),
+                customizable_params(bin_step, base_factor),
+            )],
+            &mock_user,
+            &[],
+            &mut banks_client,
+            DlmmCpiError::InvalidBinStep.into(),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,130 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_initialize_position::POSITION_V2_SPACE;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn initialize_position_ix(
+    owner: Pubkey,
+    position: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializePosition {
+            lower_bin_id,
+            width,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmInitializePosition {
+            payer: owner,
+            position,
+            lb_pair: USDC_USDT_POOL,
+            owner,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[test]
+fn test_dlmm_initialize_position_negative_lower_bin_id_data() {
+    let instruction =
+        initialize_position_ix(Pubkey::new_unique(), Pubkey::new_unique(), -443_636, 70);
+
+    let discriminator = cpi_example::instruction::DlmmInitializePosition::DISCRIMINATOR;
+    assert_eq!(&instruction.data[..discriminator.len()], discriminator);
+
+    let decoded = cpi_example::instruction::DlmmInitializePosition::try_from_slice(
+        &instruction.data[discriminator.len()..],
+    )
+    .unwrap();
+    assert_eq!(decoded.lower_bin_id, -443_636);
+    assert_eq!(decoded.width, 70);
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_position_width_too_large() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let instruction = initialize_position_ix(
+        mock_user.pubkey(),
+        position.pubkey(),
+        pool_state.active_id,
+        71,
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+        DlmmCpiError::InvalidPositionWidth.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_position_creates_v2_layout() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let instruction = initialize_position_ix(
+        mock_user.pubkey(),
+        position.pubkey(),
+        pool_state.active_id,
+        70,
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&position], &mut banks_client).await;
+
+    let position_account = banks_client
+        .get_account(position.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(position_account.owner, dlmm::ID);
+    assert_eq!(position_account.data.len(), POSITION_V2_SPACE);
+    assert_eq!(
+        &position_account.data[..dlmm::accounts::PositionV2::DISCRIMINATOR.len()],
+        dlmm::accounts::PositionV2::DISCRIMINATOR
+    );
+}
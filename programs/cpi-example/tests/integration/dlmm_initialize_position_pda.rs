@@ -0,0 +1,124 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn derive_position_owner_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position_owner"], &cpi_example::ID)
+}
+
+fn initialize_position_pda_ix(
+    payer: Pubkey,
+    base: Pubkey,
+    position: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializePositionPda {
+            lower_bin_id,
+            width,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmInitializePositionPda {
+            payer,
+            base,
+            position,
+            lb_pair: USDC_USDT_POOL,
+            position_owner: derive_position_owner_pda().0,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_position_pda_program_owned() {
+    let mock_user = Keypair::new();
+    let base = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let lower_bin_id = pool_state.active_id - 5;
+    let width = 10;
+
+    let (position, _bump) = derive_position_pda(USDC_USDT_POOL, base.pubkey(), lower_bin_id, width);
+
+    let instruction = initialize_position_pda_ix(
+        mock_user.pubkey(),
+        base.pubkey(),
+        position,
+        lower_bin_id,
+        width,
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&base], &mut banks_client).await;
+
+    let position_account = banks_client.get_account(position).await.unwrap().unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+
+    assert_eq!(position_state.owner, derive_position_owner_pda().0);
+    assert_eq!(position_state.lower_bin_id, lower_bin_id);
+    assert_eq!(position_state.upper_bin_id, lower_bin_id + width - 1);
+}
+
+#[tokio::test]
+async fn test_dlmm_initialize_position_pda_mismatched_position() {
+    let mock_user = Keypair::new();
+    let base = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let lower_bin_id = pool_state.active_id - 5;
+    let width = 10;
+
+    // Derived with a different width than the one passed to the instruction
+    let (position, _bump) =
+        derive_position_pda(USDC_USDT_POOL, base.pubkey(), lower_bin_id, width + 1);
+
+    let instruction = initialize_position_pda_ix(
+        mock_user.pubkey(),
+        base.pubkey(),
+        position,
+        lower_bin_id,
+        width,
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&base],
+        &mut banks_client,
+        DlmmCpiError::InvalidPositionPda.into(),
+    )
+    .await;
+}
@@ -0,0 +1,87 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_initialize_position_pda_idempotent() {
+    let mock_user = Keypair::new();
+    let base = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let lower_bin_id = pool_state.active_id - 5;
+    let width = 10;
+
+    let (position, _bump) = derive_position_pda(USDC_USDT_POOL, base.pubkey(), lower_bin_id, width);
+
+    let ix_data = cpi_example::instruction::DlmmInitializePositionPdaIdempotent {
+        lower_bin_id,
+        width,
+        base: base.pubkey(),
+    }
+    .data();
+
+    let accounts = cpi_example::accounts::DlmmInitializePositionPdaIdempotent {
+        payer: mock_user.pubkey(),
+        base: base.pubkey(),
+        position,
+        lb_pair: USDC_USDT_POOL,
+        owner: mock_user.pubkey(),
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    // 1. Create the position
+    process_and_assert_ok(
+        &[instruction.clone()],
+        &mock_user,
+        &[&mock_user, &base],
+        &mut banks_client,
+    )
+    .await;
+
+    // 2. Retry. The compute budget instruction only makes the transaction signature unique.
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user, &base],
+        &mut banks_client,
+    )
+    .await;
+
+    let position_account = banks_client.get_account(position).await.unwrap().unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+
+    assert_eq!(position_state.lower_bin_id, lower_bin_id);
+    assert_eq!(position_state.upper_bin_id, lower_bin_id + width - 1);
+}
@@ -0,0 +1,61 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    account::AccountSharedData, clock::Clock, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_last_activity_recent_swap() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let mut context = test.start_with_context().await;
+
+    // Pretend the pool was last swapped against 2 minutes ago
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let last_swap = clock.unix_timestamp - 120;
+
+    let mut pool_account = context
+        .banks_client
+        .get_account(USDC_USDT_POOL)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut pool_state: LbPair = read_zero_copy_account(&pool_account.data);
+    pool_state.v_parameters.last_update_timestamp = last_swap;
+    pool_account.data[8..8 + std::mem::size_of::<LbPair>()]
+        .copy_from_slice(bytemuck::bytes_of(&pool_state));
+    context.set_account(&USDC_USDT_POOL, &AccountSharedData::from(pool_account));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmLastActivity {}.data(),
+        accounts: cpi_example::accounts::DlmmLastActivity {
+            lb_pair: USDC_USDT_POOL,
+        }
+        .to_account_metas(None),
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut context.banks_client)
+            .await;
+    let elapsed = u64::from_le_bytes(return_data.try_into().unwrap());
+
+    // The clock may have moved on a little since it was read
+    assert!(elapsed >= 120);
+    assert!(elapsed < 180);
+}
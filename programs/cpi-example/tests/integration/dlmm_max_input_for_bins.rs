@@ -0,0 +1,106 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, Discriminator, InstructionData, ToAccountMetas};
+use bytemuck::Zeroable;
+use cpi_example::dlmm;
+use cpi_example::utils::ONE_Q64;
+use helpers::dlmm_pda::derive_bin_array_pda;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+fn dlmm_account_fixture(discriminator: &[u8], state: &[u8]) -> Account {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(state);
+
+    Account {
+        lamports: u32::MAX.into(),
+        data,
+        owner: dlmm::ID,
+        ..Default::default()
+    }
+}
+
+fn max_input_for_bins_ix(lb_pair: Pubkey, bin_array: Pubkey, max_bins: u16) -> Instruction {
+    let mut accounts =
+        cpi_example::accounts::DlmmMaxInputForBins { lb_pair }.to_account_metas(None);
+    accounts.push(AccountMeta::new_readonly(bin_array, false));
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmMaxInputForBins {
+            max_bins,
+            swap_for_y: true,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_max_input_for_bins() {
+    let mock_user = Keypair::new();
+    let lb_pair = Pubkey::new_unique();
+    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, 0);
+
+    // 0.1% base fee, active bin 10
+    let mut pool_state = dlmm::accounts::LbPair::zeroed();
+    pool_state.bin_step = 10;
+    pool_state.parameters.base_factor = 10_000;
+    pool_state.active_id = 10;
+
+    // 10_000 token Y in each of bins 8 to 10, at a price of 1
+    let mut bin_array_state = dlmm::accounts::BinArray::zeroed();
+    bin_array_state.lb_pair = lb_pair;
+    for bin in &mut bin_array_state.bins[8..=10] {
+        bin.amount_y = 10_000;
+        bin.price = ONE_Q64;
+    }
+
+    let mut test = setup_cpi_example_program();
+    test.add_account(
+        lb_pair,
+        dlmm_account_fixture(
+            dlmm::accounts::LbPair::DISCRIMINATOR,
+            bytemuck::bytes_of(&pool_state),
+        ),
+    );
+    test.add_account(
+        bin_array,
+        dlmm_account_fixture(
+            dlmm::accounts::BinArray::DISCRIMINATOR,
+            bytemuck::bytes_of(&bin_array_state),
+        ),
+    );
+    test.add_account(
+        mock_user.pubkey(),
+        Account {
+            lamports: u32::MAX.into(),
+            data: vec![],
+            owner: solana_sdk::system_program::ID,
+            ..Default::default()
+        },
+    );
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Each bin takes 10_000 plus a fee of 10.01, rounded up
+    for (max_bins, expected) in [(0, 10_011), (1, 20_022), (2, 30_033), (5, 30_033)] {
+        let return_data = simulate_and_get_return_data(
+            &[max_input_for_bins_ix(lb_pair, bin_array, max_bins)],
+            &mock_user,
+            &[],
+            &mut banks_client,
+        )
+        .await;
+
+        assert_eq!(
+            u64::from_le_bytes(return_data.try_into().unwrap()),
+            expected
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::PositionV2;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::setup_cpi_example_program;
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+async fn read_position(banks_client: &mut BanksClient, position: Pubkey) -> PositionV2 {
+    let account = banks_client.get_account(position).await.unwrap().unwrap();
+    read_zero_copy_account(&account.data)
+}
+
+#[tokio::test]
+async fn test_dlmm_merge_positions() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // The target covers the source's 5 bins and 5 more
+    let source = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        5,
+    )
+    .await;
+    let target = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    for position in [&source, &target] {
+        add_liquidity_one_side(
+            &mut banks_client,
+            &mock_user,
+            &pool_state,
+            position,
+            user_token_x,
+            true,
+            1_000_000,
+        )
+        .await;
+    }
+
+    let target_before = read_position(&mut banks_client, target.position).await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmMergePositions {}.data(),
+        accounts: cpi_example::accounts::DlmmMergePositions {
+            source_position: source.position,
+            source_bin_array_lower: source.bin_array_lower(),
+            source_bin_array_upper: source.bin_array_upper(),
+            target_position: target.position,
+            target_bin_array_lower: target.bin_array_lower(),
+            target_bin_array_upper: target.bin_array_upper(),
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            sender: mock_user.pubkey(),
+            rent_receiver: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    banks_client.process_transaction(tx).await.unwrap();
+
+    assert!(banks_client
+        .get_account(source.position)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The source's liquidity moved into the overlapping bins of the target
+    let target_after = read_position(&mut banks_client, target.position).await;
+    let shares = target_before
+        .liquidity_shares
+        .iter()
+        .zip(target_after.liquidity_shares.iter())
+        .take(target.width as usize);
+    for (offset, (before, after)) in shares.enumerate() {
+        if offset < source.width as usize {
+            assert!(after > before, "bin offset {offset} did not grow");
+        } else {
+            assert_eq!(after, before);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{LiquidityParameterByStrategy, StrategyParameters, StrategyType};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{get_token_balance, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_open_and_add_funds_new_position() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 10 bins above the active bin, so only token X is deposited
+    let lower_bin_id = pool_state.active_id + 1;
+    let width = 10;
+    let upper_bin_id = lower_bin_id + width - 1;
+
+    let ix_data = cpi_example::instruction::DlmmOpenAndAdd {
+        lower_bin_id,
+        width,
+        liquidity_parameter: LiquidityParameterByStrategy {
+            amount_x: 1_000_000,
+            amount_y: 0,
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 3,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: lower_bin_id,
+                max_bin_id: upper_bin_id,
+                strategy_type: StrategyType::SpotImBalanced,
+                parameteres: [0; 64],
+            },
+        },
+    }
+    .data();
+
+    let accounts = cpi_example::accounts::DlmmOpenAndAdd {
+        payer: mock_user.pubkey(),
+        position: position.pubkey(),
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower: derive_bin_array_pda(
+            USDC_USDT_POOL,
+            bin_id_to_bin_array_index(lower_bin_id).unwrap().into(),
+        )
+        .0,
+        bin_array_upper: derive_bin_array_pda(
+            USDC_USDT_POOL,
+            bin_id_to_bin_array_index(upper_bin_id).unwrap().into(),
+        )
+        .0,
+        owner: mock_user.pubkey(),
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+
+    // initialize_position created the position over the requested range
+    let position_account = banks_client
+        .get_account(position.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(position_account.owner, dlmm::ID);
+
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    assert_eq!(position_state.lb_pair, USDC_USDT_POOL);
+    assert_eq!(position_state.owner, mock_user.pubkey());
+    assert_eq!(position_state.lower_bin_id, lower_bin_id);
+    assert_eq!(position_state.upper_bin_id, upper_bin_id);
+
+    // add_liquidity_by_strategy funded every bin of it
+    assert!(position_state.liquidity_shares[..width as usize]
+        .iter()
+        .all(|share| *share > 0));
+
+    let x_after = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_after = get_token_balance(&mut banks_client, user_token_y).await;
+    assert!(x_before - x_after > 0);
+    assert_eq!(y_before, y_after);
+}
@@ -0,0 +1,94 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_open_centered::CenteredRange;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn open_centered_ix(owner: Pubkey, position: Pubkey, half_width: i32) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmOpenCentered { half_width }.data(),
+        accounts: cpi_example::accounts::DlmmInitializePosition {
+            payer: owner,
+            position,
+            lb_pair: USDC_USDT_POOL,
+            owner,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_open_centered_brackets_active_bin() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let instruction = open_centered_ix(mock_user.pubkey(), position.pubkey(), 10);
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[&position], &mut banks_client)
+            .await;
+    let range = CenteredRange::try_from_slice(&return_data).unwrap();
+
+    assert_eq!(
+        range,
+        CenteredRange {
+            lower_bin_id: pool_state.active_id - 10,
+            width: 21,
+        }
+    );
+    assert!(range.lower_bin_id <= pool_state.active_id);
+    assert!(range.lower_bin_id + range.width - 1 >= pool_state.active_id);
+}
+
+#[tokio::test]
+async fn test_dlmm_open_centered_width_too_large() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 2 * 35 + 1 = 71 bins, one more than a position can hold.
+    let instruction = open_centered_ix(mock_user.pubkey(), position.pubkey(), 35);
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+        DlmmCpiError::InvalidPositionWidth.into(),
+    )
+    .await;
+}
@@ -0,0 +1,111 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+// Serialized account sizes of the on-chain (BPF) layouts, discriminator included.
+const POSITION_V2_SPACE: usize = 8120;
+const BIN_ARRAY_SPACE: usize = 10136;
+
+#[tokio::test]
+async fn test_dlmm_open_cost_one_new_bin_array() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Only the active bin array is loaded into the test validator. Straddle the boundary
+    // to the next bin array so exactly one new bin array is needed.
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let next_bin_array_idx = active_bin_array_idx + 1;
+    let lower_bin_id = next_bin_array_idx * 70 - 5;
+    let width = 10;
+
+    let ix_data = cpi_example::instruction::DlmmOpenCost {
+        lower_bin_id,
+        width,
+    }
+    .data();
+
+    let accounts = cpi_example::accounts::DlmmOpenCost {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_lower: derive_bin_array_pda(USDC_USDT_POOL, active_bin_array_idx.into()).0,
+        bin_array_upper: derive_bin_array_pda(USDC_USDT_POOL, next_bin_array_idx.into()).0,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let lamports = u64::from_le_bytes(return_data.try_into().unwrap());
+
+    let rent = banks_client.get_rent().await.unwrap();
+    let expected = rent.minimum_balance(POSITION_V2_SPACE) + rent.minimum_balance(BIN_ARRAY_SPACE);
+
+    assert_eq!(lamports, expected);
+}
+
+#[tokio::test]
+async fn test_dlmm_open_cost_invalid_width() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let lower_bin_id = pool_state.active_id;
+    let bin_array = derive_bin_array_pda(
+        USDC_USDT_POOL,
+        bin_id_to_bin_array_index(lower_bin_id).unwrap().into(),
+    )
+    .0;
+
+    // DLMM positions cover 1 to 70 bins
+    for width in [0, 71] {
+        let instruction = Instruction {
+            program_id: cpi_example::id(),
+            data: cpi_example::instruction::DlmmOpenCost {
+                lower_bin_id,
+                width,
+            }
+            .data(),
+            accounts: cpi_example::accounts::DlmmOpenCost {
+                lb_pair: USDC_USDT_POOL,
+                bin_array_lower: bin_array,
+                bin_array_upper: bin_array,
+            }
+            .to_account_metas(None),
+        };
+
+        process_and_assert_err(
+            &[instruction],
+            &mock_user,
+            &[],
+            &mut banks_client,
+            DlmmCpiError::InvalidPositionWidth.into(),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,182 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn open_wide_ix(
+    owner: Pubkey,
+    position: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+    bin_arrays: &[Pubkey],
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmOpenWide {
+        payer: owner,
+        position,
+        lb_pair: USDC_USDT_POOL,
+        owner,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+    }
+    .to_account_metas(None);
+
+    accounts.extend(
+        bin_arrays
+            .iter()
+            .map(|bin_array| AccountMeta::new(*bin_array, false)),
+    );
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmOpenWide {
+            lower_bin_id,
+            width,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_open_wide_creates_missing_bin_arrays() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+    let second_position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // A position holds at most 70 bins, so it spans at most two bin arrays.
+    // Use the widest position, straddling the boundary between two bin
+    // arrays above the active one, which are not loaded into the validator.
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let lower_bin_id = (active_bin_array_idx + 3) * 70 - 35;
+    let width = dlmm::constants::MAX_BIN_PER_POSITION as i32;
+
+    let bin_arrays = [
+        (
+            derive_bin_array_pda(USDC_USDT_POOL, (active_bin_array_idx + 2).into()).0,
+            active_bin_array_idx + 2,
+        ),
+        (
+            derive_bin_array_pda(USDC_USDT_POOL, (active_bin_array_idx + 3).into()).0,
+            active_bin_array_idx + 3,
+        ),
+    ];
+    let bin_array_keys = bin_arrays.map(|(bin_array, _)| bin_array);
+
+    for bin_array in bin_array_keys {
+        assert!(banks_client.get_account(bin_array).await.unwrap().is_none());
+    }
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            open_wide_ix(
+                mock_user.pubkey(),
+                position.pubkey(),
+                lower_bin_id,
+                width,
+                &bin_array_keys,
+            ),
+        ],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+
+    for (bin_array, index) in bin_arrays {
+        let account = banks_client.get_account(bin_array).await.unwrap().unwrap();
+        assert_eq!(account.owner, dlmm::ID);
+
+        let bin_array_state: dlmm::accounts::BinArray = read_zero_copy_account(&account.data);
+        assert_eq!(bin_array_state.index, i64::from(index));
+    }
+
+    let position_account = banks_client
+        .get_account(position.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    assert_eq!(position_state.lb_pair, USDC_USDT_POOL);
+    assert_eq!(position_state.owner, mock_user.pubkey());
+    assert_eq!(position_state.lower_bin_id, lower_bin_id);
+    assert_eq!(position_state.upper_bin_id, lower_bin_id + width - 1);
+
+    // The bin arrays exist now, so a second position over the same range
+    // skips creating them.
+    process_and_assert_ok(
+        &[open_wide_ix(
+            mock_user.pubkey(),
+            second_position.pubkey(),
+            lower_bin_id,
+            width,
+            &bin_array_keys,
+        )],
+        &mock_user,
+        &[&second_position],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_open_wide_missing_bin_array() {
+    let mock_user = Keypair::new();
+    let position = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let lower_bin_id = (active_bin_array_idx + 3) * 70 - 5;
+
+    // The range needs two bin arrays but only the lower one is passed
+    let bin_array_lower = derive_bin_array_pda(USDC_USDT_POOL, (active_bin_array_idx + 2).into()).0;
+
+    process_and_assert_err(
+        &[open_wide_ix(
+            mock_user.pubkey(),
+            position.pubkey(),
+            lower_bin_id,
+            10,
+            &[bin_array_lower],
+        )],
+        &mock_user,
+        &[&position],
+        &mut banks_client,
+        DlmmCpiError::BinArrayCountMismatch.into(),
+    )
+    .await;
+}
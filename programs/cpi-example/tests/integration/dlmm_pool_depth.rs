@@ -0,0 +1,134 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::BinArray;
+use cpi_example::dlmm_pool_depth::MAX_POOL_DEPTH_RADIUS;
+use cpi_example::errors::DlmmCpiError;
+use cpi_example::utils::required_bin_array_indices;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn pool_depth_ix(radius: u16, bin_array_indices: &[i64]) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmPoolDepth {
+        lb_pair: USDC_USDT_POOL,
+    }
+    .to_account_metas(None);
+    accounts.extend(bin_array_indices.iter().map(|index| {
+        AccountMeta::new_readonly(derive_bin_array_pda(USDC_USDT_POOL, *index).0, false)
+    }));
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmPoolDepth { radius }.data(),
+        accounts,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_pool_depth() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The range may cross into a neighbouring bin array
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let radius = MAX_POOL_DEPTH_RADIUS;
+    let lower_bin_id = pool_state.active_id - i32::from(radius);
+    let upper_bin_id = pool_state.active_id + i32::from(radius);
+    let indices = required_bin_array_indices(lower_bin_id, upper_bin_id);
+
+    let return_data = simulate_and_get_return_data(
+        &[pool_depth_ix(radius, &indices)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+    let depth = Vec::<(i32, u128, u128)>::try_from_slice(&return_data).unwrap();
+
+    let bin_ids: Vec<i32> = depth.iter().map(|(bin_id, _, _)| *bin_id).collect();
+    assert_eq!(bin_ids, (lower_bin_id..=upper_bin_id).collect::<Vec<_>>());
+
+    // The active bin matches the bin array, and the pool has liquidity on
+    // both sides of it
+    let active_bin_array = banks_client
+        .get_account(derive_bin_array_pda(USDC_USDT_POOL, active_bin_array_idx.into()).0)
+        .await
+        .unwrap()
+        .unwrap();
+    let active_bin_array: BinArray = read_zero_copy_account(&active_bin_array.data);
+    let offset =
+        pool_state.active_id - active_bin_array_idx * dlmm::constants::MAX_BIN_PER_ARRAY as i32;
+    let active_bin = &active_bin_array.bins[offset as usize];
+
+    assert_eq!(
+        depth[usize::from(radius)],
+        (
+            pool_state.active_id,
+            u128::from(active_bin.amount_x),
+            u128::from(active_bin.amount_y)
+        )
+    );
+    assert!(depth[..usize::from(radius)]
+        .iter()
+        .any(|(_, _, reserve_y)| *reserve_y > 0));
+    assert!(depth[usize::from(radius) + 1..]
+        .iter()
+        .any(|(_, reserve_x, _)| *reserve_x > 0));
+}
+
+#[tokio::test]
+async fn test_dlmm_pool_depth_missing_bin_array() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+
+    // A radius past the return data limit, and a range without its bin array
+    for (radius, indices, error) in [
+        (
+            MAX_POOL_DEPTH_RADIUS + 1,
+            vec![i64::from(active_bin_array_idx)],
+            DlmmCpiError::TooManyBins,
+        ),
+        (0, vec![], DlmmCpiError::BinArrayCountMismatch),
+    ] {
+        process_and_assert_err(
+            &[pool_depth_ix(radius, &indices)],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+            error.into(),
+        )
+        .await;
+    }
+}
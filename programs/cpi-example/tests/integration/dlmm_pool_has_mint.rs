@@ -0,0 +1,46 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_pool_has_mint() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    for (mint, expected) in [
+        (pool_state.token_x_mint, true),
+        (pool_state.token_y_mint, true),
+        (Pubkey::new_unique(), false),
+    ] {
+        let instruction = Instruction {
+            program_id: cpi_example::id(),
+            data: cpi_example::instruction::DlmmPoolHasMint { mint }.data(),
+            accounts: cpi_example::accounts::DlmmPoolHasMint {
+                lb_pair: USDC_USDT_POOL,
+            }
+            .to_account_metas(None),
+        };
+
+        let return_data =
+            simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut banks_client).await;
+
+        assert_eq!(bool::try_from_slice(&return_data).unwrap(), expected);
+    }
+}
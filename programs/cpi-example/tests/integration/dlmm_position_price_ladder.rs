@@ -0,0 +1,69 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_position_price_ladder::MAX_PRICE_LADDER_BINS;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn position_price_ladder_ix(position: Pubkey, start_offset: u8) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmPositionPriceLadder { start_offset }.data(),
+        accounts: cpi_example::accounts::DlmmPositionPriceLadder {
+            lb_pair: USDC_USDT_POOL,
+            position,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_position_price_ladder_increases() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Wider than one call returns, centered on the active bin
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id - 30,
+        60,
+    )
+    .await;
+
+    let mut ladder = vec![];
+    for start_offset in [0, MAX_PRICE_LADDER_BINS as u8] {
+        let return_data = simulate_and_get_return_data(
+            &[position_price_ladder_ix(position.position, start_offset)],
+            &mock_user,
+            &[],
+            &mut banks_client,
+        )
+        .await;
+        ladder.extend(Vec::<(i32, u128)>::try_from_slice(&return_data).unwrap());
+    }
+
+    let bin_ids: Vec<i32> = ladder.iter().map(|(bin_id, _)| *bin_id).collect();
+    assert_eq!(
+        bin_ids,
+        (position.lower_bin_id..=position.upper_bin_id()).collect::<Vec<_>>()
+    );
+    assert!(ladder.windows(2).all(|pair| pair[1].1 > pair[0].1));
+}
@@ -0,0 +1,88 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::utils::{mul_q64, price_from_bin_id};
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+async fn position_value_in_quote(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    position: &PositionContext,
+    quote_is_y: bool,
+) -> u64 {
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmPositionValueInQuote { quote_is_y }.data(),
+        accounts: cpi_example::accounts::DlmmPositionValueInQuote {
+            lb_pair: position.lb_pair,
+            position: position.position,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+        }
+        .to_account_metas(None),
+    };
+
+    let return_data = simulate_and_get_return_data(&[instruction], payer, &[], banks_client).await;
+    u64::from_le_bytes(return_data.try_into().unwrap())
+}
+
+#[tokio::test]
+async fn test_dlmm_position_value_in_quote() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        ..
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Token X only, above the active bin
+    let deposit = 1_000_000;
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        deposit,
+    )
+    .await;
+
+    // In token X, the value is the deposit, less at most 1 unit rounded down per bin
+    let value_in_x = position_value_in_quote(&mut banks_client, &mock_user, &position, false).await;
+    assert!(value_in_x <= deposit);
+    assert!(value_in_x >= deposit - position.width as u64);
+
+    // In token Y, the token X holdings are converted at the active bin price
+    let price = price_from_bin_id(pool_state.active_id, pool_state.bin_step).unwrap();
+    let expected_in_y = mul_q64(value_in_x.into(), price).unwrap();
+
+    let value_in_y = position_value_in_quote(&mut banks_client, &mock_user, &position, true).await;
+    assert_eq!(u128::from(value_in_y), expected_in_y);
+}
@@ -0,0 +1,156 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_quote_swap::SwapQuote;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+    simulate_and_get_return_data,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+const AMOUNT_IN: u64 = 1_000_000;
+
+fn quote_swap_ix(amount_in: u64, bin_array: Pubkey) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmQuoteSwap {
+        lb_pair: USDC_USDT_POOL,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new_readonly(bin_array, false));
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmQuoteSwap {
+            amount_in,
+            swap_for_y: true,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_quote_swap_matches_swap() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let bin_array = derive_bin_array_pda(USDC_USDT_POOL, active_bin_array_idx.into()).0;
+
+    let return_data = simulate_and_get_return_data(
+        &[quote_swap_ix(AMOUNT_IN, bin_array)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+    let quote = SwapQuote::try_from_slice(&return_data).unwrap();
+
+    assert!(quote.amount_out > 0);
+    assert!(quote.fee > 0);
+
+    let mut swap_accounts = cpi_example::accounts::DlmmSwap {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+    swap_accounts.push(AccountMeta::new(bin_array, false));
+
+    let swap_ix = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmSwap {
+            amount_in: AMOUNT_IN,
+            min_amount_out: 0,
+            reject_zero_min_out: false,
+            preserve_inner_return_data: false,
+            expected_active_id: None,
+            max_active_bin_slippage: None,
+        }
+        .data(),
+        accounts: swap_accounts,
+    };
+
+    let balance_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            swap_ix,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let amount_out = get_token_balance(&mut banks_client, user_token_y).await - balance_before;
+
+    // Rounding may differ by a unit per bin crossed
+    assert!(
+        amount_out.abs_diff(quote.amount_out) <= 2,
+        "quoted {}, swapped {}",
+        quote.amount_out,
+        amount_out
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_quote_swap_zero_amount() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let bin_array = derive_bin_array_pda(USDC_USDT_POOL, active_bin_array_idx.into()).0;
+
+    process_and_assert_err(
+        &[quote_swap_ix(0, bin_array)],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidAmount.into(),
+    )
+    .await;
+}
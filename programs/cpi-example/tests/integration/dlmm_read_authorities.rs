@@ -0,0 +1,58 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_read_authorities::PoolAuthorities;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_read_authorities() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let ix_data = cpi_example::instruction::DlmmReadAuthorities {}.data();
+
+    let accounts = cpi_example::accounts::DlmmReadAuthorities {
+        lb_pair: USDC_USDT_POOL,
+    }
+    .to_account_metas(None);
+
+    assert!(accounts.iter().all(|meta| !meta.is_writable));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let authorities = PoolAuthorities::try_from_slice(&return_data).unwrap();
+
+    assert_eq!(
+        authorities,
+        PoolAuthorities {
+            creator: pool_state.creator,
+            fee_owner: Pubkey::new_from_array(pool_state.padding1),
+            reward_funders: [
+                pool_state.reward_infos[0].funder,
+                pool_state.reward_infos[1].funder,
+            ],
+        }
+    );
+}
@@ -0,0 +1,97 @@
+use crate::helpers;
+use anchor_lang::{
+    error::ErrorCode, solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_read_lb_pair::LbPairInfo;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{account::Account, instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn read_lb_pair_ix(lb_pair: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmReadLbPair {}.data(),
+        accounts: cpi_example::accounts::DlmmReadLbPair { lb_pair }.to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_read_lb_pair() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let return_data = simulate_and_get_return_data(
+        &[read_lb_pair_ix(USDC_USDT_POOL)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+    let info = LbPairInfo::try_from_slice(&return_data).unwrap();
+
+    assert_eq!(
+        info,
+        LbPairInfo {
+            active_id: pool_state.active_id,
+            bin_step: pool_state.bin_step,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            status: pool_state.status,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_read_lb_pair_truncated_account() {
+    let mock_user = Keypair::new();
+    let truncated_pool = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    // The real pool's discriminator and the first bytes of its state only
+    let mut test_context = test.start_with_context().await;
+    let pool_account = test_context
+        .banks_client
+        .get_account(USDC_USDT_POOL)
+        .await
+        .unwrap()
+        .unwrap();
+    test_context.set_account(
+        &truncated_pool,
+        &Account {
+            data: pool_account.data[..64].to_vec(),
+            ..pool_account
+        }
+        .into(),
+    );
+
+    process_and_assert_err(
+        &[read_lb_pair_ix(truncated_pool)],
+        &mock_user,
+        &[],
+        &mut test_context.banks_client,
+        ErrorCode::AccountDidNotDeserialize.into(),
+    )
+    .await;
+}
@@ -0,0 +1,91 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm_read_oracle::OracleObservation;
+use cpi_example::errors::DlmmCpiError;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{account::Account, instruction::Instruction, signature::Keypair, signer::Signer};
+
+/// Oracle account holding a single observation
+fn oracle_fixture(observation: &OracleObservation) -> Account {
+    let mut data = dlmm::accounts::Oracle::DISCRIMINATOR.to_vec();
+    // idx, active_size, length
+    for value in [0u64, 1, 1] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&observation.cumulative_active_bin_id.to_le_bytes());
+    data.extend_from_slice(&observation.created_at.to_le_bytes());
+    data.extend_from_slice(&observation.last_updated_at.to_le_bytes());
+
+    Account {
+        lamports: u32::MAX.into(),
+        data,
+        owner: dlmm::ID,
+        ..Default::default()
+    }
+}
+
+fn read_oracle_ix(oracle: Pubkey, max_staleness_seconds: u64) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmReadOracle {
+            max_staleness_seconds,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmReadOracle { oracle }.to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_read_oracle_stale() {
+    let mock_user = Keypair::new();
+    let oracle = Pubkey::new_unique();
+
+    // Last updated right after the epoch, long before the test validator clock
+    let observation = OracleObservation {
+        cumulative_active_bin_id: 1_000,
+        created_at: 1,
+        last_updated_at: 1,
+    };
+
+    let mut test = setup_cpi_example_program();
+    test.add_account(oracle, oracle_fixture(&observation));
+    test.add_account(
+        mock_user.pubkey(),
+        Account {
+            lamports: u32::MAX.into(),
+            data: vec![],
+            owner: solana_sdk::system_program::ID,
+            ..Default::default()
+        },
+    );
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    process_and_assert_err(
+        &[read_oracle_ix(oracle, 3_600)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::OracleStale.into(),
+    )
+    .await;
+
+    // Accepting any age returns the observation
+    let return_data = simulate_and_get_return_data(
+        &[read_oracle_ix(oracle, u64::MAX)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+
+    assert_eq!(
+        OracleObservation::try_from_slice(&return_data).unwrap(),
+        observation
+    );
+}
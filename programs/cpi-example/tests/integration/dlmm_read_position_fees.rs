@@ -0,0 +1,142 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use bytemuck::Zeroable;
+use cpi_example::dlmm;
+use cpi_example::dlmm_read_position_fees::PositionFees;
+use cpi_example::errors::DlmmCpiError;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{account::Account, instruction::Instruction, signature::Keypair, signer::Signer};
+
+fn dlmm_account_fixture(discriminator: &[u8], state: &[u8]) -> Account {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(state);
+
+    Account {
+        lamports: u32::MAX.into(),
+        data,
+        owner: dlmm::ID,
+        ..Default::default()
+    }
+}
+
+fn payer_account() -> Account {
+    Account {
+        lamports: u32::MAX.into(),
+        data: vec![],
+        owner: solana_sdk::system_program::ID,
+        ..Default::default()
+    }
+}
+
+fn read_position_fees_ix(position: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmReadPositionFees {}.data(),
+        accounts: cpi_example::accounts::DlmmReadPositionFees { position }.to_account_metas(None),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_read_position_fees_v1_and_v2() {
+    let mock_user = Keypair::new();
+    let position_v1 = Pubkey::new_unique();
+    let position_v2 = Pubkey::new_unique();
+
+    // Pending amounts spread over the first and last bins of each position
+    let mut state_v2 = dlmm::accounts::PositionV2::zeroed();
+    state_v2.fee_infos[0].fee_x_pending = 1_000;
+    state_v2.fee_infos[69].fee_x_pending = 234;
+    state_v2.fee_infos[0].fee_y_pending = 5_000;
+    state_v2.reward_infos[0].reward_pendings = [7, 0];
+    state_v2.reward_infos[69].reward_pendings = [3, 11];
+
+    let mut state_v1 = dlmm::accounts::Position::zeroed();
+    state_v1.fee_infos[0].fee_y_pending = 42;
+    state_v1.fee_infos[69].fee_y_pending = 8;
+    state_v1.reward_infos[0].reward_pendings = [0, 9];
+
+    let mut test = setup_cpi_example_program();
+    test.add_account(
+        position_v2,
+        dlmm_account_fixture(
+            dlmm::accounts::PositionV2::DISCRIMINATOR,
+            bytemuck::bytes_of(&state_v2),
+        ),
+    );
+    test.add_account(
+        position_v1,
+        dlmm_account_fixture(
+            dlmm::accounts::Position::DISCRIMINATOR,
+            bytemuck::bytes_of(&state_v1),
+        ),
+    );
+    test.add_account(mock_user.pubkey(), payer_account());
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let return_data = simulate_and_get_return_data(
+        &[read_position_fees_ix(position_v2)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+
+    assert_eq!(
+        PositionFees::try_from_slice(&return_data).unwrap(),
+        PositionFees {
+            fee_x_owed: 1_234,
+            fee_y_owed: 5_000,
+            reward_owed: [10, 11],
+        }
+    );
+
+    let return_data = simulate_and_get_return_data(
+        &[read_position_fees_ix(position_v1)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+
+    assert_eq!(
+        PositionFees::try_from_slice(&return_data).unwrap(),
+        PositionFees {
+            fee_x_owed: 0,
+            fee_y_owed: 50,
+            reward_owed: [0, 9],
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_read_position_fees_unknown_layout() {
+    let mock_user = Keypair::new();
+    let lb_pair = Pubkey::new_unique();
+
+    // A DLMM account that is not a position
+    let mut test = setup_cpi_example_program();
+    test.add_account(
+        lb_pair,
+        dlmm_account_fixture(
+            dlmm::accounts::LbPair::DISCRIMINATOR,
+            bytemuck::bytes_of(&dlmm::accounts::LbPair::zeroed()),
+        ),
+    );
+    test.add_account(mock_user.pubkey(), payer_account());
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    process_and_assert_err(
+        &[read_position_fees_ix(lb_pair)],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::UnknownPositionLayout.into(),
+    )
+    .await;
+}
@@ -0,0 +1,159 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityReduction;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_remove_and_balance() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 1. Deposit token X only, above the active bin
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    // 2. Withdraw everything and rebalance the proceeds to 50/50
+    let bin_liquidity_removal: Vec<_> = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityReduction {
+            bin_id,
+            bps_to_remove: 10_000,
+        })
+        .collect();
+
+    let ix_data = |expected_active_id| {
+        cpi_example::instruction::DlmmRemoveAndBalance {
+            bin_liquidity_removal: bin_liquidity_removal.clone(),
+            target_ratio_bps: 5_000,
+            max_swap_slippage_bps: 100,
+            expected_active_id,
+            max_active_bin_slippage: 0,
+        }
+        .data()
+    };
+
+    let mut accounts = cpi_example::accounts::DlmmRemoveAndBalance {
+        position: position.position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower: position.bin_array_lower(),
+        bin_array_upper: position.bin_array_upper(),
+        oracle: pool_state.oracle,
+        sender: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    // Selling token X moves the active bin down
+    for idx in [active_bin_array_idx, active_bin_array_idx - 1] {
+        let (bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, idx.into());
+        accounts.push(AccountMeta::new(bin_array, false));
+    }
+
+    // A stale observation of the active bin is rejected
+    let stale = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data(pool_state.active_id + 5),
+        accounts: accounts.clone(),
+    };
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            stale,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::SlippageExceeded.into(),
+    )
+    .await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data(pool_state.active_id),
+        accounts,
+    };
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let received_x = get_token_balance(&mut banks_client, user_token_x).await - x_before;
+    let received_y = get_token_balance(&mut banks_client, user_token_y).await - y_before;
+
+    // USDC/USDT trades close to 1:1, so equal value is close to equal amounts
+    let total = received_x + received_y;
+    assert!(total > 0);
+    assert!(received_x.abs_diff(received_y) * 100 <= total * 2);
+}
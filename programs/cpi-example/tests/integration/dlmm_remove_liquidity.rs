@@ -0,0 +1,245 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityReduction;
+use cpi_example::errors::DlmmCpiError;
+use cpi_example::utils::MAX_BINS_PER_REMOVE;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Withdraw everything from the three lowest bins of the position
+fn remove_liquidity_ix(
+    owner: Pubkey,
+    pool_setup: &PoolSetupContext,
+    position: &PositionContext,
+    min_amount_x: u64,
+    min_amount_y: u64,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquidity {
+            bin_liquidity_removal: (position.lower_bin_id..position.lower_bin_id + 3)
+                .map(|bin_id| BinLiquidityReduction {
+                    bin_id,
+                    bps_to_remove: 10_000,
+                })
+                .collect(),
+            min_amount_x,
+            min_amount_y,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x: pool_setup.user_token_x,
+            user_token_y: pool_setup.user_token_y,
+            reserve_x: pool_setup.pool_state.reserve_x,
+            reserve_y: pool_setup.pool_state.reserve_y,
+            token_x_mint: pool_setup.pool_state.token_x_mint,
+            token_y_mint: pool_setup.pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// A `width` bin position holding 1_000_000 token X spread evenly above the
+/// active bin
+async fn setup(width: i32) -> (BanksClient, Keypair, PoolSetupContext, PositionContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_setup.pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_setup.pool_state.active_id + 1,
+        width,
+    )
+    .await;
+
+    // Wide positions take more than the default compute limit to fill
+    let instruction = add_liquidity_one_side_ix(
+        mock_user.pubkey(),
+        &pool_setup.pool_state,
+        &position,
+        pool_setup.user_token_x,
+        true,
+        1_000_000,
+    );
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    (banks_client, mock_user, pool_setup, position)
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_within_slippage() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup(10).await;
+
+    let x_before = get_token_balance(&mut banks_client, pool_setup.user_token_x).await;
+
+    // Three of ten equal bins hold about 300_000 token X, less rounding
+    let instruction = remove_liquidity_ix(mock_user.pubkey(), &pool_setup, &position, 290_000, 0);
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    assert!(
+        get_token_balance(&mut banks_client, pool_setup.user_token_x).await - x_before >= 290_000
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_slippage_exceeded() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup(10).await;
+
+    for (min_amount_x, min_amount_y) in [(1_000_000, 0), (0, 1)] {
+        let instruction = remove_liquidity_ix(
+            mock_user.pubkey(),
+            &pool_setup,
+            &position,
+            min_amount_x,
+            min_amount_y,
+        );
+
+        process_and_assert_err(
+            &[instruction],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+            DlmmCpiError::SlippageExceeded.into(),
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_degenerate_removals() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup(10).await;
+
+    let zero_bps = vec![
+        BinLiquidityReduction {
+            bin_id: position.lower_bin_id,
+            bps_to_remove: 10_000,
+        },
+        BinLiquidityReduction {
+            bin_id: position.lower_bin_id + 1,
+            bps_to_remove: 0,
+        },
+    ];
+
+    for (bin_liquidity_removal, error) in [
+        (vec![], DlmmCpiError::EmptyBinList),
+        (zero_bps, DlmmCpiError::InvalidAmount),
+    ] {
+        let mut instruction = remove_liquidity_ix(mock_user.pubkey(), &pool_setup, &position, 0, 0);
+        instruction.data = cpi_example::instruction::DlmmRemoveLiquidity {
+            bin_liquidity_removal,
+            min_amount_x: 0,
+            min_amount_y: 0,
+        }
+        .data();
+
+        process_and_assert_err(
+            &[instruction],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+            error.into(),
+        )
+        .await;
+    }
+
+    // Rejected before the CPI, nothing was withdrawn
+    let position_account = banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    assert!(position_state.liquidity_shares[..10]
+        .iter()
+        .all(|share| *share > 0));
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_max_bins() {
+    let (mut banks_client, mock_user, pool_setup, position) =
+        setup(MAX_BINS_PER_REMOVE as i32).await;
+
+    let remove_bins_ix = |count: usize| {
+        let mut instruction = remove_liquidity_ix(mock_user.pubkey(), &pool_setup, &position, 0, 0);
+        instruction.data = cpi_example::instruction::DlmmRemoveLiquidity {
+            bin_liquidity_removal: (position.lower_bin_id..)
+                .take(count)
+                .map(|bin_id| BinLiquidityReduction {
+                    bin_id,
+                    bps_to_remove: 10_000,
+                })
+                .collect(),
+            min_amount_x: 0,
+            min_amount_y: 0,
+        }
+        .data();
+        instruction
+    };
+
+    // One bin over the cap, past the position's upper bin
+    process_and_assert_err(
+        &[remove_bins_ix(MAX_BINS_PER_REMOVE + 1)],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::TooManyBins.into(),
+    )
+    .await;
+
+    // Exactly at the cap, every bin of the position
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            remove_bins_ix(MAX_BINS_PER_REMOVE),
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
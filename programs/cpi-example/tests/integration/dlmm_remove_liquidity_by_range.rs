@@ -0,0 +1,201 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::PositionV2;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn remove_liquidity_by_range_ix(
+    owner: Pubkey,
+    pool_setup: &PoolSetupContext,
+    position: &PositionContext,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquidityByRange {
+            from_bin_id,
+            to_bin_id,
+            bps_to_remove,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x: pool_setup.user_token_x,
+            user_token_y: pool_setup.user_token_y,
+            reserve_x: pool_setup.pool_state.reserve_x,
+            reserve_y: pool_setup.pool_state.reserve_y,
+            token_x_mint: pool_setup.pool_state.token_x_mint,
+            token_y_mint: pool_setup.pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// A 10 bin position holding token X only, above the active bin
+async fn setup() -> (BanksClient, Keypair, PoolSetupContext, PositionContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_setup.pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_setup.pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_setup.pool_state,
+        &position,
+        pool_setup.user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    (banks_client, mock_user, pool_setup, position)
+}
+
+async fn liquidity_shares(banks_client: &mut BanksClient, position: Pubkey) -> Vec<u128> {
+    let account = banks_client.get_account(position).await.unwrap().unwrap();
+    let state: PositionV2 = read_zero_copy_account(&account.data);
+    state.liquidity_shares.to_vec()
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_by_range_single_bin() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    let shares_before = liquidity_shares(&mut banks_client, position.position).await;
+
+    let bin_id = position.lower_bin_id + 3;
+    let instruction = remove_liquidity_by_range_ix(
+        mock_user.pubkey(),
+        &pool_setup,
+        &position,
+        bin_id,
+        bin_id,
+        10_000,
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    let shares_after = liquidity_shares(&mut banks_client, position.position).await;
+
+    // Only the one bin of the range is emptied
+    let shares = shares_before
+        .iter()
+        .zip(shares_after.iter())
+        .take(position.width as usize);
+    for (offset, (before, after)) in shares.enumerate() {
+        if offset == 3 {
+            assert_eq!(*after, 0);
+        } else {
+            assert_eq!(after, before);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_by_range_half() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    let shares_before = liquidity_shares(&mut banks_client, position.position).await;
+
+    // Half of the liquidity of the five lowest bins
+    let instruction = remove_liquidity_by_range_ix(
+        mock_user.pubkey(),
+        &pool_setup,
+        &position,
+        position.lower_bin_id,
+        position.lower_bin_id + 4,
+        5_000,
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    let shares_after = liquidity_shares(&mut banks_client, position.position).await;
+
+    let shares = shares_before
+        .iter()
+        .zip(shares_after.iter())
+        .take(position.width as usize);
+    for (offset, (before, after)) in shares.enumerate() {
+        if offset < 5 {
+            assert!(after.abs_diff(before / 2) <= 1);
+        } else {
+            assert_eq!(after, before);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_by_range_invalid_args() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    for (from_bin_id, to_bin_id, bps_to_remove, error) in [
+        (
+            position.lower_bin_id + 1,
+            position.lower_bin_id,
+            10_000,
+            DlmmCpiError::InvalidBinRange,
+        ),
+        (
+            position.lower_bin_id,
+            position.lower_bin_id,
+            10_001,
+            DlmmCpiError::InvalidBps,
+        ),
+    ] {
+        let instruction = remove_liquidity_by_range_ix(
+            mock_user.pubkey(),
+            &pool_setup,
+            &position,
+            from_bin_id,
+            to_bin_id,
+            bps_to_remove,
+        );
+
+        process_and_assert_err(
+            &[instruction],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+            error.into(),
+        )
+        .await;
+    }
+}
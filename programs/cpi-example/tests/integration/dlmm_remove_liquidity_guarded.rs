@@ -0,0 +1,164 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityReduction;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Withdraw everything from the position, pinned to the given pool and owner
+fn remove_liquidity_guarded_ix(
+    owner: Pubkey,
+    pool_setup: &PoolSetupContext,
+    position: &PositionContext,
+    expected_lb_pair: Pubkey,
+    expected_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquidityGuarded {
+            expected_lb_pair,
+            expected_owner,
+            bin_liquidity_removal: (position.lower_bin_id..=position.upper_bin_id())
+                .map(|bin_id| BinLiquidityReduction {
+                    bin_id,
+                    bps_to_remove: 10_000,
+                })
+                .collect(),
+            min_amount_x: 0,
+            min_amount_y: 0,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x: pool_setup.user_token_x,
+            user_token_y: pool_setup.user_token_y,
+            reserve_x: pool_setup.pool_state.reserve_x,
+            reserve_y: pool_setup.pool_state.reserve_y,
+            token_x_mint: pool_setup.pool_state.token_x_mint,
+            token_y_mint: pool_setup.pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// A 10 bin position holding 1_000_000 token X spread evenly above the active bin
+async fn setup() -> (BanksClient, Keypair, PoolSetupContext, PositionContext) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let pool_setup = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_setup.pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_setup.pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_setup.pool_state,
+        &position,
+        pool_setup.user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    (banks_client, mock_user, pool_setup, position)
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_guarded_matching() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    let x_before = get_token_balance(&mut banks_client, pool_setup.user_token_x).await;
+
+    let instruction = remove_liquidity_guarded_ix(
+        mock_user.pubkey(),
+        &pool_setup,
+        &position,
+        USDC_USDT_POOL,
+        mock_user.pubkey(),
+    );
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    // Everything deposited comes back, less rounding
+    let received = get_token_balance(&mut banks_client, pool_setup.user_token_x).await - x_before;
+    assert!(received >= 999_990);
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_guarded_wrong_owner() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    let instruction = remove_liquidity_guarded_ix(
+        mock_user.pubkey(),
+        &pool_setup,
+        &position,
+        USDC_USDT_POOL,
+        Pubkey::new_unique(),
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::PositionOwnerMismatch.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_guarded_wrong_lb_pair() {
+    let (mut banks_client, mock_user, pool_setup, position) = setup().await;
+
+    let instruction = remove_liquidity_guarded_ix(
+        mock_user.pubkey(),
+        &pool_setup,
+        &position,
+        Pubkey::new_unique(),
+        mock_user.pubkey(),
+    );
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::PositionLbPairMismatch.into(),
+    )
+    .await;
+}
@@ -0,0 +1,221 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{
+    BinLiquidityDistributionByWeight, BinLiquidityReduction, LiquidityOneSideParameter,
+};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    add_packable_account, get_token_balance, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, system_program, sysvar,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn derive_position_owner_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position_owner"], &cpi_example::ID)
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_signed_drains_program_owned_position() {
+    let mock_user = Keypair::new();
+    let base = Keypair::new();
+    let (position_owner, position_owner_bump) = derive_position_owner_pda();
+    let owner_token_x = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    // Token X held by the program's position owner, deposited into the position
+    add_packable_account(
+        &mut test,
+        TokenAccount {
+            mint: pool_state.token_x_mint,
+            owner: position_owner,
+            amount: 1_000_000,
+            state: AccountState::Initialized,
+            ..Default::default()
+        },
+        anchor_spl::token::ID,
+        owner_token_x,
+    );
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let lower_bin_id = pool_state.active_id + 1;
+    let width = 10;
+    let (position_key, _bump) =
+        derive_position_pda(USDC_USDT_POOL, base.pubkey(), lower_bin_id, width);
+    let position = PositionContext {
+        lb_pair: USDC_USDT_POOL,
+        position: position_key,
+        lower_bin_id,
+        width,
+    };
+
+    let initialize_position = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializePositionPda {
+            lower_bin_id,
+            width,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmInitializePositionPda {
+            payer: mock_user.pubkey(),
+            base: base.pubkey(),
+            position: position_key,
+            lb_pair: USDC_USDT_POOL,
+            position_owner,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(
+        &[initialize_position],
+        &mock_user,
+        &[&base],
+        &mut banks_client,
+    )
+    .await;
+
+    let add_liquidity = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddLiquidityOneSideSigned {
+            position_owner_bump,
+            liquidity_parameter: LiquidityOneSideParameter {
+                amount: 1_000_000,
+                active_id: pool_state.active_id,
+                max_active_bin_slippage: 3,
+                bin_liquidity_dist: (lower_bin_id..=position.upper_bin_id())
+                    .map(|bin_id| BinLiquidityDistributionByWeight { bin_id, weight: 1 })
+                    .collect(),
+            },
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmAddLiquidityOneSideSigned {
+            position: position_key,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token: owner_token_x,
+            reserve: pool_state.reserve_x,
+            token_mint: pool_state.token_x_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            position_owner,
+            cpi_example_admin: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(&[add_liquidity], &mock_user, &[], &mut banks_client).await;
+    assert_eq!(get_token_balance(&mut banks_client, owner_token_x).await, 0);
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    // Only the admin signs the transaction, the program signs for the owner
+    let remove_liquidity = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquiditySigned {
+            position_owner_bump,
+            bin_liquidity_removal: (lower_bin_id..=position.upper_bin_id())
+                .map(|bin_id| BinLiquidityReduction {
+                    bin_id,
+                    bps_to_remove: 10_000,
+                })
+                .collect(),
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquiditySigned {
+            position: position_key,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            position_owner,
+            cpi_example_admin: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(&[remove_liquidity], &mock_user, &[], &mut banks_client).await;
+
+    // Everything deposited comes back, less rounding
+    let received = get_token_balance(&mut banks_client, user_token_x).await - x_before;
+    assert!(received >= 999_990);
+
+    let position_account = banks_client
+        .get_account(position_key)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    assert!(position_state
+        .liquidity_shares
+        .iter()
+        .all(|share| *share == 0));
+
+    let rent_before = banks_client.get_balance(mock_user.pubkey()).await.unwrap();
+
+    let close_position = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClosePositionSigned {
+            position_owner_bump,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmClosePositionSigned {
+            position: position_key,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            position_owner,
+            cpi_example_admin: mock_user.pubkey(),
+            rent_receiver: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(&[close_position], &mock_user, &[], &mut banks_client).await;
+
+    assert!(banks_client
+        .get_account(position_key)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(banks_client.get_balance(mock_user.pubkey()).await.unwrap() > rent_before);
+}
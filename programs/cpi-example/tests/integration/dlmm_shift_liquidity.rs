@@ -0,0 +1,137 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::{
+    BinLiquidityReduction, LiquidityParameterByStrategy, StrategyParameters, StrategyType,
+};
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+async fn liquidity_shares(banks_client: &mut BanksClient, position: Pubkey) -> [u128; 70] {
+    let position_account = banks_client.get_account(position).await.unwrap().unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    position_state.liquidity_shares
+}
+
+#[tokio::test]
+async fn test_dlmm_shift_liquidity_upward() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &[active_bin_array_idx + 1]).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 1. Deposit token X only, above the active bin
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    add_liquidity_one_side(
+        &mut banks_client,
+        &mock_user,
+        &pool_state,
+        &position,
+        user_token_x,
+        true,
+        1_000_000,
+    )
+    .await;
+
+    let shares_before = liquidity_shares(&mut banks_client, position.position).await;
+
+    // 2. Move the lower half of the position into the upper half
+    let split_bin_id = position.lower_bin_id + 5;
+
+    let from_removals = (position.lower_bin_id..split_bin_id)
+        .map(|bin_id| BinLiquidityReduction {
+            bin_id,
+            bps_to_remove: 10_000,
+        })
+        .collect();
+
+    let to_add = LiquidityParameterByStrategy {
+        amount_x: 0,
+        amount_y: 0,
+        active_id: pool_state.active_id,
+        max_active_bin_slippage: 3,
+        strategy_parameters: StrategyParameters {
+            min_bin_id: split_bin_id,
+            max_bin_id: position.upper_bin_id(),
+            strategy_type: StrategyType::SpotBalanced,
+            parameteres: [0; 64],
+        },
+    };
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmShiftLiquidity {
+            from_removals,
+            to_add,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmShiftLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let shares_after = liquidity_shares(&mut banks_client, position.position).await;
+
+    assert!(shares_after[..5].iter().all(|share| *share == 0));
+    assert!(shares_after[5..10]
+        .iter()
+        .zip(&shares_before[5..10])
+        .all(|(after, before)| after > before));
+}
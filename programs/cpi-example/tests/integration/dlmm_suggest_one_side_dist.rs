@@ -0,0 +1,53 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::types::BinLiquidityDistributionByWeight;
+use cpi_example::dlmm_suggest_one_side_dist::SHAPE_LINEAR;
+use helpers::dlmm_utils::*;
+use helpers::{setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_suggest_one_side_dist_below_active_bin() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // Token Y range ending at the active bin
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmSuggestOneSideDist {
+            from_bin_id: pool_state.active_id - 2,
+            to_bin_id: pool_state.active_id,
+            shape: SHAPE_LINEAR,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmSuggestOneSideDist {
+            lb_pair: USDC_USDT_POOL,
+        }
+        .to_account_metas(None),
+    };
+
+    let return_data =
+        simulate_and_get_return_data(&[instruction], &mock_user, &[], &mut banks_client).await;
+    let dist = Vec::<BinLiquidityDistributionByWeight>::try_from_slice(&return_data).unwrap();
+
+    let weights = dist
+        .iter()
+        .map(|dist| (dist.bin_id - pool_state.active_id, dist.weight))
+        .collect::<Vec<_>>();
+    assert_eq!(weights, [(-2, 1), (-1, 2), (0, 3)]);
+}
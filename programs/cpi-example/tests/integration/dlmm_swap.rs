@@ -1,20 +1,39 @@
 use crate::helpers;
 use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
 use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
 use helpers::dlmm_pda::*;
 use helpers::dlmm_utils::*;
-use helpers::{process_and_assert_ok, setup_cpi_example_program};
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
 use solana_program_test::*;
 use solana_sdk::instruction::AccountMeta;
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
-    signer::Signer,
+    signer::Signer, transaction::Transaction,
 };
 
 const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
 
-#[tokio::test]
-async fn test_dlmm_swap() {
+struct SwapContext {
+    banks_client: BanksClient,
+    mock_user: Keypair,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    active_id: i32,
+    bin_arrays: Vec<Pubkey>,
+    instruction: Instruction,
+}
+
+/// Sets up the pool and builds a swap selling 1 token X for token Y with no
+/// minimum output, passing `bin_array_count` bin arrays starting from the
+/// active one downwards
+async fn setup_swap(
+    bin_array_count: i32,
+    reject_zero_min_out: bool,
+    preserve_inner_return_data: bool,
+) -> SwapContext {
     let mock_user = Keypair::new();
 
     let mut test = setup_cpi_example_program();
@@ -28,11 +47,23 @@ async fn test_dlmm_swap() {
         user_token_y,
     } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
 
-    let (mut banks_client, _, _) = test.start().await;
+    // Selling token X moves the active bin down. Only the active bin array is
+    // loaded by default, clone the ones below it.
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let lower_bin_array_indexes = (1..bin_array_count)
+        .map(|i| active_bin_array_idx - i)
+        .collect::<Vec<_>>();
+    add_bin_arrays_from_cluster(&mut test, USDC_USDT_POOL, &lower_bin_array_indexes).await;
+
+    let (banks_client, _, _) = test.start().await;
 
     let ix_data = cpi_example::instruction::DlmmSwap {
         amount_in: 1_000_000,
         min_amount_out: 0,
+        reject_zero_min_out,
+        preserve_inner_return_data,
+        expected_active_id: None,
+        max_active_bin_slippage: None,
     }
     .data();
 
@@ -55,21 +86,236 @@ async fn test_dlmm_swap() {
     }
     .to_account_metas(None);
 
-    let (active_bin_array_key, _bump) = derive_bin_array_pda(
-        USDC_USDT_POOL,
-        bin_id_to_bin_array_index(pool_state.active_id)
-            .unwrap()
-            .into(),
+    let bin_arrays = (0..bin_array_count)
+        .map(|i| derive_bin_array_pda(USDC_USDT_POOL, (active_bin_array_idx - i).into()).0)
+        .collect::<Vec<_>>();
+    accounts.extend(
+        bin_arrays
+            .iter()
+            .map(|bin_array| AccountMeta::new(*bin_array, false)),
     );
 
-    accounts.push(AccountMeta::new(active_bin_array_key, false));
-
     let instruction = Instruction {
         program_id: cpi_example::id(),
         data: ix_data,
         accounts,
     };
 
+    SwapContext {
+        banks_client,
+        mock_user,
+        user_token_x,
+        user_token_y,
+        active_id: pool_state.active_id,
+        bin_arrays,
+        instruction,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        instruction,
+        ..
+    } = setup_swap(1, false, false).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_returns_amount_out() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        user_token_y,
+        instruction,
+        ..
+    } = setup_swap(1, false, false).await;
+
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    let return_data = result.metadata.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, cpi_example::id());
+
+    let amount_out = u64::from_le_bytes(return_data.data.try_into().unwrap());
+    let received = get_token_balance(&mut banks_client, user_token_y).await - y_before;
+
+    assert!(amount_out > 0);
+    assert_eq!(amount_out, received);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_preserve_inner_return_data() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        user_token_y,
+        instruction,
+        ..
+    } = setup_swap(1, false, true).await;
+
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+    assert!(get_token_balance(&mut banks_client, user_token_y).await > y_before);
+
+    // Whatever the DLMM CPI left is passed through untouched, nothing is set
+    // by the wrapper on top of it
+    if let Some(return_data) = result.metadata.unwrap().return_data {
+        assert_ne!(return_data.program_id, cpi_example::id());
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_multiple_bin_arrays() {
+    for bin_array_count in [2, 3] {
+        let SwapContext {
+            mut banks_client,
+            mock_user,
+            instruction,
+            bin_arrays,
+            ..
+        } = setup_swap(bin_array_count, false, false).await;
+
+        // Passed in order after the fixed accounts, active bin array first
+        let passed = instruction.accounts[instruction.accounts.len() - bin_arrays.len()..]
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect::<Vec<_>>();
+        assert_eq!(bin_arrays.len(), bin_array_count as usize);
+        assert_eq!(passed, bin_arrays);
+
+        process_and_assert_ok(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction,
+            ],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+        )
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_no_bin_arrays() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        instruction,
+        ..
+    } = setup_swap(0, false, false).await;
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::NoBinArrays.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_reject_zero_min_out() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        instruction,
+        ..
+    } = setup_swap(1, true, false).await;
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::ZeroMinOut.into(),
+    )
+    .await;
+
+    // The same swap goes through when zero is allowed
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        instruction,
+        ..
+    } = setup_swap(1, false, false).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_x_for_y_direction() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        user_token_x,
+        user_token_y,
+        instruction,
+        ..
+    } = setup_swap(1, false, false).await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
     process_and_assert_ok(
         &[
             ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
@@ -80,4 +326,130 @@ async fn test_dlmm_swap() {
         &mut banks_client,
     )
     .await;
+
+    assert_eq!(
+        x_before - get_token_balance(&mut banks_client, user_token_x).await,
+        1_000_000
+    );
+    assert!(get_token_balance(&mut banks_client, user_token_y).await > y_before);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_output_on_input_side() {
+    let SwapContext {
+        mut banks_client,
+        mock_user,
+        user_token_x,
+        mut instruction,
+        ..
+    } = setup_swap(1, false, false).await;
+
+    // user_token_out holds token X, the same token as user_token_in
+    instruction.accounts[5].pubkey = user_token_x;
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::SwapDirectionMismatch.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_active_bin_slippage() {
+    // (observed bin offset from the real active bin, tolerance, expected error)
+    let cases = [
+        (0, 0, None),
+        (3, 3, None),
+        (-3, 3, None),
+        (4, 3, Some(DlmmCpiError::SlippageExceeded)),
+        (-1, 0, Some(DlmmCpiError::SlippageExceeded)),
+    ];
+
+    for (offset, max_active_bin_slippage, error) in cases {
+        let SwapContext {
+            mut banks_client,
+            mock_user,
+            active_id,
+            mut instruction,
+            ..
+        } = setup_swap(1, false, false).await;
+
+        instruction.data = cpi_example::instruction::DlmmSwap {
+            amount_in: 1_000_000,
+            min_amount_out: 0,
+            reject_zero_min_out: false,
+            preserve_inner_return_data: false,
+            expected_active_id: Some(active_id + offset),
+            max_active_bin_slippage: Some(max_active_bin_slippage),
+        }
+        .data();
+
+        let instructions = [
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ];
+
+        match error {
+            None => {
+                process_and_assert_ok(&instructions, &mock_user, &[&mock_user], &mut banks_client)
+                    .await
+            }
+            Some(error) => {
+                process_and_assert_err(
+                    &instructions,
+                    &mock_user,
+                    &[&mock_user],
+                    &mut banks_client,
+                    error.into(),
+                )
+                .await
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dlmm_swap_builds_with_and_without_host_fee() {
+    let host_fee_in = Pubkey::new_unique();
+
+    for host_fee in [None, Some(host_fee_in)] {
+        let accounts = cpi_example::accounts::DlmmSwap {
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            reserve_x: Pubkey::new_unique(),
+            reserve_y: Pubkey::new_unique(),
+            user_token_in: Pubkey::new_unique(),
+            user_token_out: Pubkey::new_unique(),
+            token_x_mint: Pubkey::new_unique(),
+            token_y_mint: Pubkey::new_unique(),
+            oracle: Pubkey::new_unique(),
+            host_fee_in: host_fee,
+            user: Pubkey::new_unique(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None);
+
+        // The optional account keeps its slot, holding the program ID when omitted
+        assert_eq!(accounts.len(), 15);
+        let host_fee_meta = &accounts[9];
+        match host_fee {
+            Some(host_fee_in) => {
+                assert_eq!(host_fee_meta.pubkey, host_fee_in);
+                assert!(host_fee_meta.is_writable);
+            }
+            None => {
+                assert_eq!(host_fee_meta.pubkey, cpi_example::id());
+                assert!(!host_fee_meta.is_writable);
+            }
+        }
+    }
 }
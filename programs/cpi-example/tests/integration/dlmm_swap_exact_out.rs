@@ -0,0 +1,187 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use cpi_example::utils::derive_active_bin_array;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+struct SwapExactOutContext {
+    banks_client: BanksClient,
+    mock_user: Keypair,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    instruction: Instruction,
+}
+
+/// Sets up the pool and builds a swap_exact_out buying `out_amount` of token Y with token X
+async fn setup_swap_exact_out(max_in_amount: u64, out_amount: u64) -> SwapExactOutContext {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (banks_client, _, _) = test.start().await;
+
+    let ix_data = cpi_example::instruction::DlmmSwapExactOut {
+        max_in_amount,
+        out_amount,
+    }
+    .data();
+
+    let mut accounts = cpi_example::accounts::DlmmSwap {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let (active_bin_array_key, _bump) = derive_active_bin_array(USDC_USDT_POOL, &pool_state);
+
+    accounts.push(AccountMeta::new(active_bin_array_key, false));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    SwapExactOutContext {
+        banks_client,
+        mock_user,
+        user_token_x,
+        user_token_y,
+        instruction,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_exact_out_tight_max_in() {
+    // USDC/USDT trades close to 1:1, allow 1% for price and fees
+    let out_amount = 1_000_000;
+    let max_in_amount = 1_010_000;
+
+    let SwapExactOutContext {
+        mut banks_client,
+        mock_user,
+        user_token_x,
+        user_token_y,
+        instruction,
+    } = setup_swap_exact_out(max_in_amount, out_amount).await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let spent = x_before - get_token_balance(&mut banks_client, user_token_x).await;
+    let received = get_token_balance(&mut banks_client, user_token_y).await - y_before;
+
+    assert_eq!(received, out_amount);
+    assert!(spent <= max_in_amount);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_exact_out_keeps_unspent_input() {
+    // Allow twice the input needed, about half of it should stay with the user
+    let out_amount = 1_000_000;
+    let max_in_amount = 2_000_000;
+
+    let SwapExactOutContext {
+        mut banks_client,
+        mock_user,
+        user_token_x,
+        instruction,
+        ..
+    } = setup_swap_exact_out(max_in_amount, out_amount).await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    let spent = x_before - get_token_balance(&mut banks_client, user_token_x).await;
+    let unused = max_in_amount - spent;
+
+    assert!(unused > 0);
+    assert!(result
+        .metadata
+        .unwrap()
+        .log_messages
+        .contains(&format!("Program log: Unused input amount: {unused}")));
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_exact_out_no_bin_arrays() {
+    let SwapExactOutContext {
+        mut banks_client,
+        mock_user,
+        mut instruction,
+        ..
+    } = setup_swap_exact_out(2_000_000, 1_000_000).await;
+
+    // Drop the active bin array from the remaining accounts
+    instruction.accounts.pop();
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::NoBinArrays.into(),
+    )
+    .await;
+}
@@ -0,0 +1,231 @@
+use crate::helpers;
+use anchor_lang::{
+    error::ErrorCode, solana_program::pubkey::Pubkey, AccountDeserialize, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::state::PriceFeed;
+use cpi_example::utils::derive_active_bin_array;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, system_program,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn initialize_price_feed_ix(price_feed: Pubkey, authority: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializePriceFeed {}.data(),
+        accounts: cpi_example::accounts::DlmmInitializePriceFeed {
+            price_feed,
+            lb_pair: USDC_USDT_POOL,
+            authority,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+struct SwapFeedOracleAccounts {
+    lb_pair: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    user: Pubkey,
+    authority: Pubkey,
+    price_feed: Pubkey,
+}
+
+fn swap_feed_oracle_ix(
+    pool_state: &dlmm::accounts::LbPair,
+    swap: SwapFeedOracleAccounts,
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmSwapFeedOracle {
+        lb_pair: swap.lb_pair,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: swap.user_token_in,
+        user_token_out: swap.user_token_out,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: swap.user,
+        price_feed: swap.price_feed,
+        authority: swap.authority,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let (active_bin_array_key, _bump) = derive_active_bin_array(USDC_USDT_POOL, pool_state);
+
+    accounts.push(AccountMeta::new(active_bin_array_key, false));
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmSwapFeedOracle {
+            amount_in: 1_000_000,
+            min_amount_out: 0,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+async fn read_price_feed(banks_client: &mut BanksClient, price_feed: Pubkey) -> PriceFeed {
+    let account = banks_client.get_account(price_feed).await.unwrap().unwrap();
+    PriceFeed::try_deserialize(&mut account.data.as_ref()).unwrap()
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_feed_oracle() {
+    let mock_user = Keypair::new();
+    let price_feed = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 1. Create the price feed, bound to the pool and the user
+    process_and_assert_ok(
+        &[initialize_price_feed_ix(
+            price_feed.pubkey(),
+            mock_user.pubkey(),
+            mock_user.pubkey(),
+        )],
+        &mock_user,
+        &[&mock_user, &price_feed],
+        &mut banks_client,
+    )
+    .await;
+
+    let price_feed_state = read_price_feed(&mut banks_client, price_feed.pubkey()).await;
+    assert_eq!(price_feed_state.authority, mock_user.pubkey());
+    assert_eq!(price_feed_state.lb_pair, USDC_USDT_POOL);
+    assert_eq!(price_feed_state.token_x_mint, pool_state.token_x_mint);
+    assert_eq!(price_feed_state.token_y_mint, pool_state.token_y_mint);
+
+    // 2. Swap both ways. USDC/USDT trades close to 1:1, so the price stays
+    // close to 1 << 64 in Q64.64 as it is always stored as Y per X.
+    for (user_token_in, user_token_out) in
+        [(user_token_x, user_token_y), (user_token_y, user_token_x)]
+    {
+        let instruction = swap_feed_oracle_ix(
+            &pool_state,
+            SwapFeedOracleAccounts {
+                lb_pair: USDC_USDT_POOL,
+                user_token_in,
+                user_token_out,
+                user: mock_user.pubkey(),
+                authority: mock_user.pubkey(),
+                price_feed: price_feed.pubkey(),
+            },
+        );
+
+        process_and_assert_ok(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction,
+            ],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+        )
+        .await;
+
+        let price_feed_state = read_price_feed(&mut banks_client, price_feed.pubkey()).await;
+        assert!(price_feed_state.last_price > (1u128 << 64) / 2);
+        assert!(price_feed_state.last_price < 2u128 << 64);
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_feed_oracle_rejects_foreign_swaps() {
+    let mock_user = Keypair::new();
+    let feed_authority = Keypair::new();
+    let price_feed = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    process_and_assert_ok(
+        &[initialize_price_feed_ix(
+            price_feed.pubkey(),
+            feed_authority.pubkey(),
+            mock_user.pubkey(),
+        )],
+        &mock_user,
+        &[&mock_user, &feed_authority, &price_feed],
+        &mut banks_client,
+    )
+    .await;
+
+    // A signer other than the feed's authority
+    process_and_assert_err(
+        &[swap_feed_oracle_ix(
+            &pool_state,
+            SwapFeedOracleAccounts {
+                lb_pair: USDC_USDT_POOL,
+                user_token_in: user_token_x,
+                user_token_out: user_token_y,
+                user: mock_user.pubkey(),
+                authority: mock_user.pubkey(),
+                price_feed: price_feed.pubkey(),
+            },
+        )],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        ErrorCode::ConstraintHasOne.into(),
+    )
+    .await;
+
+    // The feed's authority, swapping through another pool
+    process_and_assert_err(
+        &[swap_feed_oracle_ix(
+            &pool_state,
+            SwapFeedOracleAccounts {
+                lb_pair: Pubkey::new_unique(),
+                user_token_in: user_token_x,
+                user_token_out: user_token_y,
+                user: mock_user.pubkey(),
+                authority: feed_authority.pubkey(),
+                price_feed: price_feed.pubkey(),
+            },
+        )],
+        &mock_user,
+        &[&mock_user, &feed_authority],
+        &mut banks_client,
+        ErrorCode::ConstraintHasOne.into(),
+    )
+    .await;
+}
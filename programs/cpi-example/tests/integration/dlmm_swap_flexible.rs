@@ -0,0 +1,140 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm_swap_flexible::SwapMode;
+use cpi_example::utils::derive_active_bin_array;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{get_token_balance, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, transaction::Transaction,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Token balances moved by a flexible swap of token X for token Y, and the amount returned
+struct SwapFlexibleResult {
+    spent_x: u64,
+    received_y: u64,
+    return_amount: u64,
+}
+
+/// Sets up the pool and executes a flexible swap selling token X for token Y
+async fn swap_flexible(mode: SwapMode, amount: u64, limit: u64) -> SwapFlexibleResult {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let ix_data = cpi_example::instruction::DlmmSwapFlexible {
+        mode,
+        amount,
+        limit,
+    }
+    .data();
+
+    let mut accounts = cpi_example::accounts::DlmmSwap {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let (active_bin_array_key, _bump) = derive_active_bin_array(USDC_USDT_POOL, &pool_state);
+
+    accounts.push(AccountMeta::new(active_bin_array_key, false));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        Some(&mock_user.pubkey()),
+        &[&mock_user],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert!(result.result.is_ok());
+
+    let return_data = result.metadata.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, cpi_example::id());
+
+    SwapFlexibleResult {
+        spent_x: x_before - get_token_balance(&mut banks_client, user_token_x).await,
+        received_y: get_token_balance(&mut banks_client, user_token_y).await - y_before,
+        return_amount: u64::from_le_bytes(return_data.data.try_into().unwrap()),
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_flexible_exact_in() {
+    // USDC/USDT trades close to 1:1, allow 1% for price and fees
+    let amount_in = 1_000_000;
+    let min_amount_out = 990_000;
+
+    let SwapFlexibleResult {
+        spent_x,
+        received_y,
+        return_amount,
+    } = swap_flexible(SwapMode::ExactIn, amount_in, min_amount_out).await;
+
+    assert_eq!(spent_x, amount_in);
+    assert!(received_y >= min_amount_out);
+    // The counterpart of an exact input is the output received
+    assert_eq!(return_amount, received_y);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_flexible_exact_out() {
+    let out_amount = 1_000_000;
+    let max_in_amount = 1_010_000;
+
+    let SwapFlexibleResult {
+        spent_x,
+        received_y,
+        return_amount,
+    } = swap_flexible(SwapMode::ExactOut, out_amount, max_in_amount).await;
+
+    assert_eq!(received_y, out_amount);
+    assert!(spent_x <= max_in_amount);
+    // The counterpart of an exact output is the input spent
+    assert_eq!(return_amount, spent_x);
+}
@@ -0,0 +1,194 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    add_packable_account, get_token_balance, process_and_assert_err, process_and_assert_ok,
+    setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+struct SplitContext {
+    banks_client: BanksClient,
+    mock_user: Keypair,
+    user_token_y: Pubkey,
+    recipient_primary: Pubkey,
+    recipient_secondary: Pubkey,
+    instruction: Instruction,
+}
+
+/// Sets up the pool and builds a swap selling 1 token X for token Y, split
+/// between two fresh token Y accounts
+async fn setup_split(split_bps: u16) -> SplitContext {
+    let mock_user = Keypair::new();
+    let recipient_primary = Pubkey::new_unique();
+    let recipient_secondary = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    for recipient in [recipient_primary, recipient_secondary] {
+        let state = TokenAccount {
+            mint: pool_state.token_y_mint,
+            owner: Pubkey::new_unique(),
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+
+        add_packable_account(&mut test, state, anchor_spl::token::ID, recipient);
+    }
+
+    let (banks_client, _, _) = test.start().await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    let (active_bin_array, _bump) =
+        derive_bin_array_pda(USDC_USDT_POOL, active_bin_array_idx.into());
+
+    let mut accounts = cpi_example::accounts::DlmmSwapSplitOutput {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        recipient_primary,
+        recipient_secondary,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(active_bin_array, false));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmSwapSplitOutput {
+            amount_in: 1_000_000,
+            min_amount_out: 0,
+            split_bps,
+        }
+        .data(),
+        accounts,
+    };
+
+    SplitContext {
+        banks_client,
+        mock_user,
+        user_token_y,
+        recipient_primary,
+        recipient_secondary,
+        instruction,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_split_output() {
+    let SplitContext {
+        mut banks_client,
+        mock_user,
+        user_token_y,
+        recipient_primary,
+        recipient_secondary,
+        instruction,
+    } = setup_split(2_500).await;
+
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let primary = get_token_balance(&mut banks_client, recipient_primary).await;
+    let secondary = get_token_balance(&mut banks_client, recipient_secondary).await;
+    let amount_out = primary + secondary;
+
+    // Everything is forwarded, a quarter of it rounded down to the secondary
+    assert!(amount_out > 0);
+    assert_eq!(
+        get_token_balance(&mut banks_client, user_token_y).await,
+        y_before
+    );
+    assert_eq!(secondary, amount_out * 2_500 / 10_000);
+    assert_eq!(primary, amount_out - secondary);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_split_output_invalid_bps() {
+    let SplitContext {
+        mut banks_client,
+        mock_user,
+        instruction,
+        ..
+    } = setup_split(10_001).await;
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBps.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_split_output_same_side() {
+    let SplitContext {
+        mut banks_client,
+        mock_user,
+        user_token_y,
+        mut instruction,
+        ..
+    } = setup_split(2_500).await;
+
+    // Sell from the token Y account into itself. user_token_in is the fifth
+    // account, after lb_pair, the extension placeholder and both reserves.
+    instruction.accounts[4].pubkey = user_token_y;
+
+    process_and_assert_err(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::SwapDirectionMismatch.into(),
+    )
+    .await;
+}
@@ -0,0 +1,210 @@
+use crate::helpers;
+use anchor_lang::{
+    error::ErrorCode, solana_program::pubkey::Pubkey, AccountDeserialize, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::state::VolumeStats;
+use cpi_example::utils::derive_active_bin_array;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{
+    get_token_balance, process_and_assert_err, process_and_assert_ok, setup_cpi_example_program,
+};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer, system_program,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn initialize_volume_stats_ix(stats: Pubkey, authority: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializeVolumeStats {}.data(),
+        accounts: cpi_example::accounts::DlmmInitializeVolumeStats {
+            stats,
+            authority,
+            payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Builds a swap_track_volume selling `amount_in` of token X
+fn swap_track_volume_ix(
+    pool_state: &dlmm::accounts::LbPair,
+    (user_token_x, user_token_y): (Pubkey, Pubkey),
+    user: Pubkey,
+    authority: Pubkey,
+    stats: Pubkey,
+    amount_in: u64,
+) -> Instruction {
+    let mut accounts = cpi_example::accounts::DlmmSwapTrackVolume {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user,
+        stats,
+        authority,
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let (active_bin_array_key, _bump) = derive_active_bin_array(USDC_USDT_POOL, pool_state);
+    accounts.push(AccountMeta::new(active_bin_array_key, false));
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmSwapTrackVolume {
+            amount_in,
+            min_amount_out: 0,
+        }
+        .data(),
+        accounts,
+    }
+}
+
+async fn read_stats(banks_client: &mut BanksClient, stats: Pubkey) -> VolumeStats {
+    let account = banks_client.get_account(stats).await.unwrap().unwrap();
+    VolumeStats::try_deserialize(&mut account.data.as_ref()).unwrap()
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_track_volume() {
+    let mock_user = Keypair::new();
+    let stats = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // 1. Create the stats account, owned by the user
+    process_and_assert_ok(
+        &[initialize_volume_stats_ix(
+            stats.pubkey(),
+            mock_user.pubkey(),
+            mock_user.pubkey(),
+        )],
+        &mock_user,
+        &[&mock_user, &stats],
+        &mut banks_client,
+    )
+    .await;
+
+    // 2. Swap twice, checking the stats against the user's balances each time
+    let mut expected_in = 0u128;
+    let mut expected_out = 0u128;
+
+    for (swap_count, amount_in) in [(1, 1_000_000), (2, 2_000_000)] {
+        let instruction = swap_track_volume_ix(
+            &pool_state,
+            (user_token_x, user_token_y),
+            mock_user.pubkey(),
+            mock_user.pubkey(),
+            stats.pubkey(),
+            amount_in,
+        );
+
+        let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+        let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+        process_and_assert_ok(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                instruction,
+            ],
+            &mock_user,
+            &[&mock_user],
+            &mut banks_client,
+        )
+        .await;
+
+        expected_in +=
+            u128::from(x_before - get_token_balance(&mut banks_client, user_token_x).await);
+        expected_out +=
+            u128::from(get_token_balance(&mut banks_client, user_token_y).await - y_before);
+
+        let stats_state = read_stats(&mut banks_client, stats.pubkey()).await;
+
+        assert_eq!(stats_state.swap_count, swap_count);
+        assert_eq!(stats_state.total_in, expected_in);
+        assert_eq!(stats_state.total_out, expected_out);
+    }
+
+    assert_eq!(expected_in, 3_000_000);
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_track_volume_foreign_signer() {
+    let mock_user = Keypair::new();
+    let stats_authority = Keypair::new();
+    let stats = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    process_and_assert_ok(
+        &[initialize_volume_stats_ix(
+            stats.pubkey(),
+            stats_authority.pubkey(),
+            mock_user.pubkey(),
+        )],
+        &mock_user,
+        &[&mock_user, &stats_authority, &stats],
+        &mut banks_client,
+    )
+    .await;
+
+    // The user signs as authority of stats they don't own
+    process_and_assert_err(
+        &[swap_track_volume_ix(
+            &pool_state,
+            (user_token_x, user_token_y),
+            mock_user.pubkey(),
+            mock_user.pubkey(),
+            stats.pubkey(),
+            1_000_000,
+        )],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        ErrorCode::ConstraintHasOne.into(),
+    )
+    .await;
+
+    let stats_state = read_stats(&mut banks_client, stats.pubkey()).await;
+    assert_eq!(stats_state.swap_count, 0);
+}
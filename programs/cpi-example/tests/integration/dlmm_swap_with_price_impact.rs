@@ -0,0 +1,121 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use cpi_example::utils::derive_active_bin_array;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+/// Sets up the pool and builds a swap_with_price_impact selling 1 token X
+async fn setup_swap(
+    active_id: impl FnOnce(i32) -> Option<i32>,
+    max_price_impact_bps: u16,
+) -> (BanksClient, Keypair, Instruction) {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (banks_client, _, _) = test.start().await;
+
+    let ix_data = cpi_example::instruction::DlmmSwapWithPriceImpact {
+        amount_in: 1_000_000,
+        active_id: active_id(pool_state.active_id),
+        max_price_impact_bps,
+    }
+    .data();
+
+    let mut accounts = cpi_example::accounts::DlmmSwap {
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        user_token_in: user_token_x,
+        user_token_out: user_token_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        oracle: pool_state.oracle,
+        host_fee_in: None,
+        user: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let (active_bin_array_key, _bump) = derive_active_bin_array(USDC_USDT_POOL, &pool_state);
+
+    accounts.push(AccountMeta::new(active_bin_array_key, false));
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: ix_data,
+        accounts,
+    };
+
+    (banks_client, mock_user, instruction)
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_with_price_impact_some_active_id() {
+    let (mut banks_client, mock_user, instruction) = setup_swap(Some, 100).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_with_price_impact_none_active_id() {
+    let (mut banks_client, mock_user, instruction) = setup_swap(|_| None, 100).await;
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_swap_with_price_impact_invalid_bps() {
+    let (mut banks_client, mock_user, instruction) = setup_swap(Some, 10_001).await;
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBps.into(),
+    )
+    .await;
+}
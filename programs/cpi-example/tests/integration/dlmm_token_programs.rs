@@ -0,0 +1,132 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use bytemuck::Zeroable;
+use cpi_example::dlmm;
+use cpi_example::dlmm_token_programs::PoolTokenPrograms;
+use cpi_example::errors::DlmmCpiError;
+use helpers::{process_and_assert_err, setup_cpi_example_program, simulate_and_get_return_data};
+use solana_program_test::*;
+use solana_sdk::{account::Account, instruction::Instruction, signature::Keypair, signer::Signer};
+
+struct MixedPool {
+    lb_pair: Pubkey,
+    token_x_mint: Pubkey,
+    token_y_mint: Pubkey,
+}
+
+/// Pool whose token X is an spl-token mint and token Y a Token-2022 mint
+fn setup_mixed_pool(test: &mut ProgramTest) -> MixedPool {
+    let pool = MixedPool {
+        lb_pair: Pubkey::new_unique(),
+        token_x_mint: Pubkey::new_unique(),
+        token_y_mint: Pubkey::new_unique(),
+    };
+
+    let mut pool_state = dlmm::accounts::LbPair::zeroed();
+    pool_state.token_x_mint = pool.token_x_mint;
+    pool_state.token_y_mint = pool.token_y_mint;
+
+    let mut data = dlmm::accounts::LbPair::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&pool_state));
+
+    for (key, data, owner) in [
+        (pool.lb_pair, data, dlmm::ID),
+        (pool.token_x_mint, vec![0; 82], anchor_spl::token::ID),
+        (pool.token_y_mint, vec![0; 82], anchor_spl::token_2022::ID),
+    ] {
+        test.add_account(
+            key,
+            Account {
+                lamports: u32::MAX.into(),
+                data,
+                owner,
+                ..Default::default()
+            },
+        );
+    }
+
+    pool
+}
+
+fn token_programs_ix(lb_pair: Pubkey, token_x_mint: Pubkey, token_y_mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmTokenPrograms {}.data(),
+        accounts: cpi_example::accounts::DlmmTokenPrograms {
+            lb_pair,
+            token_x_mint,
+            token_y_mint,
+        }
+        .to_account_metas(None),
+    }
+}
+
+fn setup_payer(test: &mut ProgramTest, payer: &Keypair) {
+    test.add_account(
+        payer.pubkey(),
+        Account {
+            lamports: u32::MAX.into(),
+            data: vec![],
+            owner: solana_sdk::system_program::ID,
+            ..Default::default()
+        },
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_token_programs_mixed_pool() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+    let pool = setup_mixed_pool(&mut test);
+    setup_payer(&mut test, &mock_user);
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let return_data = simulate_and_get_return_data(
+        &[token_programs_ix(
+            pool.lb_pair,
+            pool.token_x_mint,
+            pool.token_y_mint,
+        )],
+        &mock_user,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+
+    assert_eq!(
+        PoolTokenPrograms::try_from_slice(&return_data).unwrap(),
+        PoolTokenPrograms {
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token_2022::ID,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_dlmm_token_programs_mints_swapped() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+    let pool = setup_mixed_pool(&mut test);
+    setup_payer(&mut test, &mock_user);
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    process_and_assert_err(
+        &[token_programs_ix(
+            pool.lb_pair,
+            pool.token_y_mint,
+            pool.token_x_mint,
+        )],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::PoolMintMismatch.into(),
+    )
+    .await;
+}
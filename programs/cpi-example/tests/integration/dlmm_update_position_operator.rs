@@ -0,0 +1,82 @@
+use crate::helpers;
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::PositionV2;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+fn update_position_operator_ix(owner: Pubkey, position: Pubkey, operator: Pubkey) -> Instruction {
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmUpdatePositionOperator { operator }.data(),
+        accounts: cpi_example::accounts::DlmmUpdatePositionOperator {
+            position,
+            owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+#[test]
+fn test_dlmm_update_position_operator_data() {
+    let operator = Pubkey::new_unique();
+    let instruction =
+        update_position_operator_ix(Pubkey::new_unique(), Pubkey::new_unique(), operator);
+
+    let discriminator = cpi_example::instruction::DlmmUpdatePositionOperator::DISCRIMINATOR;
+    assert_eq!(&instruction.data[..discriminator.len()], discriminator);
+
+    let decoded = cpi_example::instruction::DlmmUpdatePositionOperator::try_from_slice(
+        &instruction.data[discriminator.len()..],
+    )
+    .unwrap();
+    assert_eq!(decoded.operator, operator);
+}
+
+#[tokio::test]
+async fn test_dlmm_update_position_operator() {
+    let mock_user = Keypair::new();
+    let operator = Pubkey::new_unique();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext { pool_state, .. } =
+        setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id,
+        10,
+    )
+    .await;
+
+    let instruction = update_position_operator_ix(mock_user.pubkey(), position.position, operator);
+
+    process_and_assert_ok(&[instruction], &mock_user, &[&mock_user], &mut banks_client).await;
+
+    let account = banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap();
+    let state: PositionV2 = read_zero_copy_account(&account.data);
+    assert_eq!(state.operator, operator);
+}
@@ -0,0 +1,201 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::dlmm::types::BinLiquidityReduction;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+struct WrongBinArrayContext {
+    banks_client: BanksClient,
+    mock_user: Keypair,
+    pool_state: LbPair,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    position: PositionContext,
+    wrong_bin_array: Pubkey,
+}
+
+/// Creates an empty position and a bin array PDA of the pool that does not
+/// cover the position's lower bin
+async fn setup_wrong_bin_array() -> WrongBinArrayContext {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id + 1,
+        10,
+    )
+    .await;
+
+    let lower_idx = bin_id_to_bin_array_index(position.lower_bin_id).unwrap();
+    let (wrong_bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, (lower_idx - 1).into());
+
+    WrongBinArrayContext {
+        banks_client,
+        mock_user,
+        pool_state,
+        user_token_x,
+        user_token_y,
+        position,
+        wrong_bin_array,
+    }
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_liquidity_wrong_bin_array() {
+    let WrongBinArrayContext {
+        mut banks_client,
+        mock_user,
+        pool_state,
+        user_token_x,
+        user_token_y,
+        position,
+        wrong_bin_array,
+    } = setup_wrong_bin_array().await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveLiquidity {
+            bin_liquidity_removal: vec![BinLiquidityReduction {
+                bin_id: position.lower_bin_id,
+                bps_to_remove: 10_000,
+            }],
+            min_amount_x: 0,
+            min_amount_y: 0,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmRemoveLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: wrong_bin_array,
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBinArrayPda.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_remove_all_liquidity_wrong_bin_array() {
+    let WrongBinArrayContext {
+        mut banks_client,
+        mock_user,
+        pool_state,
+        user_token_x,
+        user_token_y,
+        position,
+        wrong_bin_array,
+    } = setup_wrong_bin_array().await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmRemoveAllLiquidity {}.data(),
+        accounts: cpi_example::accounts::DlmmRemoveAllLiquidity {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_bitmap_extension: None,
+            user_token_x,
+            user_token_y,
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            bin_array_lower: wrong_bin_array,
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_x_program: anchor_spl::token::ID,
+            token_y_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBinArrayPda.into(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dlmm_close_position_wrong_bin_array() {
+    let WrongBinArrayContext {
+        mut banks_client,
+        mock_user,
+        position,
+        wrong_bin_array,
+        ..
+    } = setup_wrong_bin_array().await;
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClosePosition {}.data(),
+        accounts: cpi_example::accounts::DlmmClosePosition {
+            position: position.position,
+            lb_pair: USDC_USDT_POOL,
+            bin_array_lower: wrong_bin_array,
+            bin_array_upper: position.bin_array_upper(),
+            sender: mock_user.pubkey(),
+            rent_receiver: Some(mock_user.pubkey()),
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+        DlmmCpiError::InvalidBinArrayPda.into(),
+    )
+    .await;
+}
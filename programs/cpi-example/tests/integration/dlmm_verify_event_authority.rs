@@ -0,0 +1,60 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::errors::DlmmCpiError;
+use helpers::dlmm_utils::*;
+use helpers::{process_and_assert_err, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_claim_fee_wrong_event_authority() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    // The event authority is checked first, so nothing else needs to exist
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmClaimFee {}.data(),
+        accounts: cpi_example::accounts::DlmmClaimFee {
+            lb_pair: USDC_USDT_POOL,
+            position: Pubkey::new_unique(),
+            bin_array_lower: Pubkey::new_unique(),
+            bin_array_upper: Pubkey::new_unique(),
+            sender: mock_user.pubkey(),
+            reserve_x: pool_state.reserve_x,
+            reserve_y: pool_state.reserve_y,
+            user_token_x,
+            user_token_y,
+            token_x_mint: pool_state.token_x_mint,
+            token_y_mint: pool_state.token_y_mint,
+            token_program: anchor_spl::token::ID,
+            event_authority: Pubkey::new_unique(),
+            dlmm_program: dlmm::ID,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_err(
+        &[instruction],
+        &mock_user,
+        &[],
+        &mut banks_client,
+        DlmmCpiError::InvalidEventAuthority.into(),
+    )
+    .await;
+}
@@ -0,0 +1,77 @@
+use anchor_lang::{
+    solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator, InstructionData,
+    ToAccountMetas,
+};
+use cpi_example::dlmm;
+use solana_sdk::instruction::AccountMeta;
+
+#[test]
+fn test_dlmm_withdraw_protocol_fee_data() {
+    let data = cpi_example::instruction::DlmmWithdrawProtocolFee {
+        max_amount_x: 1_000,
+        max_amount_y: u64::MAX,
+    }
+    .data();
+
+    let discriminator = cpi_example::instruction::DlmmWithdrawProtocolFee::DISCRIMINATOR;
+    assert_eq!(&data[..discriminator.len()], discriminator);
+
+    let decoded = cpi_example::instruction::DlmmWithdrawProtocolFee::try_from_slice(
+        &data[discriminator.len()..],
+    )
+    .unwrap();
+    assert_eq!(decoded.max_amount_x, 1_000);
+    assert_eq!(decoded.max_amount_y, u64::MAX);
+}
+
+#[test]
+fn test_dlmm_withdraw_protocol_fee_accounts_match_dlmm_withdraw_protocol_fee() {
+    let lb_pair = Pubkey::new_unique();
+    let reserve_x = Pubkey::new_unique();
+    let reserve_y = Pubkey::new_unique();
+    let token_x_mint = Pubkey::new_unique();
+    let token_y_mint = Pubkey::new_unique();
+    let receiver_token_x = Pubkey::new_unique();
+    let receiver_token_y = Pubkey::new_unique();
+    let fee_owner = Pubkey::new_unique();
+
+    let mut accounts = cpi_example::accounts::DlmmWithdrawProtocolFee {
+        lb_pair,
+        reserve_x,
+        reserve_y,
+        token_x_mint,
+        token_y_mint,
+        receiver_token_x,
+        receiver_token_y,
+        fee_owner,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+        dlmm_program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    // The fee owner signs our instruction but is not passed to DLMM
+    assert_eq!(
+        accounts.remove(7),
+        AccountMeta::new_readonly(fee_owner, true)
+    );
+    assert_eq!(
+        accounts.pop(),
+        Some(AccountMeta::new_readonly(dlmm::ID, false))
+    );
+
+    let dlmm_accounts = dlmm::client::accounts::WithdrawProtocolFee {
+        lb_pair,
+        reserve_x,
+        reserve_y,
+        token_x_mint,
+        token_y_mint,
+        receiver_token_x,
+        receiver_token_y,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    assert_eq!(accounts, dlmm_accounts);
+}
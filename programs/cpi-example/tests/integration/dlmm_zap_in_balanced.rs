@@ -0,0 +1,131 @@
+use crate::helpers;
+use anchor_lang::{solana_program::pubkey::Pubkey, InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use helpers::dlmm_pda::*;
+use helpers::dlmm_position::*;
+use helpers::dlmm_utils::*;
+use helpers::{get_token_balance, process_and_assert_ok, setup_cpi_example_program};
+use solana_program_test::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Keypair,
+    signer::Signer,
+};
+
+const USDC_USDT_POOL: Pubkey = solana_sdk::pubkey!("ARwi1S4DaiTG5DX7S4M4ZsrXqpMD1MrTmbu9ue2tpmEq");
+
+#[tokio::test]
+async fn test_dlmm_zap_in_balanced() {
+    let mock_user = Keypair::new();
+
+    let mut test = setup_cpi_example_program();
+
+    test.prefer_bpf(true);
+    test.add_program("dlmm", dlmm::ID, None);
+
+    let PoolSetupContext {
+        pool_state,
+        user_token_x,
+        user_token_y,
+    } = setup_pool_from_cluster(&mut test, USDC_USDT_POOL, mock_user.pubkey()).await;
+
+    let active_bin_array_idx = bin_id_to_bin_array_index(pool_state.active_id).unwrap();
+    add_bin_arrays_from_cluster(
+        &mut test,
+        USDC_USDT_POOL,
+        &[active_bin_array_idx - 1, active_bin_array_idx + 1],
+    )
+    .await;
+
+    let (mut banks_client, _, _) = test.start().await;
+
+    let range = 5;
+    let position = initialize_position(
+        &mut banks_client,
+        &mock_user,
+        USDC_USDT_POOL,
+        pool_state.active_id - range,
+        2 * range + 1,
+    )
+    .await;
+
+    let x_before = get_token_balance(&mut banks_client, user_token_x).await;
+    let y_before = get_token_balance(&mut banks_client, user_token_y).await;
+
+    let amount_in_x = 1_000_000;
+
+    let mut accounts = cpi_example::accounts::DlmmZapInBalanced {
+        position: position.position,
+        lb_pair: USDC_USDT_POOL,
+        bin_array_bitmap_extension: None,
+        user_token_x,
+        user_token_y,
+        reserve_x: pool_state.reserve_x,
+        reserve_y: pool_state.reserve_y,
+        token_x_mint: pool_state.token_x_mint,
+        token_y_mint: pool_state.token_y_mint,
+        bin_array_lower: position.bin_array_lower(),
+        bin_array_upper: position.bin_array_upper(),
+        oracle: pool_state.oracle,
+        sender: mock_user.pubkey(),
+        dlmm_program: dlmm::ID,
+        event_authority: derive_event_authority_pda().0,
+        token_x_program: anchor_spl::token::ID,
+        token_y_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    // Selling token X moves the active bin down
+    for idx in [active_bin_array_idx, active_bin_array_idx - 1] {
+        let (bin_array, _bump) = derive_bin_array_pda(USDC_USDT_POOL, idx.into());
+        accounts.push(AccountMeta::new(bin_array, false));
+    }
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmZapInBalanced {
+            amount_in_x,
+            range,
+            min_swap_out: 0,
+        }
+        .data(),
+        accounts,
+    };
+
+    process_and_assert_ok(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction,
+        ],
+        &mock_user,
+        &[&mock_user],
+        &mut banks_client,
+    )
+    .await;
+
+    let spent_x = x_before - get_token_balance(&mut banks_client, user_token_x).await;
+    let y_after = get_token_balance(&mut banks_client, user_token_y).await;
+
+    // Token X is swapped or deposited, and the swapped token Y is deposited
+    // in turn, up to the rounding dust of the strategy
+    assert!(amount_in_x - spent_x <= amount_in_x / 100);
+    assert!(y_after.abs_diff(y_before) <= amount_in_x / 100);
+
+    let position_account = banks_client
+        .get_account(position.position)
+        .await
+        .unwrap()
+        .unwrap();
+    let position_state: dlmm::accounts::PositionV2 = read_zero_copy_account(&position_account.data);
+    let offset = (pool_state.active_id - position.lower_bin_id) as usize;
+
+    // Both sides of the active bin received liquidity
+    assert!(position_state.liquidity_shares[..offset]
+        .iter()
+        .any(|share| *share > 0));
+    assert!(
+        position_state.liquidity_shares[offset + 1..=2 * range as usize]
+            .iter()
+            .any(|share| *share > 0)
+    );
+}
@@ -0,0 +1,150 @@
+use super::dlmm_pda::*;
+use super::dlmm_utils::bin_id_to_bin_array_index;
+use super::process_and_assert_ok;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use cpi_example::dlmm;
+use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::dlmm::types::BinLiquidityDistributionByWeight;
+use solana_program_test::BanksClient;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, system_program, sysvar,
+};
+
+pub struct PositionContext {
+    pub lb_pair: Pubkey,
+    pub position: Pubkey,
+    pub lower_bin_id: i32,
+    pub width: i32,
+}
+
+impl PositionContext {
+    pub fn upper_bin_id(&self) -> i32 {
+        self.lower_bin_id + self.width - 1
+    }
+
+    pub fn bin_array_lower(&self) -> Pubkey {
+        let idx = bin_id_to_bin_array_index(self.lower_bin_id).unwrap();
+        derive_bin_array_pda(self.lb_pair, idx.into()).0
+    }
+
+    pub fn bin_array_upper(&self) -> Pubkey {
+        let idx = bin_id_to_bin_array_index(self.upper_bin_id()).unwrap();
+        derive_bin_array_pda(self.lb_pair, idx.into()).0
+    }
+}
+
+/// Create a position owned by `owner` through the cpi example program
+pub async fn initialize_position(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    lb_pair: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> PositionContext {
+    let base = Keypair::new();
+    let (position, _bump) = derive_position_pda(lb_pair, base.pubkey(), lower_bin_id, width);
+
+    let instruction = Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmInitializePositionPdaIdempotent {
+            lower_bin_id,
+            width,
+            base: base.pubkey(),
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmInitializePositionPdaIdempotent {
+            payer: owner.pubkey(),
+            base: base.pubkey(),
+            position,
+            lb_pair,
+            owner: owner.pubkey(),
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+        }
+        .to_account_metas(None),
+    };
+
+    process_and_assert_ok(&[instruction], owner, &[owner, &base], banks_client).await;
+
+    PositionContext {
+        lb_pair,
+        position,
+        lower_bin_id,
+        width,
+    }
+}
+
+/// Build the instruction of `add_liquidity_one_side` below.
+/// The position must sit entirely above the active bin for token X, or at/below it for token Y.
+pub fn add_liquidity_one_side_ix(
+    owner: Pubkey,
+    pool_state: &LbPair,
+    position: &PositionContext,
+    user_token: Pubkey,
+    deposit_x: bool,
+    amount: u64,
+) -> Instruction {
+    let (reserve, token_mint) = if deposit_x {
+        (pool_state.reserve_x, pool_state.token_x_mint)
+    } else {
+        (pool_state.reserve_y, pool_state.token_y_mint)
+    };
+
+    let bin_liquidity_dist = (position.lower_bin_id..=position.upper_bin_id())
+        .map(|bin_id| BinLiquidityDistributionByWeight { bin_id, weight: 1 })
+        .collect();
+
+    Instruction {
+        program_id: cpi_example::id(),
+        data: cpi_example::instruction::DlmmAddLiquidityOneSide {
+            amount,
+            active_id: pool_state.active_id,
+            max_active_bin_slippage: 3,
+            bin_liquidity_dist,
+            min_net_amount: None,
+            max_weight_per_bin: None,
+        }
+        .data(),
+        accounts: cpi_example::accounts::DlmmAddLiquidityOneSide {
+            position: position.position,
+            lb_pair: position.lb_pair,
+            bin_array_bitmap_extension: None,
+            user_token,
+            reserve,
+            token_mint,
+            bin_array_lower: position.bin_array_lower(),
+            bin_array_upper: position.bin_array_upper(),
+            sender: owner,
+            dlmm_program: dlmm::ID,
+            event_authority: derive_event_authority_pda().0,
+            token_program: anchor_spl::token::ID,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Deposit `amount` of a single token evenly across every bin of the position.
+/// The position must sit entirely above the active bin for token X, or at/below it for token Y.
+pub async fn add_liquidity_one_side(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    pool_state: &LbPair,
+    position: &PositionContext,
+    user_token: Pubkey,
+    deposit_x: bool,
+    amount: u64,
+) {
+    let instruction = add_liquidity_one_side_ix(
+        owner.pubkey(),
+        pool_state,
+        position,
+        user_token,
+        deposit_x,
+        amount,
+    );
+
+    process_and_assert_ok(&[instruction], owner, &[owner], banks_client).await;
+}
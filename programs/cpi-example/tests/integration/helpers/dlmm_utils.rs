@@ -3,11 +3,11 @@ use anchor_lang::prelude::Pubkey;
 use anchor_lang::{AccountDeserialize, Discriminator};
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::spl_token::state::AccountState;
-use cpi_example::dlmm::accounts::LbPair;
+use cpi_example::dlmm::accounts::{BinArray, LbPair};
 use cpi_example::dlmm::types::{ProtocolFee, RewardInfo, StaticParameters, VariableParameters};
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_program_test::ProgramTest;
-use solana_sdk::account::Account;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
 
 use super::utils::add_packable_account;
 use super::RPC;
@@ -149,6 +149,11 @@ impl AccountDeserialize for BorshLbPairWrapper {
     }
 }
 
+/// Read a zero copy DLMM account (PositionV2, BinArray, ...) regardless of buffer alignment
+pub fn read_zero_copy_account<T: bytemuck::Pod>(data: &[u8]) -> T {
+    bytemuck::pod_read_unaligned(&data[8..8 + std::mem::size_of::<T>()])
+}
+
 /// Get bin array index from bin id
 pub fn bin_id_to_bin_array_index(bin_id: i32) -> Option<i32> {
     use cpi_example::dlmm::constants::MAX_BIN_PER_ARRAY;
@@ -162,6 +167,60 @@ pub fn bin_id_to_bin_array_index(bin_id: i32) -> Option<i32> {
     }
 }
 
+/// Bump the fee per token of `bin_id` by `growth_x` and `growth_y` (Q64.64),
+/// as if swaps through the bin had collected fees.
+pub async fn add_bin_fee_growth(
+    context: &mut ProgramTestContext,
+    lb_pair: Pubkey,
+    bin_id: i32,
+    growth_x: u128,
+    growth_y: u128,
+) {
+    let idx = bin_id_to_bin_array_index(bin_id).unwrap();
+    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, idx.into());
+
+    let mut account = context
+        .banks_client
+        .get_account(bin_array)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut state: BinArray = read_zero_copy_account(&account.data);
+
+    let bin = &mut state.bins[(bin_id - idx * 70) as usize];
+    bin.fee_amount_x_per_token_stored += growth_x;
+    bin.fee_amount_y_per_token_stored += growth_y;
+
+    account.data[8..8 + std::mem::size_of::<BinArray>()]
+        .copy_from_slice(bytemuck::bytes_of(&state));
+    context.set_account(&bin_array, &AccountSharedData::from(account));
+}
+
+/// Clone the given bin arrays of the pool from the cluster into the test validator
+pub async fn add_bin_arrays_from_cluster(
+    test: &mut ProgramTest,
+    pool: Pubkey,
+    bin_array_indexes: &[i32],
+) {
+    let rpc_client = RpcClient::new(RPC.to_owned());
+
+    let bin_array_keys = bin_array_indexes
+        .iter()
+        .map(|idx| derive_bin_array_pda(pool, (*idx).into()).0)
+        .collect::<Vec<_>>();
+
+    let bin_arrays = rpc_client
+        .get_multiple_accounts(&bin_array_keys)
+        .await
+        .unwrap();
+
+    for (key, account) in bin_array_keys.into_iter().zip(bin_arrays) {
+        if let Some(account) = account {
+            test.add_account(key, account);
+        }
+    }
+}
+
 pub struct PoolSetupContext {
     pub pool_state: LbPair,
     pub user_token_x: Pubkey,
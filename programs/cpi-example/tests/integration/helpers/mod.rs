@@ -3,6 +3,7 @@ use solana_program_test::*;
 use solana_sdk::{entrypoint::ProgramResult, pubkey::Pubkey};
 
 pub mod dlmm_pda;
+pub mod dlmm_position;
 pub mod dlmm_utils;
 pub mod dynamic_amm_ix_account_builder;
 pub mod dynamic_amm_pda;
@@ -15,7 +16,10 @@ mod dynamic_vault_aux_lp_mint;
 
 mod utils;
 
-pub use utils::process_and_assert_ok;
+pub use utils::{
+    add_packable_account, decode_event, get_token_balance, process_and_assert_err,
+    process_and_assert_ok, process_and_get_logs, simulate_and_get_return_data,
+};
 const RPC: &str = "https://api.mainnet-beta.solana.com";
 
 pub const JUP: Pubkey = solana_sdk::pubkey!("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN");
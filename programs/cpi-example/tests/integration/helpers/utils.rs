@@ -1,12 +1,15 @@
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AnchorDeserialize, Discriminator};
 use assert_matches::assert_matches;
-use solana_program_test::{BanksClient, ProgramTest};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program_test::{BanksClient, BanksClientError, ProgramTest};
 use solana_sdk::{
     account::Account,
+    instruction::InstructionError,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 
 pub async fn process_and_assert_ok(
@@ -30,6 +33,115 @@ pub async fn process_and_assert_ok(
     assert_matches!(banks_client.process_transaction(tx).await, Ok(()));
 }
 
+pub async fn process_and_assert_err(
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+    banks_client: &mut BanksClient,
+    expected_error_code: u32,
+) {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    assert_matches!(
+        banks_client.process_transaction(tx).await,
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code)
+        ))) if code == expected_error_code
+    );
+}
+
+pub async fn simulate_and_get_return_data(
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+    banks_client: &mut BanksClient,
+) -> Vec<u8> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    let simulation = banks_client.simulate_transaction(tx).await.unwrap();
+    assert_matches!(simulation.result, Some(Ok(())));
+
+    simulation
+        .simulation_details
+        .and_then(|details| details.return_data)
+        .map(|return_data| return_data.data)
+        .unwrap_or_default()
+}
+
+pub async fn process_and_get_logs(
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+    banks_client: &mut BanksClient,
+) -> Vec<String> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    let result = banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    assert_matches!(result.result, Ok(()));
+
+    result.metadata.unwrap().log_messages
+}
+
+/// Decode the first event of type `T` emitted with `emit!` from the transaction logs
+pub fn decode_event<T: AnchorDeserialize + Discriminator>(log_messages: &[String]) -> Option<T> {
+    log_messages
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .find_map(|data| {
+            let payload = data.strip_prefix(T::DISCRIMINATOR)?;
+            T::try_from_slice(payload).ok()
+        })
+}
+
+pub async fn get_token_balance(banks_client: &mut BanksClient, token_account: Pubkey) -> u64 {
+    let account = banks_client
+        .get_account(token_account)
+        .await
+        .unwrap()
+        .unwrap();
+
+    anchor_spl::token::spl_token::state::Account::unpack(
+        &account.data[..anchor_spl::token::spl_token::state::Account::LEN],
+    )
+    .unwrap()
+    .amount
+}
+
 pub fn add_packable_account<T: Pack>(
     test: &mut ProgramTest,
     account: T,
@@ -1,6 +1,71 @@
 mod helpers;
 
+mod dlmm_activation_info;
+mod dlmm_add_liquidity;
+mod dlmm_add_liquidity_by_strategy;
+mod dlmm_add_liquidity_by_strategy_auto;
+mod dlmm_add_liquidity_one_side;
+mod dlmm_add_liquidity_smart;
+mod dlmm_add_price_impact;
+mod dlmm_batch_close;
+mod dlmm_batch_read_positions;
+mod dlmm_batch_remove_liquidity;
+mod dlmm_bootstrap_position;
+mod dlmm_claim_all_rewards;
+mod dlmm_claim_fee;
+mod dlmm_claim_reward;
+mod dlmm_close_position;
+mod dlmm_estimate_apr;
+mod dlmm_events;
+mod dlmm_exit_position;
+mod dlmm_exit_preview;
+mod dlmm_initialize_bin_array;
+mod dlmm_initialize_bin_array_bitmap_extension;
+mod dlmm_initialize_lb_pair;
+mod dlmm_initialize_position;
+mod dlmm_initialize_position_pda;
+mod dlmm_initialize_position_pda_idempotent;
+mod dlmm_last_activity;
+mod dlmm_max_input_for_bins;
+mod dlmm_merge_positions;
+mod dlmm_open_and_add;
+mod dlmm_open_centered;
+mod dlmm_open_cost;
+mod dlmm_open_wide;
+mod dlmm_pool_depth;
+mod dlmm_pool_has_mint;
+mod dlmm_position_price_ladder;
+mod dlmm_position_value_in_quote;
+mod dlmm_quote_swap;
+mod dlmm_read_authorities;
+mod dlmm_read_fee_growth;
+mod dlmm_read_lb_pair;
+mod dlmm_read_oracle;
+mod dlmm_read_position_fees;
+mod dlmm_read_protocol_fees;
+mod dlmm_remove_and_balance;
+mod dlmm_remove_liquidity;
+mod dlmm_remove_liquidity_by_range;
+mod dlmm_remove_liquidity_guarded;
+mod dlmm_remove_liquidity_signed;
+mod dlmm_shift_liquidity;
+mod dlmm_suggest_one_side_dist;
 mod dlmm_swap;
+mod dlmm_swap_checked;
+mod dlmm_swap_exact_out;
+mod dlmm_swap_feed_oracle;
+mod dlmm_swap_flexible;
+mod dlmm_swap_split_output;
+mod dlmm_swap_track_volume;
+mod dlmm_swap_with_price_impact;
+mod dlmm_token_programs;
+mod dlmm_update_position_operator;
+mod dlmm_verify_bin_array;
+mod dlmm_verify_dlmm_owned;
+mod dlmm_verify_event_authority;
+mod dlmm_verify_reserves;
+mod dlmm_withdraw_protocol_fee;
+mod dlmm_zap_in_balanced;
 mod dynamic_amm_claim_fee;
 mod dynamic_amm_init_pool;
 mod dynamic_amm_lock_liquidity;